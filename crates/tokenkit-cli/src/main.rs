@@ -0,0 +1,490 @@
+use clap::{Parser, ValueEnum};
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tokenkit_core::{
+    encoding, from_config, InvalidUtf8Policy, Token, TokenFilter, TokenStream, TokenizerConfig, TokenizerStrategy,
+};
+
+// Same read size the gem uses when streaming an IO
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Tokenize text from files or stdin.
+///
+/// Input is streamed in chunks, so arbitrarily large files can be piped
+/// through. Token offsets are byte offsets into each input. The bytes
+/// strategy reads any input as bytes and writes its tokens back as bytes.
+#[derive(Parser, Debug)]
+#[command(name = "tokenkit", version)]
+struct Cli {
+    /// Files to tokenize; reads stdin when none are given or for `-`
+    files: Vec<PathBuf>,
+
+    /// Tokenization strategy
+    #[arg(short, long, value_enum, default_value_t = Strategy::Unicode)]
+    strategy: Strategy,
+
+    /// Versioned JSON or YAML config file (see docs/CONFIG_FILES.md), used
+    /// instead of the strategy options
+    #[arg(
+        short,
+        long,
+        value_name = "FILE",
+        conflicts_with_all = [
+            "strategy", "regex", "no_extended", "min_gram", "max_gram", "delimiter",
+            "split_on_chars", "dictionary", "user_dictionary", "search_mode", "no_hmm", "tokenizer",
+            "no_special_tokens", "no_lowercase", "locale", "remove_punctuation",
+            "preserve", "char_filter", "filter", "output_unigrams", "invalid_utf8",
+        ]
+    )]
+    config: Option<PathBuf>,
+
+    /// Regex matching tokens (pattern strategy)
+    #[arg(long, required_if_eq("strategy", "pattern"))]
+    regex: Option<String>,
+
+    /// Split into legacy instead of extended grapheme clusters (grapheme strategy)
+    #[arg(long)]
+    no_extended: bool,
+
+    /// Shortest gram (ngram and edge_ngram strategies)
+    #[arg(long, default_value_t = 2)]
+    min_gram: usize,
+
+    /// Longest gram (ngram and edge_ngram strategies)
+    #[arg(long, default_value_t = 10)]
+    max_gram: usize,
+
+    /// Path separator (path_hierarchy strategy)
+    #[arg(long, default_value = "/")]
+    delimiter: String,
+
+    /// Characters to split on (char_group strategy)
+    #[arg(long, default_value = " \t\n\r")]
+    split_on_chars: String,
+
+    /// Frequency dictionary replacing the bundled one (cjk strategy), or
+    /// dictionary directory (japanese strategy)
+    #[arg(long, value_name = "PATH", required_if_eq("strategy", "japanese"))]
+    dictionary: Option<PathBuf>,
+
+    /// Extra dictionary loaded on top (cjk and japanese strategies, repeatable)
+    #[arg(long, value_name = "FILE")]
+    user_dictionary: Vec<PathBuf>,
+
+    /// Also emit the words inside long words (cjk strategy)
+    #[arg(long)]
+    search_mode: bool,
+
+    /// Don't guess words missing from the dictionary (cjk strategy)
+    #[arg(long)]
+    no_hmm: bool,
+
+    /// Hugging Face tokenizer.json file (huggingface strategy)
+    #[arg(long, value_name = "FILE", required_if_eq("strategy", "huggingface"))]
+    tokenizer: Option<PathBuf>,
+
+    /// Leave out special tokens such as [CLS] and [SEP] (huggingface strategy)
+    #[arg(long)]
+    no_special_tokens: bool,
+
+    /// Keep the original case of tokens
+    #[arg(long)]
+    no_lowercase: bool,
+
+    /// Lowercase with the case rules of this language, such as tr, az or lt
+    #[arg(long, value_name = "TAG")]
+    locale: Option<String>,
+
+    /// Strip ASCII punctuation from tokens
+    #[arg(long)]
+    remove_punctuation: bool,
+
+    /// Regex whose matches are kept as single, unmodified tokens (repeatable)
+    #[arg(short, long, value_name = "REGEX")]
+    preserve: Vec<String>,
+
+    /// Char filter applied to the input before the strategy (repeatable, in order)
+    #[arg(long, value_enum, value_name = "FILTER")]
+    char_filter: Vec<CharFilter>,
+
+    /// Token filter applied after the strategy (repeatable, in order)
+    #[arg(long, value_enum, value_name = "FILTER")]
+    filter: Vec<Filter>,
+
+    /// Also emit single characters (cjk_bigram filter)
+    #[arg(long)]
+    output_unigrams: bool,
+
+    /// What to do with input that isn't valid UTF-8
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t = InvalidUtf8::Raise)]
+    invalid_utf8: InvalidUtf8,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Lines)]
+    format: Format,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "snake_case")]
+enum Strategy {
+    Unicode,
+    Whitespace,
+    Pattern,
+    Sentence,
+    Grapheme,
+    Keyword,
+    EdgeNgram,
+    Ngram,
+    PathHierarchy,
+    UrlEmail,
+    CharGroup,
+    Letter,
+    Lowercase,
+    Bytes,
+    Cjk,
+    Japanese,
+    #[value(name = "huggingface")]
+    HuggingFace,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "snake_case")]
+enum Filter {
+    CjkBigram,
+    ArabicNormalization,
+    PersianNormalization,
+    HebrewNormalization,
+    ArabicStem,
+    IndicNormalization,
+    CjkWidth,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+#[value(rename_all = "snake_case")]
+enum CharFilter {
+    CjkWidth,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum InvalidUtf8 {
+    /// Stop with an error
+    Raise,
+    /// Replace each invalid sequence with U+FFFD
+    Replace,
+    /// Leave invalid bytes out
+    Skip,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Format {
+    /// One token per line
+    Lines,
+    /// One JSON array of tokens per input
+    Json,
+    /// One JSON object per token, with byte offsets (and part-of-speech
+    /// attributes from the japanese strategy)
+    Ndjson,
+}
+
+impl Cli {
+    fn tokenizer_config(&self) -> Result<TokenizerConfig, Box<dyn Error>> {
+        if let Some(path) = &self.config {
+            return Ok(TokenizerConfig::load_file(path)?);
+        }
+
+        let strategy = match self.strategy {
+            Strategy::Unicode => TokenizerStrategy::Unicode,
+            Strategy::Whitespace => TokenizerStrategy::Whitespace,
+            Strategy::Pattern => TokenizerStrategy::Pattern {
+                regex: self.regex.clone().unwrap_or_default(),
+            },
+            Strategy::Sentence => TokenizerStrategy::Sentence,
+            Strategy::Grapheme => TokenizerStrategy::Grapheme {
+                extended: !self.no_extended,
+            },
+            Strategy::Keyword => TokenizerStrategy::Keyword,
+            Strategy::EdgeNgram => TokenizerStrategy::EdgeNgram {
+                min_gram: self.min_gram,
+                max_gram: self.max_gram,
+            },
+            Strategy::Ngram => TokenizerStrategy::Ngram {
+                min_gram: self.min_gram,
+                max_gram: self.max_gram,
+            },
+            Strategy::PathHierarchy => TokenizerStrategy::PathHierarchy {
+                delimiter: self.delimiter.clone(),
+            },
+            Strategy::UrlEmail => TokenizerStrategy::UrlEmail,
+            Strategy::CharGroup => TokenizerStrategy::CharGroup {
+                split_on_chars: self.split_on_chars.clone(),
+            },
+            Strategy::Letter => TokenizerStrategy::Letter,
+            Strategy::Lowercase => TokenizerStrategy::Lowercase,
+            Strategy::Bytes => TokenizerStrategy::Bytes,
+            Strategy::Cjk => TokenizerStrategy::Cjk {
+                dictionary: self.dictionary.as_ref().map(|p| p.display().to_string()),
+                user_dictionaries: self.user_dictionary.iter().map(|p| p.display().to_string()).collect(),
+                search_mode: self.search_mode,
+                hmm: !self.no_hmm,
+            },
+            Strategy::Japanese => TokenizerStrategy::Japanese {
+                dictionary: self.dictionary.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+                user_dictionaries: self.user_dictionary.iter().map(|p| p.display().to_string()).collect(),
+            },
+            Strategy::HuggingFace => TokenizerStrategy::HuggingFace {
+                path: self.tokenizer.as_ref().map(|p| p.display().to_string()).unwrap_or_default(),
+                add_special_tokens: !self.no_special_tokens,
+            },
+        };
+
+        Ok(TokenizerConfig {
+            strategy,
+            lowercase: !self.no_lowercase,
+            locale: self.locale.clone(),
+            remove_punctuation: self.remove_punctuation,
+            preserve_patterns: self.preserve.clone(),
+            char_filters: self
+                .char_filter
+                .iter()
+                .map(|filter| match filter {
+                    CharFilter::CjkWidth => tokenkit_core::CharFilter::CjkWidth,
+                })
+                .collect(),
+            filters: self
+                .filter
+                .iter()
+                .map(|filter| match filter {
+                    Filter::CjkBigram => TokenFilter::CjkBigram {
+                        output_unigrams: self.output_unigrams,
+                    },
+                    Filter::ArabicNormalization => TokenFilter::ArabicNormalization,
+                    Filter::PersianNormalization => TokenFilter::PersianNormalization,
+                    Filter::HebrewNormalization => TokenFilter::HebrewNormalization,
+                    Filter::ArabicStem => TokenFilter::ArabicStem,
+                    Filter::IndicNormalization => TokenFilter::IndicNormalization,
+                    Filter::CjkWidth => TokenFilter::CjkWidth,
+                })
+                .collect(),
+            invalid_utf8: match self.invalid_utf8 {
+                InvalidUtf8::Raise => InvalidUtf8Policy::Raise,
+                InvalidUtf8::Replace => InvalidUtf8Policy::Replace,
+                InvalidUtf8::Skip => InvalidUtf8Policy::Skip,
+            },
+            ..TokenizerConfig::default()
+        })
+    }
+}
+
+/// Writes tokens in the chosen format as they are produced.
+struct Output<W: Write> {
+    out: W,
+    format: Format,
+    // Whether the current JSON array is still empty
+    first: bool,
+}
+
+impl<W: Write> Output<W> {
+    fn begin(&mut self) -> io::Result<()> {
+        self.first = true;
+        if self.format == Format::Json {
+            self.out.write_all(b"[")?;
+        }
+        Ok(())
+    }
+
+    fn token(&mut self, token: &Token<'_>) -> io::Result<()> {
+        match self.format {
+            Format::Lines => writeln!(self.out, "{}", token.text),
+            Format::Json => {
+                if !self.first {
+                    self.out.write_all(b",")?;
+                }
+                self.first = false;
+                serde_json::to_writer(&mut self.out, &token.text)?;
+                Ok(())
+            }
+            Format::Ndjson => {
+                serde_json::to_writer(&mut self.out, token)?;
+                self.out.write_all(b"\n")
+            }
+        }
+    }
+
+    // A token of the bytes strategy, written as its bytes, or as text with
+    // invalid UTF-8 replaced in the JSON formats
+    fn bytes_token(&mut self, bytes: &[u8], start: usize, end: usize) -> io::Result<()> {
+        if self.format == Format::Lines {
+            self.out.write_all(bytes)?;
+            return self.out.write_all(b"\n");
+        }
+        self.token(&Token::new(String::from_utf8_lossy(bytes), start, end))
+    }
+
+    fn tokens(&mut self, tokens: Vec<Token<'_>>, offsets: Option<&mut ByteOffsets>) -> io::Result<()> {
+        let Some(offsets) = offsets else {
+            return tokens.iter().try_for_each(|token| self.token(token));
+        };
+
+        for token in &tokens {
+            let (start, end) = offsets.map(token);
+            self.bytes_token(&encoding::encode_bytes(&token.text), start, end)?;
+        }
+        if let Some(end) = tokens.iter().map(|token| token.end).max() {
+            offsets.forget(end);
+        }
+        Ok(())
+    }
+
+    fn end(&mut self) -> io::Result<()> {
+        if self.format == Format::Json {
+            self.out.write_all(b"]\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps offsets into the text `encoding::decode_bytes` makes of the input
+/// back to input bytes, where each byte from 0x80 up became two.
+///
+/// Tokens from later chunks start after every token so far, so only the
+/// input from the end of the last token on is kept.
+#[derive(Default)]
+struct ByteOffsets {
+    // Input bytes from `start` on
+    bytes: VecDeque<u8>,
+    start: Position,
+    // Where the last token started, and the next one most likely does
+    last: Position,
+}
+
+#[derive(Clone, Copy, Default)]
+struct Position {
+    decoded: usize,
+    input: usize,
+}
+
+impl ByteOffsets {
+    fn push(&mut self, bytes: &[u8]) {
+        self.bytes.extend(bytes);
+    }
+
+    fn map(&mut self, token: &Token<'_>) -> (usize, usize) {
+        let from = if token.start >= self.last.decoded { self.last } else { self.start };
+        let start = self.walk(from, token.start);
+        let end = self.walk(start, token.end);
+        self.last = start;
+        (start.input, end.input)
+    }
+
+    // Drops the input before `offset`, where no later token starts
+    fn forget(&mut self, offset: usize) {
+        let to = self.walk(self.start, offset);
+        self.bytes.drain(..to.input - self.start.input);
+        self.start = to;
+        if self.last.decoded < to.decoded {
+            self.last = to;
+        }
+    }
+
+    fn walk(&self, mut from: Position, offset: usize) -> Position {
+        for &byte in self.bytes.range(from.input - self.start.input..) {
+            if from.decoded >= offset {
+                break;
+            }
+            from.decoded += if byte < 0x80 { 1 } else { 2 };
+            from.input += 1;
+        }
+        from
+    }
+}
+
+// Streams one input through `stream`, which is left ready for the next. With
+// `bytes`, the stream reads every byte as one character and offsets are mapped
+// back to input bytes
+fn tokenize_input<W: Write>(
+    input: &mut dyn Read,
+    stream: &mut TokenStream,
+    bytes: bool,
+    output: &mut Output<W>,
+) -> Result<(), Box<dyn Error>> {
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut offsets = bytes.then(ByteOffsets::default);
+
+    output.begin()?;
+    loop {
+        let read = match input.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e.into()),
+        };
+        let chunk = &buf[..read];
+        if let Some(offsets) = &mut offsets {
+            offsets.push(chunk);
+        }
+        let tokens = stream.try_push_tokens(chunk)?;
+        output.tokens(tokens, offsets.as_mut())?;
+    }
+    let tokens = stream.try_finish_tokens()?;
+    output.tokens(tokens, offsets.as_mut())?;
+    output.end()?;
+
+    Ok(())
+}
+
+fn open(path: &Path) -> Result<Box<dyn Read>, Box<dyn Error>> {
+    if path == Path::new("-") {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Box::new(file))
+}
+
+fn run(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    // Built once, since dictionaries can take a while to load
+    let config = cli.tokenizer_config()?;
+    let bytes = matches!(config.strategy, TokenizerStrategy::Bytes);
+    let mut stream = TokenStream::new(from_config(config.clone())?)
+        .with_invalid_utf8(config.invalid_utf8)
+        .with_limits(config.limits);
+    if bytes {
+        stream = stream.with_byte_input();
+    }
+
+    let mut output = Output {
+        out: BufWriter::new(io::stdout().lock()),
+        format: cli.format,
+        first: true,
+    };
+
+    if cli.files.is_empty() {
+        tokenize_input(&mut io::stdin().lock(), &mut stream, bytes, &mut output)?;
+    } else {
+        for path in &cli.files {
+            tokenize_input(&mut open(path)?, &mut stream, bytes, &mut output)?;
+        }
+    }
+
+    output.out.flush()?;
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(&cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader went away (e.g. `| head`), which is not an error for us
+        Err(e) if e.downcast_ref::<io::Error>().is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) => {
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("tokenkit: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
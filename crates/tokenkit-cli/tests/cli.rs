@@ -0,0 +1,201 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn tokenkit(args: &[&str], stdin: impl AsRef<[u8]>) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_tokenkit"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // Feed stdin from another thread so large inputs can't deadlock on full pipes
+    let mut input = child.stdin.take().unwrap();
+    let stdin = stdin.as_ref().to_vec();
+    // (the write fails harmlessly when tokenkit exits early without reading it)
+    let writer = std::thread::spawn(move || input.write_all(&stdin));
+    let output = child.wait_with_output().unwrap();
+    let _ = writer.join().unwrap();
+    output
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn write_tmp(name: &str, contents: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn writes_one_token_per_line_by_default() {
+    let output = tokenkit(&[], "Hello, World!");
+    assert_eq!(stdout(&output), "hello\nworld\n");
+}
+
+#[test]
+fn takes_strategy_and_options() {
+    let output = tokenkit(&["-s", "edge_ngram", "--min-gram", "2", "--max-gram", "3", "-f", "json"], "Laptop");
+    assert_eq!(stdout(&output), "[\"la\",\"lap\"]\n");
+}
+
+#[test]
+fn ndjson_includes_byte_offsets() {
+    let output = tokenkit(&["--preserve", r"\d+mg", "-f", "ndjson"], "Café 100mg");
+    assert_eq!(
+        stdout(&output),
+        "{\"text\":\"café\",\"start\":0,\"end\":5}\n{\"text\":\"100mg\",\"start\":6,\"end\":11}\n"
+    );
+}
+
+#[test]
+fn applies_filters() {
+    let output = tokenkit(&["--filter", "cjk_bigram", "-f", "json"], "東京都 Tokyo");
+    assert_eq!(stdout(&output), "[\"東京\",\"京都\",\"tokyo\"]\n");
+}
+
+#[test]
+fn applies_char_filters() {
+    let output = tokenkit(&["--char-filter", "cjk_width", "-p", r"\d+mg", "-f", "json"], "ｶﾞｲﾄﾞ ５０ｍｇ");
+    assert_eq!(stdout(&output), "[\"ガイド\",\"50mg\"]\n");
+}
+
+#[test]
+fn lowercases_with_a_locale() {
+    let output = tokenkit(&["--locale", "tr", "-f", "json"], "İSTANBUL IRMAK");
+    assert_eq!(stdout(&output), "[\"istanbul\",\"ırmak\"]\n");
+}
+
+#[test]
+fn loads_a_hugging_face_tokenizer() {
+    let tokenizer = concat!(env!("CARGO_MANIFEST_DIR"), "/../tokenkit-core/tests/fixtures/huggingface/bert/tokenizer.json");
+    let output = tokenkit(&["-s", "huggingface", "--tokenizer", tokenizer, "-f", "json"], "Hello, world!");
+    assert_eq!(stdout(&output), "[\"[CLS]\",\"hello\",\",\",\"world\",\"!\",\"[SEP]\"]\n");
+}
+
+#[test]
+fn reads_files_in_order() {
+    let first = write_tmp("first.txt", "one two");
+    let second = write_tmp("second.txt", "three");
+    let output = tokenkit(&["-f", "json", first.to_str().unwrap(), second.to_str().unwrap()], "");
+    assert_eq!(stdout(&output), "[\"one\",\"two\"]\n[\"three\"]\n");
+}
+
+#[test]
+fn offsets_restart_for_each_file() {
+    let first = write_tmp("offsets-first.txt", "one two");
+    let second = write_tmp("offsets-second.txt", "three");
+    let output = tokenkit(&["-f", "ndjson", first.to_str().unwrap(), second.to_str().unwrap()], "");
+    assert!(stdout(&output).ends_with("{\"text\":\"three\",\"start\":0,\"end\":5}\n"));
+}
+
+#[test]
+fn invalid_utf8_is_an_error_by_default() {
+    let output = tokenkit(&[], b"caf\xE9 menu");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid byte at offset 3"));
+}
+
+#[test]
+fn invalid_utf8_can_be_replaced_or_skipped() {
+    let output = tokenkit(&["-s", "whitespace", "--invalid-utf8", "replace"], b"caf\xE9 menu");
+    assert_eq!(stdout(&output), "caf\u{FFFD}\nmenu\n");
+
+    let config = write_tmp(
+        "skip.json",
+        r#"{"version": 1, "tokenizer": {"strategy": {"type": "whitespace"}, "invalid_utf8": "skip"}}"#,
+    );
+    let output = tokenkit(&["--config", config.to_str().unwrap()], b"caf\xE9 menu");
+    assert_eq!(stdout(&output), "caf\nmenu\n");
+}
+
+#[test]
+fn bytes_strategy_reads_and_writes_bytes() {
+    let output = tokenkit(&["-s", "bytes"], b"GIF89a \xFF\xD8");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(output.stdout, b"gif89a\n\xFF\xD8\n");
+
+    // Offsets count input bytes, not the two bytes each high byte decodes to
+    let output = tokenkit(&["-s", "bytes", "-f", "ndjson"], "Café \u{FFFD} x");
+    assert_eq!(
+        stdout(&output),
+        "{\"text\":\"café\",\"start\":0,\"end\":5}\n{\"text\":\"\u{FFFD}\",\"start\":6,\"end\":9}\n\
+         {\"text\":\"x\",\"start\":10,\"end\":11}\n"
+    );
+
+    // Across chunks too
+    let output = tokenkit(&["-s", "bytes", "-f", "ndjson"], "é ".repeat(40_000) + "end");
+    assert!(stdout(&output).ends_with("{\"text\":\"end\",\"start\":120000,\"end\":120003}\n"));
+}
+
+#[test]
+fn bytes_strategy_limits_input_bytes() {
+    let config = write_tmp(
+        "bytes_limit.json",
+        r#"{"version": 1, "tokenizer": {"strategy": {"type": "bytes"}, "limits": {"max_input_bytes": 100000}}}"#,
+    );
+    let config = config.to_str().unwrap();
+    // High bytes count once, though each decodes to two bytes
+    let input = b"\xE9 ".repeat(50_000);
+    let output = tokenkit(&["--config", config], &input);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(output.stdout, b"\xE9\n".repeat(50_000));
+
+    let output = tokenkit(&["--config", config], [&input[..], b"\xE9"].concat());
+    assert!(!output.status.success());
+}
+
+#[test]
+fn loads_a_json_config() {
+    let config = write_tmp(
+        "config.json",
+        r#"{"version": 1, "tokenizer": {"strategy": {"type": "path_hierarchy"}, "lowercase": false}}"#,
+    );
+    let output = tokenkit(&["--config", config.to_str().unwrap()], "/usr/Local");
+    assert_eq!(stdout(&output), "/usr\n/usr/Local\n");
+}
+
+#[test]
+fn loads_a_yaml_config() {
+    let config = write_tmp(
+        "config.yml",
+        "version: 1\ntokenizer:\n  strategy:\n    type: char_group\n    split_on_chars: \",\"\n",
+    );
+    let output = tokenkit(&["--config", config.to_str().unwrap()], "A b,C");
+    assert_eq!(stdout(&output), "a b\nc\n");
+}
+
+#[test]
+fn reports_unknown_config_fields() {
+    let config = write_tmp("typo.json", r#"{"version": 1, "tokenizer": {"lowercse": false}}"#);
+    let output = tokenkit(&["--config", config.to_str().unwrap()], "text");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown field `lowercse`"));
+}
+
+#[test]
+fn tokenizes_large_input_across_chunks() {
+    let text = "alpha béta ".repeat(20_000);
+    let output = tokenkit(&["-s", "whitespace"], &text);
+    let out = stdout(&output);
+    assert_eq!(out.lines().count(), 40_000);
+    assert!(out.lines().all(|line| line == "alpha" || line == "béta"));
+}
+
+#[test]
+fn reports_invalid_configuration() {
+    let output = tokenkit(&["-s", "pattern", "--regex", "[unclosed"], "text");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("tokenkit: Invalid regex pattern"));
+}
+
+#[test]
+fn reports_missing_files() {
+    let output = tokenkit(&["does-not-exist.txt"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("does-not-exist.txt"));
+}
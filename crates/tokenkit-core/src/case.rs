@@ -0,0 +1,110 @@
+//! Locale-aware lowercasing.
+//!
+//! Text is lowercased with the Unicode default case mapping, which turns a
+//! Greek capital sigma at the end of a word into `ς`, plus the
+//! language-specific rules of `SpecialCasing.txt`: Turkish and Azerbaijani
+//! `I` → `ı` and `İ` → `i`, and Lithuanian `i` keeping its dot under an
+//! accent.
+
+use unicode_normalization_alignments::char::canonical_combining_class;
+
+const COMBINING_DOT_ABOVE: char = '\u{307}';
+
+/// The case mapping rules of a locale.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CaseLocale {
+    /// The default mapping, for languages without rules of their own.
+    #[default]
+    Root,
+    /// Turkish and Azerbaijani.
+    Turkic,
+    Lithuanian,
+}
+
+impl CaseLocale {
+    /// The rules of the BCP 47 tag `locale`, such as `tr` or `az-Latn-AZ`,
+    /// by its language subtag.
+    pub fn new(locale: Option<&str>) -> Self {
+        let language = locale.and_then(|tag| tag.split(['-', '_']).next()).unwrap_or_default();
+        let is_one_of = |codes: &[&str]| codes.iter().any(|code| language.eq_ignore_ascii_case(code));
+        if is_one_of(&["tr", "tur", "az", "aze"]) {
+            Self::Turkic
+        } else if is_one_of(&["lt", "lit"]) {
+            Self::Lithuanian
+        } else {
+            Self::Root
+        }
+    }
+
+    /// `text` in lowercase.
+    pub fn lowercase(self, text: &str) -> String {
+        match self {
+            Self::Root => text.to_lowercase(),
+            // The special mappings come first, leaving the rest (and the
+            // word-final sigma, which needs context) to the default mapping
+            Self::Turkic => turkic(text).to_lowercase(),
+            Self::Lithuanian => lithuanian(text).to_lowercase(),
+        }
+    }
+}
+
+/// Whether `locale` looks like a BCP 47 language tag: a language subtag of
+/// two or three letters, then subtags of up to eight letters or digits,
+/// separated by `-` or `_`.
+pub fn is_valid_locale(locale: &str) -> bool {
+    let mut subtags = locale.split(['-', '_']);
+    let language = subtags.next().unwrap_or_default();
+    (2..=3).contains(&language.len())
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+// Combining marks that stop the After_I and More_Above contexts of
+// SpecialCasing.txt: base characters (class 0) and marks above (230)
+fn is_blocking(c: char) -> bool {
+    matches!(canonical_combining_class(c), 0 | 230)
+}
+
+// The first base character or mark above in `rest`
+fn next_blocking(rest: &str) -> Option<char> {
+    rest.chars().find(|&c| is_blocking(c))
+}
+
+fn turkic(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    // Right after an `I`, with no base character or mark above since
+    let mut after_i = false;
+    for (i, c) in text.char_indices() {
+        match c {
+            'İ' => out.push('i'),
+            // `I` followed by a dot above is `i`; the dot is dropped below
+            'I' if next_blocking(&text[i + 1..]) == Some(COMBINING_DOT_ABOVE) => out.push('i'),
+            'I' => out.push('ı'),
+            COMBINING_DOT_ABOVE if after_i => {}
+            _ => out.push(c),
+        }
+        after_i = c == 'I' || (after_i && !is_blocking(c));
+    }
+    out
+}
+
+fn lithuanian(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        let more_above = || {
+            next_blocking(&text[i + c.len_utf8()..]).is_some_and(|next| canonical_combining_class(next) == 230)
+        };
+        match c {
+            // Keep the dot the accent would otherwise replace
+            'I' | 'J' | 'Į' if more_above() => {
+                out.push(c);
+                out.push(COMBINING_DOT_ABOVE);
+            }
+            'Ì' => out.push_str("i\u{307}\u{300}"),
+            'Í' => out.push_str("i\u{307}\u{301}"),
+            'Ĩ' => out.push_str("i\u{307}\u{303}"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
@@ -0,0 +1,90 @@
+//! Character filters: steps applied in order to the input text before the
+//! strategy splits it, configured with [`TokenizerConfig::char_filters`].
+//!
+//! Unlike token filters, they change what the strategy sees, so full-width
+//! `ＡＢＣ１２３` folded to `ABC123` is split like any ASCII word. Tokens of
+//! filtered input own their text, and their offsets are mapped back to the
+//! original input.
+//!
+//! [`TokenizerConfig::char_filters`]: crate::TokenizerConfig::char_filters
+
+use crate::config::CharFilter;
+use crate::filter::CjkWidthFilter;
+use std::ops::Range;
+
+/// One step of the char filter chain.
+pub trait Filter: Send + Sync {
+    /// The characters of the filtered `text`, each with the byte range of
+    /// `text` it came from, or `None` when `text` is left as it is.
+    fn filter(&self, text: &str) -> Option<Vec<(char, Range<usize>)>>;
+}
+
+/// Builds the filter for a config entry.
+pub fn from_config(filter: &CharFilter) -> Box<dyn Filter> {
+    match filter {
+        CharFilter::CjkWidth => Box::new(CjkWidthFilter),
+    }
+}
+
+/// Input text changed by char filters, with the range of the input each of
+/// its characters came from.
+#[derive(Debug)]
+pub struct FilteredText {
+    text: String,
+    // Byte offset of each character in `text` and the input range it replaces
+    sources: Vec<(usize, Range<usize>)>,
+    input_len: usize,
+}
+
+impl FilteredText {
+    /// `input` run through `filters` in order, or `None` when none of them
+    /// change it.
+    pub fn new(input: &str, filters: &[Box<dyn Filter>]) -> Option<Self> {
+        let mut filtered: Option<Self> = None;
+        for filter in filters {
+            let current = filtered.as_ref().map_or(input, |filtered| filtered.text.as_str());
+            let Some(chars) = filter.filter(current) else {
+                continue;
+            };
+
+            let mut text = String::with_capacity(current.len());
+            let mut sources = Vec::with_capacity(chars.len());
+            for (c, range) in chars {
+                // Ranges of an earlier filter's output map through to the input
+                let range = match &filtered {
+                    Some(previous) => previous.input_start(range.start)..previous.input_end(range.end),
+                    None => range,
+                };
+                sources.push((text.len(), range));
+                text.push(c);
+            }
+            filtered = Some(Self {
+                text,
+                sources,
+                input_len: input.len(),
+            });
+        }
+        filtered
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Input offset of the character starting at byte `offset` of the
+    /// filtered text; the end of the input for `offset` at its end.
+    pub fn input_start(&self, offset: usize) -> usize {
+        let i = self.sources.partition_point(|(start, _)| *start < offset);
+        self.sources.get(i).map_or(self.input_len, |(_, range)| range.start)
+    }
+
+    /// Input offset just past the character ending at byte `offset` of the
+    /// filtered text.
+    pub fn input_end(&self, offset: usize) -> usize {
+        let i = self.sources.partition_point(|(start, _)| *start < offset);
+        match i.checked_sub(1) {
+            Some(last) => self.sources[last].1.end,
+            None => self.input_start(0),
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use crate::case::CaseLocale;
+use crate::error::{Result, TokenizerError};
+use crate::validation;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+/// Serialized as the `tokenizer` section of a config file (see `config_file`).
+/// Omitted fields take their `Default` values; unknown fields are an error.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct TokenizerConfig {
+    pub strategy: TokenizerStrategy,
+    pub lowercase: bool,
+    /// BCP 47 tag whose case mapping `lowercase` follows, such as `tr`; see
+    /// `case`. The default Unicode mapping when `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    pub remove_punctuation: bool,
+    pub preserve_patterns: Vec<String>,
+    /// Word lists added to the bundled Thai, Lao, Khmer and Myanmar ones
+    /// the `unicode` strategy breaks words with; see `word_break`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub word_break_dictionaries: Vec<String>,
+    /// Applied in order to the input before the strategy; see `char_filter`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub char_filters: Vec<CharFilter>,
+    /// Applied in order to the strategy's tokens; see `filter`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<TokenFilter>,
+    #[serde(skip_serializing_if = "Limits::is_unlimited")]
+    pub limits: Limits,
+    #[serde(skip_serializing_if = "RegexLimits::is_default")]
+    pub regex_limits: RegexLimits,
+    /// What happens to input bytes that aren't valid UTF-8; see `encoding`.
+    #[serde(skip_serializing_if = "InvalidUtf8Policy::is_default")]
+    pub invalid_utf8: InvalidUtf8Policy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InvalidUtf8Policy {
+    /// Fail with `TokenizerError::InvalidUtf8`.
+    #[default]
+    Raise,
+    /// Replace each invalid sequence with U+FFFD.
+    Replace,
+    /// Leave invalid bytes out.
+    Skip,
+}
+
+impl InvalidUtf8Policy {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Caps on the work a single input can cause, for untrusted text. Each
+/// limit is off when `None`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Limits {
+    /// Longest input, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_input_bytes: Option<usize>,
+    /// Most tokens emitted for one input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    /// Longest token, in characters.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_token_length: Option<usize>,
+    /// What happens when the input or the token count is over its limit.
+    pub overflow: OverflowPolicy,
+    /// What happens to tokens over `max_token_length`.
+    pub long_tokens: LongTokenPolicy,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// Fail with `TokenizerError::LimitExceeded`.
+    #[default]
+    Error,
+    /// Tokenize only the start of the input, or emit only the first tokens.
+    Truncate,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LongTokenPolicy {
+    /// Keep the first `max_token_length` characters.
+    #[default]
+    Truncate,
+    /// Leave the token out.
+    Drop,
+}
+
+impl Limits {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_input_bytes.is_none() && self.max_tokens.is_none() && self.max_token_length.is_none()
+    }
+}
+
+/// Bounds on the regexes a config compiles, for patterns that come from
+/// users. Unset values keep the `regex` crate's defaults.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct RegexLimits {
+    /// Largest compiled program for one pattern, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_limit: Option<usize>,
+    /// Size of the lazy DFA cache for one pattern, in bytes. Only a hint: it
+    /// never rejects a pattern, and patterns needing more cache just match
+    /// more slowly.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dfa_size_limit: Option<usize>,
+    /// Most `preserve_patterns` in one config.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_preserve_patterns: Option<usize>,
+}
+
+impl RegexLimits {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Compiles `pattern` within `size_limit`, the only limit that can reject
+    /// a pattern here.
+    pub fn compile(&self, pattern: &str) -> Result<Regex> {
+        let mut builder = RegexBuilder::new(pattern);
+        if let Some(size_limit) = self.size_limit {
+            builder.size_limit(size_limit);
+        }
+        if let Some(dfa_size_limit) = self.dfa_size_limit {
+            builder.dfa_size_limit(dfa_size_limit);
+        }
+
+        builder.build().map_err(|e| match e {
+            regex::Error::CompiledTooBig(max) => TokenizerError::RegexLimitExceeded {
+                pattern: pattern.to_string(),
+                limit: "size_limit".to_string(),
+                max,
+            },
+            e => TokenizerError::InvalidRegex {
+                pattern: pattern.to_string(),
+                error: e.to_string(),
+            },
+        })
+    }
+}
+
+/// Serialized with a `type` tag holding the snake_case strategy name, next to
+/// the strategy's own options, e.g. `{"type": "edge_ngram", "min_gram": 2}`.
+/// Options that are left out get the same defaults as the Ruby API.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TokenizerStrategy {
+    Whitespace,
+    Unicode,
+    Pattern { regex: String },
+    Sentence,
+    Grapheme {
+        #[serde(default = "default_extended")]
+        extended: bool,
+    },
+    Keyword,
+    EdgeNgram {
+        #[serde(default = "default_min_gram")]
+        min_gram: usize,
+        #[serde(default = "default_max_gram")]
+        max_gram: usize,
+    },
+    Ngram {
+        #[serde(default = "default_min_gram")]
+        min_gram: usize,
+        #[serde(default = "default_max_gram")]
+        max_gram: usize,
+    },
+    PathHierarchy {
+        #[serde(default = "default_delimiter")]
+        delimiter: String,
+    },
+    UrlEmail,
+    CharGroup {
+        #[serde(default = "default_split_on_chars")]
+        split_on_chars: String,
+    },
+    Letter,
+    Lowercase,
+    /// Splits on ASCII whitespace and only changes ASCII letters, so every
+    /// other character passes through untouched. Meant for binary input,
+    /// decoded one character per byte (see `encoding::decode_bytes`).
+    Bytes,
+    /// Dictionary-based Chinese word segmentation. Each run of Han characters
+    /// is cut along the most probable path through the dictionary words it
+    /// contains; unknown words are guessed with an HMM when `hmm` is set.
+    Cjk {
+        /// Frequency dictionary replacing the bundled one; see `dictionary`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        dictionary: Option<String>,
+        /// Dictionaries loaded on top, in order.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        user_dictionaries: Vec<String>,
+        /// Also emit the dictionary words inside long words.
+        #[serde(default)]
+        search_mode: bool,
+        #[serde(default = "default_hmm")]
+        hmm: bool,
+    },
+    /// Japanese morphological analysis with a MeCab-format dictionary
+    /// directory; tokens carry part-of-speech attributes (see `japanese`).
+    Japanese {
+        dictionary: String,
+        /// CSV files of words that are always segmented as they say.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        user_dictionaries: Vec<String>,
+    },
+    /// A Hugging Face `tokenizer.json`; tokens are the model's subword tokens.
+    #[serde(rename = "huggingface")]
+    HuggingFace {
+        path: String,
+        #[serde(default = "default_add_special_tokens")]
+        add_special_tokens: bool,
+    },
+    /// Sends each script run of the input to the tokenizer of the first
+    /// route matching its script and the input's language; see
+    /// `tokenizer::router`.
+    Router {
+        routes: Vec<Route>,
+        /// Tokenizer for scripts no route names; the default config when
+        /// left out.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fallback: Option<Box<TokenizerConfig>>,
+    },
+}
+
+/// One branch of the `router` strategy.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+    /// Unicode script names such as `latin`, `han` or `thai`, in any case;
+    /// see `script::parse_script`. Any script when left out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scripts: Vec<String>,
+    /// Languages such as `eng` or `german` the input must be detected as
+    /// for this route to apply; see `language::parse_language`. Any
+    /// language when left out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    /// Builds this route's tokenizer, with its own case folding, filters
+    /// and limits.
+    #[serde(default)]
+    pub tokenizer: TokenizerConfig,
+}
+
+/// A token filter, serialized like [`TokenizerStrategy`] with a `type` tag:
+/// `{"type": "cjk_bigram", "output_unigrams": true}`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TokenFilter {
+    /// Overlapping bigrams of adjacent Han, Hiragana, Katakana and Hangul
+    /// characters, as Lucene's `CJKBigramFilter` makes; other tokens pass
+    /// through.
+    CjkBigram {
+        /// Also emit each character on its own.
+        #[serde(default)]
+        output_unigrams: bool,
+    },
+    /// Arabic spelling variants folded and diacritics removed, as Lucene's
+    /// `ArabicNormalizationFilter` does.
+    ArabicNormalization,
+    /// Persian letter forms folded into Arabic ones, as Lucene's
+    /// `PersianNormalizationFilter` does.
+    PersianNormalization,
+    /// Hebrew niqqud and cantillation marks removed.
+    HebrewNormalization,
+    /// Common Arabic prefixes and suffixes stripped, as Lucene's
+    /// `ArabicStemFilter` does.
+    ArabicStem,
+    /// Devanagari, Bengali, Tamil and other Indic spellings normalized: NFC,
+    /// zero-width joiners removed and split vowels composed.
+    IndicNormalization,
+    /// Full-width ASCII and half-width katakana, Hangul and symbols folded
+    /// to their usual width, leaving other compatibility characters alone.
+    CjkWidth,
+}
+
+/// A character filter, serialized like [`TokenFilter`]: `{"type": "cjk_width"}`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum CharFilter {
+    /// Width variants folded as by [`TokenFilter::CjkWidth`], before the
+    /// strategy splits the input.
+    CjkWidth,
+}
+
+fn default_extended() -> bool {
+    true
+}
+
+fn default_min_gram() -> usize {
+    2
+}
+
+fn default_max_gram() -> usize {
+    10
+}
+
+fn default_delimiter() -> String {
+    "/".to_string()
+}
+
+fn default_split_on_chars() -> String {
+    " \t\n\r".to_string()
+}
+
+fn default_hmm() -> bool {
+    true
+}
+
+fn default_add_special_tokens() -> bool {
+    true
+}
+
+impl Default for TokenizerConfig {
+    fn default() -> Self {
+        Self {
+            strategy: TokenizerStrategy::Unicode,
+            lowercase: true,
+            locale: None,
+            remove_punctuation: false,
+            preserve_patterns: Vec::new(),
+            word_break_dictionaries: Vec::new(),
+            char_filters: Vec::new(),
+            filters: Vec::new(),
+            limits: Limits::default(),
+            regex_limits: RegexLimits::default(),
+            invalid_utf8: InvalidUtf8Policy::default(),
+        }
+    }
+}
+
+impl TokenizerConfig {
+    /// The case mapping rules of `locale`.
+    pub fn case_locale(&self) -> CaseLocale {
+        CaseLocale::new(self.locale.as_deref())
+    }
+}
+
+/// Checks strategy parameters and compiles every regex, so tokenizers built
+/// from a validated config can rely on their patterns being valid. A single
+/// problem is returned as is; several as `ConfigurationProblems`.
+pub fn validate_config(config: &TokenizerConfig) -> Result<()> {
+    validation::into_result(validation::problems(config))
+}
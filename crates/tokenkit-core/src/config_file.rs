@@ -0,0 +1,163 @@
+//! Versioned JSON/YAML config files.
+//!
+//! A config file wraps a [`TokenizerConfig`] in a small document so the
+//! schema can evolve without breaking files already checked in elsewhere:
+//!
+//! ```yaml
+//! version: 1
+//! tokenizer:
+//!   strategy:
+//!     type: edge_ngram
+//!     min_gram: 2
+//!     max_gram: 10
+//!   lowercase: true
+//!   remove_punctuation: false
+//!   preserve_patterns:
+//!     - '\d+mg'
+//! ```
+//!
+//! `version` is required and must not be newer than [`CONFIG_VERSION`].
+//! Inside `tokenizer` every field is optional and defaults to
+//! `TokenizerConfig::default()`; unknown fields are rejected so typos don't
+//! silently fall back to defaults.
+
+use crate::config::{validate_config, TokenizerConfig};
+use crate::error::{Result, TokenizerError};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Schema version written by this release and the newest one it can read.
+pub const CONFIG_VERSION: u32 = 1;
+
+/// On-disk layout of a config file.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile<C> {
+    version: u32,
+    tokenizer: C,
+}
+
+// Read before the full document, so a file from a newer release reports its
+// version instead of whichever new field it trips over first
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: Option<u32>,
+}
+
+/// Serialization format of a config file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Format implied by a `.json`, `.yml` or `.yaml` extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl FromStr for ConfigFormat {
+    type Err = TokenizerError;
+
+    fn from_str(format: &str) -> Result<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "json" => Ok(ConfigFormat::Json),
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            _ => Err(TokenizerError::InvalidConfigFile(format!(
+                "unknown format '{}', expected json or yaml",
+                format
+            ))),
+        }
+    }
+}
+
+fn file_error(error: impl std::fmt::Display) -> TokenizerError {
+    TokenizerError::InvalidConfigFile(error.to_string())
+}
+
+fn check_version(version: Option<u32>) -> Result<()> {
+    match version {
+        None => Err(file_error(format!("missing `version` (current version is {})", CONFIG_VERSION))),
+        Some(0) => Err(file_error("version must be at least 1")),
+        Some(version) if version > CONFIG_VERSION => Err(file_error(format!(
+            "version {} is newer than this release supports ({})",
+            version, CONFIG_VERSION
+        ))),
+        Some(_) => Ok(()),
+    }
+}
+
+impl TokenizerConfig {
+    /// Parses and validates a config file's contents.
+    pub fn parse(contents: &str, format: ConfigFormat) -> Result<Self> {
+        let file: ConfigFile<TokenizerConfig> = match format {
+            ConfigFormat::Json => {
+                let probe: VersionProbe = serde_json::from_str(contents).map_err(file_error)?;
+                check_version(probe.version)?;
+                serde_json::from_str(contents).map_err(file_error)?
+            }
+            ConfigFormat::Yaml => {
+                let probe: VersionProbe = serde_yaml::from_str(contents).map_err(file_error)?;
+                check_version(probe.version)?;
+                serde_yaml::from_str(contents).map_err(file_error)?
+            }
+        };
+
+        validate_config(&file.tokenizer)?;
+        Ok(file.tokenizer)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::parse(json, ConfigFormat::Json)
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self> {
+        Self::parse(yaml, ConfigFormat::Yaml)
+    }
+
+    /// Reads a config file, picking the format from its extension.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let format = ConfigFormat::from_path(path).ok_or_else(|| {
+            file_error(format!("{}: expected a .json, .yml or .yaml file", path.display()))
+        })?;
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| file_error(format!("{}: {}", path.display(), e)))?;
+
+        Self::parse(&contents, format)
+            .map_err(|e| match e {
+                TokenizerError::InvalidConfigFile(message) => {
+                    file_error(format!("{}: {}", path.display(), message))
+                }
+                other => other,
+            })
+    }
+
+    /// Serializes the config as a current-version config file.
+    pub fn dump(&self, format: ConfigFormat) -> Result<String> {
+        let file = ConfigFile {
+            version: CONFIG_VERSION,
+            tokenizer: self,
+        };
+
+        match format {
+            ConfigFormat::Json => {
+                let mut json = serde_json::to_string_pretty(&file).map_err(file_error)?;
+                json.push('\n');
+                Ok(json)
+            }
+            ConfigFormat::Yaml => serde_yaml::to_string(&file).map_err(file_error),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        self.dump(ConfigFormat::Json)
+    }
+
+    pub fn to_yaml(&self) -> Result<String> {
+        self.dump(ConfigFormat::Yaml)
+    }
+}
@@ -0,0 +1,612 @@
+//! Import analyzers from Elasticsearch/OpenSearch index settings.
+//!
+//! [`import_analysis`] reads the `analysis` block of index settings and
+//! builds a [`TokenizerConfig`] for every analyzer in it, so queries can be
+//! tokenized offline the same way the index does:
+//!
+//! ```json
+//! {
+//!   "settings": {
+//!     "analysis": {
+//!       "analyzer": {
+//!         "autocomplete": { "tokenizer": "autocomplete", "filter": ["lowercase"] }
+//!       },
+//!       "tokenizer": {
+//!         "autocomplete": { "type": "edge_ngram", "min_gram": 2, "max_gram": 10,
+//!                           "token_chars": ["letter", "digit", "punctuation", "symbol"] }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! Only settings TokenKit reproduces exactly are accepted. Anything else, such
+//! as a stemmer filter or a `token_chars` list that splits on punctuation, is
+//! reported with its path in the settings instead of being approximated.
+
+use crate::config::{validate_config, TokenFilter, TokenizerConfig, TokenizerStrategy};
+use crate::error::{Result, TokenizerError};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+// Longer tokens would be split by Elasticsearch; TokenKit never splits them
+const MAX_TOKEN_LENGTH: u64 = 255;
+
+// `token_chars` equivalent to TokenKit's n-grams, which split on whitespace only
+const NON_WHITESPACE_CHARS: [&str; 4] = ["digit", "letter", "punctuation", "symbol"];
+
+// Java's Character.isWhitespace for the characters char_group accepts
+const WHITESPACE_CHARS: &str = " \t\n\r\u{b}\u{c}";
+
+// Runs of letters as the letter and lowercase tokenizers split them. Unlike
+// TokenKit's letter strategy, combining marks that aren't alphabetic split
+// words, as they do in Elasticsearch
+const LETTERS: &str = r"\p{Alphabetic}+";
+
+type Object = Map<String, Value>;
+
+// A supported token filter: lowercasing is a config flag, the rest are filters
+enum ImportedFilter {
+    Lowercase,
+    Token(TokenFilter),
+}
+
+// A supported tokenizer, and whether it lowercases its tokens itself
+struct ImportedTokenizer {
+    strategy: TokenizerStrategy,
+    lowercase: bool,
+}
+
+/// Builds a config for every analyzer in Elasticsearch/OpenSearch settings.
+///
+/// `json` may be the full response of `GET /<index>/_settings`, the index
+/// settings (`{"settings": {"analysis": ...}}`, with or without `index`), or
+/// the `analysis` block itself. All unsupported settings are reported at once
+/// in a single [`TokenizerError::InvalidAnalysisSettings`].
+pub fn import_analysis(json: &str) -> Result<BTreeMap<String, TokenizerConfig>> {
+    let root: Value =
+        serde_json::from_str(json).map_err(|e| TokenizerError::InvalidAnalysisSettings(format!("not valid JSON: {}", e)))?;
+    let (path, analysis) = find_analysis(&root)
+        .ok_or_else(|| TokenizerError::InvalidAnalysisSettings("no analysis block found".to_string()))?;
+
+    let mut importer = Importer {
+        path,
+        analysis,
+        problems: Vec::new(),
+    };
+    let configs = importer.analyzers();
+
+    if importer.problems.is_empty() {
+        Ok(configs)
+    } else {
+        Err(TokenizerError::InvalidAnalysisSettings(importer.problems.join("; ")))
+    }
+}
+
+// Where index settings keep the analysis block
+const ANALYSIS_PATHS: [&[&str]; 4] = [
+    &["settings", "index", "analysis"],
+    &["settings", "analysis"],
+    &["index", "analysis"],
+    &["analysis"],
+];
+
+fn lookup<'v>(value: &'v Value, prefix: Option<&str>) -> Option<(String, &'v Object)> {
+    ANALYSIS_PATHS.iter().find_map(|path| {
+        let found = path.iter().try_fold(value, |value, key| value.get(key))?.as_object()?;
+        let path = prefix.into_iter().chain(path.iter().copied()).collect::<Vec<_>>().join(".");
+        Some((path, found))
+    })
+}
+
+// Locate the analysis block and the path to it, for error messages
+fn find_analysis(root: &Value) -> Option<(String, &Object)> {
+    let object = root.as_object()?;
+    if let Some(found) = lookup(root, None) {
+        return Some(found);
+    }
+
+    // `GET /<index>/_settings` nests the settings under the index name
+    if object.len() == 1 {
+        let (index, settings) = object.iter().next()?;
+        if let Some(found) = lookup(settings, Some(index)) {
+            return Some(found);
+        }
+    }
+
+    // The analysis block on its own
+    object.contains_key("analyzer").then(|| ("analysis".to_string(), object))
+}
+
+struct Importer<'a> {
+    path: String,
+    analysis: &'a Object,
+    problems: Vec<String>,
+}
+
+impl<'a> Importer<'a> {
+    fn problem(&mut self, path: &str, message: impl Display) {
+        self.problems.push(format!("{}: {}", path, message));
+    }
+
+    fn analyzers(&mut self) -> BTreeMap<String, TokenizerConfig> {
+        let path = format!("{}.analyzer", self.path);
+        let analysis = self.analysis;
+        let analyzers = match analysis.get("analyzer") {
+            Some(Value::Object(analyzers)) if !analyzers.is_empty() => analyzers,
+            _ => {
+                self.problem(&path, "no analyzers defined");
+                return BTreeMap::new();
+            }
+        };
+
+        let mut configs = BTreeMap::new();
+        for (name, definition) in analyzers {
+            let path = format!("{}.{}", path, name);
+            if let Some(config) = self.analyzer(&path, definition) {
+                match validate_config(&config) {
+                    Ok(()) => {
+                        configs.insert(name.clone(), config);
+                    }
+                    Err(e) => self.problem(&path, e),
+                }
+            }
+        }
+        configs
+    }
+
+    fn analyzer(&mut self, path: &str, definition: &Value) -> Option<TokenizerConfig> {
+        let definition = self.object(path, definition)?;
+        let kind = match definition.get("type") {
+            None => "custom",
+            Some(Value::String(kind)) => kind.as_str(),
+            Some(_) => {
+                self.problem(&format!("{}.type", path), "expected a string");
+                return None;
+            }
+        };
+
+        let (strategy, lowercase) = match kind {
+            "custom" => return self.custom_analyzer(path, definition),
+            "standard" => {
+                self.check_settings(path, definition, &["max_token_length", "stopwords", "stopwords_path"]);
+                self.check_max_token_length(path, definition);
+                self.check_no_stopwords(path, definition);
+                (TokenizerStrategy::Unicode, true)
+            }
+            "simple" => {
+                self.check_settings(path, definition, &[]);
+                (letters(), true)
+            }
+            "whitespace" => {
+                self.check_settings(path, definition, &[]);
+                (TokenizerStrategy::Whitespace, false)
+            }
+            "keyword" => {
+                self.check_settings(path, definition, &[]);
+                (TokenizerStrategy::Keyword, false)
+            }
+            other => {
+                self.problem(&format!("{}.type", path), format!("unsupported analyzer type '{}'", other));
+                return None;
+            }
+        };
+
+        Some(TokenizerConfig {
+            strategy,
+            lowercase,
+            ..TokenizerConfig::default()
+        })
+    }
+
+    fn custom_analyzer(&mut self, path: &str, definition: &Object) -> Option<TokenizerConfig> {
+        self.check_settings(
+            path,
+            definition,
+            &["tokenizer", "filter", "char_filter", "position_increment_gap", "position_offset_gap"],
+        );
+
+        if !names(definition.get("char_filter")).is_empty() {
+            self.problem(&format!("{}.char_filter", path), "character filters are not supported");
+        }
+
+        // TokenKit lowercases before any filter, which is the same for the
+        // filters it supports
+        let mut lowercase = false;
+        let mut filters = Vec::new();
+        for (i, filter) in names(definition.get("filter")).into_iter().enumerate() {
+            let filter_path = format!("{}.filter[{}]", path, i);
+            match filter {
+                Some(name) => match self.filter(&filter_path, name) {
+                    Some(ImportedFilter::Lowercase) => lowercase = true,
+                    Some(ImportedFilter::Token(filter)) => filters.push(filter),
+                    None => {}
+                },
+                None => self.problem(&filter_path, "expected a filter name"),
+            }
+        }
+
+        let tokenizer = match definition.get("tokenizer") {
+            Some(tokenizer) => self.tokenizer(&format!("{}.tokenizer", path), tokenizer)?,
+            None => {
+                self.problem(path, "custom analyzer without a tokenizer");
+                return None;
+            }
+        };
+
+        Some(TokenizerConfig {
+            strategy: tokenizer.strategy,
+            lowercase: lowercase || tokenizer.lowercase,
+            filters,
+            ..TokenizerConfig::default()
+        })
+    }
+
+    /// The filter `name`, a built-in one or defined in the settings; `None`
+    /// for unsupported filters.
+    fn filter(&mut self, path: &str, name: &str) -> Option<ImportedFilter> {
+        let analysis = self.analysis;
+        let Some(definition) = analysis.get("filter").and_then(|filters| filters.get(name)) else {
+            return match name {
+                "lowercase" => Some(ImportedFilter::Lowercase),
+                "cjk_bigram" => Some(ImportedFilter::Token(TokenFilter::CjkBigram { output_unigrams: false })),
+                "arabic_normalization" => Some(ImportedFilter::Token(TokenFilter::ArabicNormalization)),
+                "persian_normalization" => Some(ImportedFilter::Token(TokenFilter::PersianNormalization)),
+                _ => {
+                    self.problem(path, format!("unsupported token filter '{}'", name));
+                    None
+                }
+            };
+        };
+
+        let path = format!("{}.filter.{}", self.path, name);
+        let definition = self.object(&path, definition)?;
+        match definition.get("type").and_then(Value::as_str) {
+            Some("lowercase") => {
+                // Language-specific lowercasing (greek, irish, turkish) differs
+                self.check_settings(&path, definition, &[]);
+                Some(ImportedFilter::Lowercase)
+            }
+            Some("cjk_bigram") => {
+                self.check_settings(&path, definition, &["output_unigrams", "ignored_scripts"]);
+                if !names(definition.get("ignored_scripts")).is_empty() {
+                    self.problem(&format!("{}.ignored_scripts", path), "ignoring scripts is not supported");
+                }
+                let output_unigrams = self.bool_setting(&path, definition, "output_unigrams", false)?;
+                Some(ImportedFilter::Token(TokenFilter::CjkBigram { output_unigrams }))
+            }
+            Some("arabic_normalization") => {
+                self.check_settings(&path, definition, &[]);
+                Some(ImportedFilter::Token(TokenFilter::ArabicNormalization))
+            }
+            Some("persian_normalization") => {
+                self.check_settings(&path, definition, &[]);
+                Some(ImportedFilter::Token(TokenFilter::PersianNormalization))
+            }
+            // Lucene's light Arabic stemmer; other languages' stemmers aren't
+            // reproduced
+            Some("stemmer") => {
+                self.check_settings(&path, definition, &["language", "name"]);
+                let language = definition.get("language").or_else(|| definition.get("name"));
+                match language.and_then(Value::as_str) {
+                    Some("arabic") => Some(ImportedFilter::Token(TokenFilter::ArabicStem)),
+                    Some(other) => {
+                        self.problem(&format!("{}.language", path), format!("unsupported stemmer language '{}'", other));
+                        None
+                    }
+                    None => {
+                        self.problem(&path, "stemmer without a language");
+                        None
+                    }
+                }
+            }
+            Some(kind) => {
+                self.problem(&path, format!("unsupported token filter '{}'", kind));
+                None
+            }
+            None => {
+                self.problem(&path, "filter without a type");
+                None
+            }
+        }
+    }
+
+    fn tokenizer(&mut self, path: &str, tokenizer: &Value) -> Option<ImportedTokenizer> {
+        let analysis = self.analysis;
+        match tokenizer {
+            Value::String(name) => match analysis.get("tokenizer").and_then(|t| t.get(name)) {
+                Some(definition) => {
+                    let path = format!("{}.tokenizer.{}", self.path, name);
+                    self.tokenizer_definition(&path, definition)
+                }
+                None => self.tokenizer_type(path, name, &Object::new()),
+            },
+            Value::Object(_) => self.tokenizer_definition(path, tokenizer),
+            _ => {
+                self.problem(path, "expected a tokenizer name");
+                None
+            }
+        }
+    }
+
+    fn tokenizer_definition(&mut self, path: &str, definition: &Value) -> Option<ImportedTokenizer> {
+        let definition = self.object(path, definition)?;
+        match definition.get("type").and_then(Value::as_str) {
+            Some(kind) => self.tokenizer_type(path, kind, definition),
+            None => {
+                self.problem(path, "tokenizer without a type");
+                None
+            }
+        }
+    }
+
+    fn tokenizer_type(&mut self, path: &str, kind: &str, settings: &Object) -> Option<ImportedTokenizer> {
+        let strategy = match kind {
+            "standard" | "whitespace" | "uax_url_email" => {
+                self.check_settings(path, settings, &["max_token_length"]);
+                self.check_max_token_length(path, settings);
+                match kind {
+                    "standard" => TokenizerStrategy::Unicode,
+                    "whitespace" => TokenizerStrategy::Whitespace,
+                    _ => TokenizerStrategy::UrlEmail,
+                }
+            }
+            "keyword" => {
+                // buffer_size only tunes reading, not the output
+                self.check_settings(path, settings, &["buffer_size"]);
+                TokenizerStrategy::Keyword
+            }
+            "letter" | "lowercase" => {
+                self.check_settings(path, settings, &[]);
+                letters()
+            }
+            // The Unicode strategy breaks Thai words with its bundled dictionary
+            "thai" => {
+                self.check_settings(path, settings, &[]);
+                TokenizerStrategy::Unicode
+            }
+            "edge_ngram" | "ngram" => {
+                self.check_settings(path, settings, &["min_gram", "max_gram", "token_chars", "custom_token_chars"]);
+                self.check_token_chars(path, settings);
+                let min_gram = self.usize_setting(path, settings, "min_gram", 1)?;
+                let max_gram = self.usize_setting(path, settings, "max_gram", 2)?;
+                if kind == "ngram" {
+                    TokenizerStrategy::Ngram { min_gram, max_gram }
+                } else {
+                    TokenizerStrategy::EdgeNgram { min_gram, max_gram }
+                }
+            }
+            "path_hierarchy" => {
+                self.check_settings(path, settings, &["delimiter", "replacement", "buffer_size", "reverse", "skip"]);
+                let delimiter = self.string_setting(path, settings, "delimiter", "/")?;
+                if self.string_setting(path, settings, "replacement", &delimiter)? != delimiter {
+                    self.problem(&format!("{}.replacement", path), "a replacement delimiter is not supported");
+                }
+                if self.bool_setting(path, settings, "reverse", false)? {
+                    self.problem(&format!("{}.reverse", path), "reverse path hierarchies are not supported");
+                }
+                if self.usize_setting(path, settings, "skip", 0)? != 0 {
+                    self.problem(&format!("{}.skip", path), "skipping path components is not supported");
+                }
+                TokenizerStrategy::PathHierarchy { delimiter }
+            }
+            "char_group" => {
+                self.check_settings(path, settings, &["tokenize_on_chars", "max_token_length"]);
+                self.check_max_token_length(path, settings);
+                TokenizerStrategy::CharGroup {
+                    split_on_chars: self.tokenize_on_chars(path, settings)?,
+                }
+            }
+            "pattern" => {
+                self.check_settings(path, settings, &["pattern", "flags", "group"]);
+                let pattern = self.string_setting(path, settings, "pattern", r"\W+")?;
+                let flags = self.string_setting(path, settings, "flags", "")?;
+                let flags = self.regex_flags(&format!("{}.flags", path), &flags)?;
+
+                // Only group 0 (each match is a token) has a TokenKit equivalent;
+                // the default, -1, splits on the pattern instead
+                let group = match settings.get("group") {
+                    None => -1,
+                    Some(value) => match as_i64(value) {
+                        Some(group) => group,
+                        None => {
+                            self.problem(&format!("{}.group", path), "expected an integer");
+                            return None;
+                        }
+                    },
+                };
+                if group != 0 {
+                    self.problem(
+                        &format!("{}.group", path),
+                        format!("only group 0 is supported, got {} (TokenKit patterns match tokens)", group),
+                    );
+                    return None;
+                }
+
+                let regex = if flags.is_empty() { pattern } else { format!("(?{}){}", flags, pattern) };
+                TokenizerStrategy::Pattern { regex }
+            }
+            other => {
+                self.problem(path, format!("unsupported tokenizer type '{}'", other));
+                return None;
+            }
+        };
+        Some(ImportedTokenizer {
+            strategy,
+            lowercase: kind == "lowercase",
+        })
+    }
+
+    fn check_token_chars(&mut self, path: &str, settings: &Object) {
+        let mut token_chars: Vec<&str> = names(settings.get("token_chars")).into_iter().flatten().collect();
+        token_chars.sort_unstable();
+        token_chars.dedup();
+
+        if token_chars != NON_WHITESPACE_CHARS {
+            self.problem(
+                &format!("{}.token_chars", path),
+                format!(
+                    "token_chars {:?} is not supported; TokenKit n-grams split on whitespace only, \
+                     which is token_chars {:?}",
+                    token_chars, NON_WHITESPACE_CHARS
+                ),
+            );
+        }
+        if !names(settings.get("custom_token_chars")).is_empty() {
+            self.problem(&format!("{}.custom_token_chars", path), "custom_token_chars is not supported");
+        }
+    }
+
+    fn tokenize_on_chars(&mut self, path: &str, settings: &Object) -> Option<String> {
+        let path = format!("{}.tokenize_on_chars", path);
+        let mut split_on_chars = String::new();
+
+        for (i, entry) in names(settings.get("tokenize_on_chars")).into_iter().enumerate() {
+            match entry {
+                Some("whitespace") => split_on_chars.push_str(WHITESPACE_CHARS),
+                Some(entry) if entry.chars().count() == 1 => split_on_chars.push_str(entry),
+                Some(entry) => self.problem(
+                    &format!("{}[{}]", path, i),
+                    format!("unsupported character class '{}'; only single characters and whitespace are", entry),
+                ),
+                None => self.problem(&format!("{}[{}]", path, i), "expected a string"),
+            }
+        }
+
+        if split_on_chars.is_empty() {
+            self.problem(&path, "no characters to split on");
+            return None;
+        }
+        Some(split_on_chars)
+    }
+
+    // Java Pattern flags, e.g. "CASE_INSENSITIVE|COMMENTS", as inline Rust flags
+    fn regex_flags(&mut self, path: &str, flags: &str) -> Option<String> {
+        let mut inline = String::new();
+        for flag in flags.split('|').map(str::trim).filter(|f| !f.is_empty()) {
+            match flag {
+                "CASE_INSENSITIVE" => inline.push('i'),
+                "MULTILINE" => inline.push('m'),
+                "DOTALL" => inline.push('s'),
+                "COMMENTS" => inline.push('x'),
+                // Rust regexes are Unicode-aware by default
+                "UNICODE_CASE" | "UNICODE_CHARACTER_CLASS" => {}
+                other => {
+                    self.problem(path, format!("unsupported regex flag '{}'", other));
+                    return None;
+                }
+            }
+        }
+        Some(inline)
+    }
+
+    fn check_max_token_length(&mut self, path: &str, settings: &Object) {
+        if let Some(value) = settings.get("max_token_length") {
+            if as_u64(value) != Some(MAX_TOKEN_LENGTH) {
+                self.problem(
+                    &format!("{}.max_token_length", path),
+                    format!("only the default of {} is supported", MAX_TOKEN_LENGTH),
+                );
+            }
+        }
+    }
+
+    fn check_no_stopwords(&mut self, path: &str, settings: &Object) {
+        let none = match settings.get("stopwords") {
+            None => true,
+            Some(Value::String(s)) => s == "_none_",
+            Some(Value::Array(words)) => words.is_empty(),
+            Some(_) => false,
+        };
+        if !none || settings.contains_key("stopwords_path") {
+            self.problem(&format!("{}.stopwords", path), "stop words are not supported");
+        }
+    }
+
+    // Report every setting that isn't `type` or in `allowed`
+    fn check_settings(&mut self, path: &str, settings: &Object, allowed: &[&str]) {
+        for key in settings.keys() {
+            if key != "type" && !allowed.contains(&key.as_str()) {
+                self.problem(&format!("{}.{}", path, key), "unsupported setting");
+            }
+        }
+    }
+
+    fn object<'v>(&mut self, path: &str, value: &'v Value) -> Option<&'v Object> {
+        let object = value.as_object();
+        if object.is_none() {
+            self.problem(path, "expected an object");
+        }
+        object
+    }
+
+    // Elasticsearch accepts numbers and booleans as strings too, e.g. "2"
+    fn usize_setting(&mut self, path: &str, settings: &Object, key: &str, default: usize) -> Option<usize> {
+        let Some(value) = settings.get(key) else {
+            return Some(default);
+        };
+        let parsed = as_u64(value).and_then(|n| usize::try_from(n).ok());
+        if parsed.is_none() {
+            self.problem(&format!("{}.{}", path, key), "expected a non-negative integer");
+        }
+        parsed
+    }
+
+    fn bool_setting(&mut self, path: &str, settings: &Object, key: &str, default: bool) -> Option<bool> {
+        let parsed = match settings.get(key) {
+            None => return Some(default),
+            Some(Value::Bool(b)) => Some(*b),
+            Some(Value::String(s)) => s.parse().ok(),
+            Some(_) => None,
+        };
+        if parsed.is_none() {
+            self.problem(&format!("{}.{}", path, key), "expected a boolean");
+        }
+        parsed
+    }
+
+    fn string_setting(&mut self, path: &str, settings: &Object, key: &str, default: &str) -> Option<String> {
+        match settings.get(key) {
+            None => Some(default.to_string()),
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(_) => {
+                self.problem(&format!("{}.{}", path, key), "expected a string");
+                None
+            }
+        }
+    }
+}
+
+fn letters() -> TokenizerStrategy {
+    TokenizerStrategy::Pattern {
+        regex: LETTERS.to_string(),
+    }
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn as_i64(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+// A list setting, which Elasticsearch also accepts as a single string.
+// `None` entries are not strings
+fn names(value: Option<&Value>) -> Vec<Option<&str>> {
+    match value {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::String(s)) => vec![Some(s.as_str())],
+        Some(Value::Array(items)) => items.iter().map(Value::as_str).collect(),
+        Some(_) => vec![None],
+    }
+}
@@ -0,0 +1,49 @@
+//! Turning raw input bytes into text to tokenize.
+//!
+//! Tokenizers work on `&str`, so input that isn't valid UTF-8 is decoded
+//! first according to the config's [`InvalidUtf8Policy`]. Binary input for
+//! the `bytes` strategy is instead decoded one character per byte (the
+//! Latin-1 mapping), which keeps every byte and lets tokens be turned back
+//! into exactly the bytes they came from.
+
+use crate::config::InvalidUtf8Policy;
+use crate::error::{Result, TokenizerError};
+use std::borrow::Cow;
+
+/// Decodes `bytes` as UTF-8, borrowing them when they are valid.
+pub fn decode(bytes: &[u8], policy: InvalidUtf8Policy) -> Result<Cow<'_, str>> {
+    let error = match std::str::from_utf8(bytes) {
+        Ok(text) => return Ok(Cow::Borrowed(text)),
+        Err(error) => error,
+    };
+
+    match policy {
+        InvalidUtf8Policy::Raise => Err(TokenizerError::InvalidUtf8 {
+            position: error.valid_up_to(),
+        }),
+        InvalidUtf8Policy::Replace => Ok(String::from_utf8_lossy(bytes)),
+        InvalidUtf8Policy::Skip => Ok(Cow::Owned(skip_invalid(bytes))),
+    }
+}
+
+// The valid parts of `bytes`, as `from_utf8_lossy` finds them
+fn skip_invalid(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        text.push_str(chunk.valid());
+    }
+    text
+}
+
+/// Decodes every byte as the character with the same value, U+0000 to
+/// U+00FF. Never fails, and byte offsets into the input become char offsets
+/// into the text.
+pub fn decode_bytes(bytes: &[u8]) -> String {
+    bytes.iter().map(|&byte| char::from(byte)).collect()
+}
+
+/// Reverses [`decode_bytes`] for a token of its text. Characters above
+/// U+00FF can't come from it and are left out.
+pub fn encode_bytes(text: &str) -> Vec<u8> {
+    text.chars().filter_map(|c| u8::try_from(c).ok()).collect()
+}
@@ -0,0 +1,96 @@
+use crate::validation::{self, Diagnostic};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TokenizerError {
+    /// `key` names the offending config field, such as `max_tokens`.
+    #[error("Invalid configuration: {message}")]
+    InvalidConfiguration {
+        key: String,
+        message: String,
+    },
+
+    #[error("Invalid regex pattern '{pattern}': {error}")]
+    InvalidRegex {
+        pattern: String,
+        error: String,
+    },
+
+    #[error("Invalid n-gram configuration: min_gram ({min}) must be > 0 and <= max_gram ({max})")]
+    InvalidNgramConfig {
+        min: usize,
+        max: usize,
+    },
+
+    #[error("Empty delimiter is not allowed for {tokenizer} tokenizer")]
+    EmptyDelimiter {
+        tokenizer: String,
+    },
+
+    #[error("Invalid config file: {0}")]
+    InvalidConfigFile(String),
+
+    #[error("Unknown tokenizer strategy: {0}{}", did_you_mean(&validation::suggest(.0, validation::STRATEGIES.iter().copied())))]
+    UnknownStrategy(String),
+
+    #[error("Unknown option '{key}'{}", did_you_mean(.suggestion))]
+    UnknownOption {
+        key: String,
+        suggestion: Option<String>,
+    },
+
+    #[error("Option '{key}' is not used by the {strategy} strategy")]
+    UnusedOption {
+        key: String,
+        strategy: String,
+    },
+
+    /// More than one problem with a config; see `validation`.
+    #[error("Invalid configuration:{}", .0.iter().map(|d| format!("\n  {}", d)).collect::<String>())]
+    ConfigurationProblems(Vec<Diagnostic>),
+
+    #[error("Unknown analyzer: {0}")]
+    UnknownAnalyzer(String),
+
+    #[error("Invalid analysis settings: {0}")]
+    InvalidAnalysisSettings(String),
+
+    /// A dictionary file that can't be read or has a malformed line.
+    #[error("Invalid dictionary '{path}': {message}")]
+    InvalidDictionary {
+        path: String,
+        message: String,
+    },
+
+    #[error("Invalid Hugging Face tokenizer: {0}")]
+    InvalidHuggingFaceTokenizer(String),
+
+    #[error("Regex pattern '{pattern}' exceeds {limit} ({max})")]
+    RegexLimitExceeded {
+        pattern: String,
+        limit: String,
+        max: usize,
+    },
+
+    /// `position` is the byte offset of the first invalid byte.
+    #[error("Input is not valid UTF-8: invalid byte at offset {position}")]
+    InvalidUtf8 {
+        position: usize,
+    },
+
+    #[error("Input exceeds {limit} ({max})")]
+    LimitExceeded {
+        limit: String,
+        max: usize,
+    },
+}
+
+fn did_you_mean(suggestion: &Option<String>) -> String {
+    match suggestion {
+        Some(suggestion) => format!(" (did you mean '{}'?)", suggestion),
+        None => String::new(),
+    }
+}
+
+// Result type for fallible tokenkit-core functions
+pub type Result<T> = std::result::Result<T, TokenizerError>;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use super::{map_tokens, normalize_chars, Filter, Tokens};
+
+/// Folds the spelling variants that make Arabic searches miss, as Lucene's
+/// `ArabicNormalizationFilter` does: alef with madda or hamza to bare alef,
+/// alef maksura to yeh and teh marbuta to heh, with tatweel and harakat
+/// removed. Tokens left empty are dropped.
+pub struct ArabicNormalizationFilter;
+
+impl Filter for ArabicNormalizationFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, |word| normalize_chars(word, normalize))
+    }
+}
+
+fn normalize(c: char) -> Option<char> {
+    match c {
+        'آ' | 'أ' | 'إ' => Some('ا'),
+        'ى' => Some('ي'),
+        'ة' => Some('ه'),
+        // Tatweel, then fathatan through sukun
+        '\u{640}' | '\u{64B}'..='\u{652}' => None,
+        _ => Some(c),
+    }
+}
+
+// Lucene's light10 affixes, in the order they are tried
+const PREFIXES: [&str; 7] = ["ال", "وال", "بال", "كال", "فال", "لل", "و"];
+const SUFFIXES: [&str; 10] = ["ها", "ان", "ات", "ون", "ين", "يه", "ية", "ه", "ة", "ي"];
+
+/// Strips the common Arabic prefixes and suffixes with Lucene's light
+/// `ArabicStemmer`: at most one prefix such as the article `ال`, then every
+/// suffix in turn, each only while at least two letters remain. Meant to run
+/// after `arabic_normalization`.
+pub struct ArabicStemFilter;
+
+impl Filter for ArabicStemFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, |word| {
+            let stem = stem(word);
+            (stem.len() < word.len()).then(|| stem.to_string())
+        })
+    }
+}
+
+fn stem(word: &str) -> &str {
+    let mut len = word.chars().count();
+    let mut stem = word;
+
+    let prefix = PREFIXES.iter().find(|prefix| {
+        let affix_len = prefix.chars().count();
+        // A lone waw is only a prefix of words of four letters or more
+        let min_len = if affix_len == 1 { 4 } else { affix_len + 2 };
+        len >= min_len && stem.starts_with(*prefix)
+    });
+    if let Some(prefix) = prefix {
+        stem = &stem[prefix.len()..];
+        len -= prefix.chars().count();
+    }
+
+    for suffix in SUFFIXES {
+        let affix_len = suffix.chars().count();
+        if len >= affix_len + 2 && stem.ends_with(suffix) {
+            stem = &stem[..stem.len() - suffix.len()];
+            len -= affix_len;
+        }
+    }
+    stem
+}
@@ -0,0 +1,121 @@
+use super::{Filter, Tokens};
+use crate::script::is_cjk;
+use crate::tokenizer::Token;
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+/// Turns runs of Han, Hiragana, Katakana and Hangul into overlapping bigrams,
+/// a dictionary-free way to index CJK text: `東京都庁` becomes `東京`, `京都`,
+/// `都庁`. Tokens with any other character pass through untouched.
+///
+/// A run is a sequence of CJK tokens with no gap between them in the input,
+/// split into characters; a run of one character is emitted as it is. With
+/// `output_unigrams`, each character is also emitted on its own, before the
+/// bigram starting with it.
+pub struct CjkBigramFilter {
+    output_unigrams: bool,
+}
+
+impl CjkBigramFilter {
+    pub fn new(output_unigrams: bool) -> Self {
+        Self { output_unigrams }
+    }
+
+}
+
+impl Filter for CjkBigramFilter {
+    fn filter<'a>(&self, text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        Box::new(Bigrams {
+            text,
+            tokens,
+            output_unigrams: self.output_unigrams,
+            run: Vec::new(),
+            out: VecDeque::new(),
+        })
+    }
+}
+
+// Reads tokens up to the end of each run, so the run's bigrams are made
+// without reading the rest of the input
+struct Bigrams<'a> {
+    text: &'a str,
+    tokens: Tokens<'a>,
+    output_unigrams: bool,
+    // CJK characters of the current run
+    run: Vec<Token<'a>>,
+    // Tokens ready to be returned
+    out: VecDeque<Token<'a>>,
+}
+
+impl<'a> Bigrams<'a> {
+    fn flush(&mut self) {
+        if self.run.len() == 1 {
+            self.out.extend(self.run.drain(..));
+            return;
+        }
+
+        for (i, character) in self.run.iter().enumerate() {
+            if self.output_unigrams {
+                self.out.push_back(character.clone());
+            }
+            if let Some(next) = self.run.get(i + 1) {
+                self.out.push_back(bigram(self.text, character, next));
+            }
+        }
+        self.run.clear();
+    }
+}
+
+impl<'a> Iterator for Bigrams<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        while self.out.is_empty() {
+            let Some(token) = self.tokens.next() else {
+                self.flush();
+                break;
+            };
+
+            if token.text.is_empty() || !token.text.chars().all(is_cjk) {
+                self.flush();
+                self.out.push_back(token);
+                continue;
+            }
+
+            if self.run.last().is_some_and(|last| last.end != token.start) {
+                self.flush();
+            }
+            characters(self.text, token, &mut self.run);
+        }
+
+        self.out.pop_front()
+    }
+}
+
+// Splits `token` into one token per character, each with its own offsets
+// when the token is still the input's text, or sharing the token's otherwise
+fn characters<'a>(text: &'a str, token: Token<'a>, run: &mut Vec<Token<'a>>) {
+    if text.get(token.start..token.end) == Some(token.text.as_ref()) {
+        let word = &text[token.start..token.end];
+        run.extend(word.char_indices().map(|(i, c)| {
+            let start = token.start + i;
+            Token::new(&word[i..i + c.len_utf8()], start, start + c.len_utf8())
+        }));
+    } else {
+        run.extend(
+            token
+                .text
+                .chars()
+                .map(|c| Token::new(c.to_string(), token.start, token.end)),
+        );
+    }
+}
+
+// Borrows the input when the two characters are still its text
+fn bigram<'a>(text: &'a str, first: &Token<'a>, second: &Token<'a>) -> Token<'a> {
+    let joined = match text.get(first.start..second.end) {
+        Some(slice) if slice.strip_prefix(first.text.as_ref()) == Some(second.text.as_ref()) => Cow::Borrowed(slice),
+        _ => Cow::Owned(format!("{}{}", first.text, second.text)),
+    };
+    Token::new(joined, first.start, second.end)
+}
@@ -0,0 +1,84 @@
+use super::{map_tokens, Filter, Tokens};
+use crate::char_filter;
+use std::ops::Range;
+use unicode_normalization_alignments::char::{compose, decompose_compatible};
+
+/// Folds East Asian width variants into their usual forms: full-width ASCII
+/// such as `ＡＢＣ１２３` into `ABC123`, the ideographic space into a space,
+/// and half-width katakana, Hangul and symbols such as `ｶﾀｶﾅ` into full
+/// width. A half-width voiced sound mark is joined with the kana before it,
+/// so `ｶﾞ` becomes `ガ`.
+///
+/// Only the `<wide>` and `<narrow>` compatibility mappings are applied, so
+/// unlike NFKC it leaves characters such as `™`, `½` and `①` alone.
+///
+/// As a char filter it folds the input before the strategy splits it; as a
+/// token filter it folds the text of each token.
+pub struct CjkWidthFilter;
+
+impl Filter for CjkWidthFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, |word| {
+            fold(word).map(|chars| chars.into_iter().map(|(c, _)| c).collect())
+        })
+    }
+}
+
+impl char_filter::Filter for CjkWidthFilter {
+    fn filter(&self, text: &str) -> Option<Vec<(char, Range<usize>)>> {
+        fold(text)
+    }
+}
+
+fn fold(text: &str) -> Option<Vec<(char, Range<usize>)>> {
+    if !text.chars().any(|c| fold_char(c).is_some()) {
+        return None;
+    }
+
+    let mut out: Vec<(char, Range<usize>)> = Vec::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        let range = i..i + c.len_utf8();
+        let Some(folded) = fold_char(c) else {
+            out.push((c, range));
+            continue;
+        };
+
+        // A voiced or semi-voiced sound mark composes with the kana before it
+        if let (Some((last, last_range)), '\u{3099}' | '\u{309A}') = (out.last_mut(), folded) {
+            if let Some(composed) = compose(*last, folded) {
+                *last = composed;
+                last_range.end = range.end;
+                continue;
+            }
+        }
+        out.push((folded, range));
+    }
+    Some(out)
+}
+
+// The usual form of the width variant `c`, `None` for other characters
+fn fold_char(c: char) -> Option<char> {
+    let shifted = |from: u32, to: u32| char::from_u32(c as u32 - from + to);
+    match c {
+        '\u{3000}' => Some(' '),
+        '\u{FF01}'..='\u{FF5E}' => shifted(0xFF01, 0x21),
+        // Half-width Hangul, whose compatibility jamo decompose further
+        '\u{FFA0}' => Some('\u{3164}'),
+        '\u{FFA1}'..='\u{FFBE}' => shifted(0xFFA1, 0x3131),
+        '\u{FFC2}'..='\u{FFC7}' => shifted(0xFFC2, 0x314F),
+        '\u{FFCA}'..='\u{FFCF}' => shifted(0xFFCA, 0x3155),
+        '\u{FFD2}'..='\u{FFD7}' => shifted(0xFFD2, 0x315B),
+        '\u{FFDA}'..='\u{FFDC}' => shifted(0xFFDA, 0x3161),
+        // The full-width macron, whose narrow form decomposes further
+        '\u{FFE3}' => Some('\u{AF}'),
+        '\u{FF5F}'..='\u{FF9F}' | '\u{FFE0}'..='\u{FFEE}' => {
+            let mut decomposed = Vec::new();
+            decompose_compatible(c, |d| decomposed.push(d));
+            match decomposed[..] {
+                [single] if single != c => Some(single),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
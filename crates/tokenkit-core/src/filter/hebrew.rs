@@ -0,0 +1,23 @@
+use super::{map_tokens, normalize_chars, Filter, Tokens};
+
+/// Strips Hebrew niqqud (vowel points, dagesh, and the shin and sin dots)
+/// and cantillation marks, so pointed and unpointed spellings match. The
+/// maqaf and sof pasuq are punctuation and stay. Tokens left empty are
+/// dropped.
+pub struct HebrewNormalizationFilter;
+
+impl Filter for HebrewNormalizationFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, |word| normalize_chars(word, normalize))
+    }
+}
+
+fn normalize(c: char) -> Option<char> {
+    match c {
+        // Cantillation marks, then points up to meteg and rafe
+        '\u{591}'..='\u{5AF}' | '\u{5B0}'..='\u{5BD}' | '\u{5BF}' => None,
+        // Shin and sin dots, upper and lower dots, qamats qatan
+        '\u{5C1}' | '\u{5C2}' | '\u{5C4}' | '\u{5C5}' | '\u{5C7}' => None,
+        _ => Some(c),
+    }
+}
@@ -0,0 +1,67 @@
+use super::{map_tokens, Filter, Tokens};
+use crate::script::is_indic;
+use unicode_normalization_alignments::UnicodeNormalization;
+
+const NUKTAS: [char; 9] = [
+    '\u{93C}', '\u{9BC}', '\u{A3C}', '\u{ABC}', '\u{B3C}', '\u{C3C}', '\u{CBC}', '\u{D3B}', '\u{D3C}',
+];
+
+const VIRAMAS: [char; 9] = [
+    '\u{94D}', '\u{9CD}', '\u{A4D}', '\u{ACD}', '\u{B4D}', '\u{BCD}', '\u{C4D}', '\u{CCD}', '\u{D4D}',
+];
+
+// Independent vowels sometimes typed as another vowel and a vowel sign,
+// which canonical composition leaves apart
+const VOWELS: [(char, char, char); 6] = [
+    ('अ', 'ा', 'आ'),
+    ('अ', 'ो', 'ओ'),
+    ('अ', 'ौ', 'औ'),
+    ('ए', 'े', 'ऐ'),
+    ('অ', 'া', 'আ'),
+    ('ਅ', 'ਾ', 'ਆ'),
+];
+
+/// Normalizes the spelling of words in the Brahmic scripts of India
+/// (Devanagari, Bengali, Gurmukhi, Gujarati, Oriya, Tamil, Telugu, Kannada
+/// and Malayalam), so that words typed differently but shown alike match:
+///
+/// - Text is put in NFC, so letters with a nukta such as `क़` are always
+///   written as the letter and the nukta, and split vowel signs such as
+///   Bengali `ে` + `া` become `ো`
+/// - Zero-width joiners and non-joiners, which only choose how a conjunct is
+///   drawn, are removed
+/// - A nukta or virama typed twice is kept once
+/// - An independent vowel typed as another vowel and a vowel sign, such as
+///   `अ` + `ा`, becomes the single vowel `आ`
+///
+/// Tokens without characters of these scripts pass through unchanged.
+pub struct IndicNormalizationFilter;
+
+impl Filter for IndicNormalizationFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, normalize)
+    }
+}
+
+fn normalize(word: &str) -> Option<String> {
+    if !word.chars().any(is_indic) {
+        return None;
+    }
+
+    let mut out = String::with_capacity(word.len());
+    for (c, _) in word.nfc() {
+        let previous = out.chars().next_back();
+        match c {
+            '\u{200C}' | '\u{200D}' => {}
+            _ if previous == Some(c) && (NUKTAS.contains(&c) || VIRAMAS.contains(&c)) => {}
+            _ => match VOWELS.iter().find(|&&(vowel, sign, _)| previous == Some(vowel) && c == sign) {
+                Some(&(vowel, _, composed)) => {
+                    out.truncate(out.len() - vowel.len_utf8());
+                    out.push(composed);
+                }
+                None => out.push(c),
+            },
+        }
+    }
+    (out != word).then_some(out)
+}
@@ -0,0 +1,72 @@
+//! Token filters: steps applied in order to the tokens a strategy produces,
+//! configured with [`TokenizerConfig::filters`].
+//!
+//! Filters run after the strategy's own lowercasing, punctuation removal and
+//! pattern preservation, and before `limits` are enforced. They may split,
+//! join, change or drop tokens, keeping offsets into the original input.
+//!
+//! [`TokenizerConfig::filters`]: crate::TokenizerConfig::filters
+
+mod arabic;
+mod cjk_bigram;
+mod cjk_width;
+mod hebrew;
+mod indic;
+mod persian;
+
+pub use arabic::{ArabicNormalizationFilter, ArabicStemFilter};
+pub use cjk_bigram::CjkBigramFilter;
+pub use cjk_width::CjkWidthFilter;
+pub use hebrew::HebrewNormalizationFilter;
+pub use indic::IndicNormalizationFilter;
+pub use persian::PersianNormalizationFilter;
+
+use crate::config::TokenFilter;
+use crate::tokenizer::Token;
+use std::borrow::Cow;
+
+/// Tokens in input order, as filters take and return them.
+pub type Tokens<'a> = Box<dyn Iterator<Item = Token<'a>> + 'a>;
+
+/// One step of the filter chain.
+pub trait Filter: Send + Sync {
+    /// Filters the tokens of `text`, in order. Tokens are pulled only as
+    /// they are needed, so a caller that stops early, like `max_tokens`,
+    /// doesn't filter the rest of the input.
+    fn filter<'a>(&self, text: &'a str, tokens: Tokens<'a>) -> Tokens<'a>;
+}
+
+/// Builds the filter for a config entry.
+pub fn from_config(filter: &TokenFilter) -> Box<dyn Filter> {
+    match filter {
+        TokenFilter::CjkBigram { output_unigrams } => Box::new(CjkBigramFilter::new(*output_unigrams)),
+        TokenFilter::ArabicNormalization => Box::new(ArabicNormalizationFilter),
+        TokenFilter::PersianNormalization => Box::new(PersianNormalizationFilter),
+        TokenFilter::HebrewNormalization => Box::new(HebrewNormalizationFilter),
+        TokenFilter::ArabicStem => Box::new(ArabicStemFilter),
+        TokenFilter::IndicNormalization => Box::new(IndicNormalizationFilter),
+        TokenFilter::CjkWidth => Box::new(CjkWidthFilter),
+    }
+}
+
+// Replaces the text of each token `map` changes, returning `None` for text
+// it leaves alone, and drops tokens left empty. Offsets stay those of the
+// original text.
+fn map_tokens<'a>(tokens: Tokens<'a>, map: impl Fn(&str) -> Option<String> + 'a) -> Tokens<'a> {
+    Box::new(tokens.filter_map(move |token| {
+        let token = match map(&token.text) {
+            Some(text) => token.map_text(|_| Cow::Owned(text)),
+            None => token,
+        };
+        (!token.text.is_empty()).then_some(token)
+    }))
+}
+
+// `text` with every character replaced by `map`'s result, or left out where
+// that is `None`; `None` when no character changes
+fn normalize_chars(text: &str, map: impl Fn(char) -> Option<char>) -> Option<String> {
+    if text.chars().all(|c| map(c) == Some(c)) {
+        return None;
+    }
+    Some(text.chars().filter_map(map).collect())
+}
@@ -0,0 +1,23 @@
+use super::{map_tokens, normalize_chars, Filter, Tokens};
+
+/// Folds Persian letter forms into the Arabic ones, as Lucene's
+/// `PersianNormalizationFilter` does: Farsi yeh and yeh barree to yeh, keheh
+/// to kaf, heh with yeh above and heh goal to heh, with hamza above removed.
+/// Usually run after `arabic_normalization`.
+pub struct PersianNormalizationFilter;
+
+impl Filter for PersianNormalizationFilter {
+    fn filter<'a>(&self, _text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        map_tokens(tokens, |word| normalize_chars(word, normalize))
+    }
+}
+
+fn normalize(c: char) -> Option<char> {
+    match c {
+        'ی' | 'ے' => Some('ي'),
+        'ک' => Some('ك'),
+        'ۀ' | 'ہ' => Some('ه'),
+        '\u{654}' => None,
+        _ => Some(c),
+    }
+}
@@ -0,0 +1,161 @@
+//! Added tokens: special tokens like `[CLS]` and any tokens added on top of
+//! the model vocabulary. They are cut out of the text before the model sees
+//! it, so they are never split into pieces.
+
+use super::normalized::{ModelToken, NormalizedString, Offsets, PreTokenizedString, Split};
+use super::normalizer::Normalizer;
+use aho_corasick::{AhoCorasick, MatchKind};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Clone, Debug)]
+pub(crate) struct AddedToken {
+    pub(crate) id: u32,
+    pub(crate) content: String,
+    pub(crate) single_word: bool,
+    pub(crate) lstrip: bool,
+    pub(crate) rstrip: bool,
+    pub(crate) normalized: bool,
+    pub(crate) special: bool,
+}
+
+// Leftmost-longest matcher over token contents, with the id of each pattern
+struct Matcher {
+    automaton: AhoCorasick,
+    ids: Vec<u32>,
+}
+
+impl Matcher {
+    fn new(patterns: Vec<(String, u32)>) -> std::result::Result<Self, aho_corasick::BuildError> {
+        let (patterns, ids): (Vec<String>, Vec<u32>) = patterns.into_iter().filter(|(p, _)| !p.is_empty()).unzip();
+        let automaton = AhoCorasick::builder().match_kind(MatchKind::LeftmostLongest).build(&patterns)?;
+        Ok(Self { automaton, ids })
+    }
+}
+
+pub(crate) struct AddedVocabulary {
+    by_id: HashMap<u32, AddedToken>,
+    by_content: HashMap<String, u32>,
+    special: HashSet<String>,
+    // Tokens matched in the original text
+    raw: Matcher,
+    // Tokens matched in the normalized text, by their normalized content
+    normalized: Matcher,
+    starts_with_word: Regex,
+    ends_with_word: Regex,
+}
+
+impl AddedVocabulary {
+    pub(crate) fn new(tokens: Vec<AddedToken>, normalizer: Option<&Normalizer>) -> std::result::Result<Self, String> {
+        let mut raw = Vec::new();
+        let mut normalized = Vec::new();
+        for token in &tokens {
+            if token.normalized {
+                let mut content = NormalizedString::from(token.content.as_str());
+                if let Some(normalizer) = normalizer {
+                    normalizer.normalize(&mut content);
+                }
+                normalized.push((content.get().to_string(), token.id));
+            } else {
+                raw.push((token.content.clone(), token.id));
+            }
+        }
+
+        Ok(Self {
+            by_content: tokens.iter().map(|t| (t.content.clone(), t.id)).collect(),
+            special: tokens.iter().filter(|t| t.special).map(|t| t.content.clone()).collect(),
+            by_id: tokens.into_iter().map(|t| (t.id, t)).collect(),
+            raw: Matcher::new(raw).map_err(|e| e.to_string())?,
+            normalized: Matcher::new(normalized).map_err(|e| e.to_string())?,
+            starts_with_word: Regex::new(r"^\w").map_err(|e| e.to_string())?,
+            ends_with_word: Regex::new(r"\w$").map_err(|e| e.to_string())?,
+        })
+    }
+
+    pub(crate) fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.by_content.get(token).copied()
+    }
+
+    pub(crate) fn id_to_token(&self, id: u32) -> Option<&str> {
+        self.by_id.get(&id).map(|t| t.content.as_str())
+    }
+
+    pub(crate) fn is_special(&self, token: &str) -> bool {
+        self.special.contains(token)
+    }
+
+    /// Cuts added tokens out of `text`, normalizing the text around them.
+    ///
+    /// Tokens that are not `normalized` are matched in the original text
+    /// first. Every piece between them is then normalized on its own, and the
+    /// normalized tokens are matched in the result.
+    pub(crate) fn extract_and_normalize(&self, normalizer: Option<&Normalizer>, text: &str) -> PreTokenizedString {
+        let mut pretokenized = PreTokenizedString {
+            splits: vec![Split::from(NormalizedString::from(text))],
+        };
+        pretokenized.split(|piece| self.split_with(piece, &self.raw));
+        pretokenized.split(|mut piece| {
+            if let Some(normalizer) = normalizer {
+                normalizer.normalize(&mut piece);
+            }
+            self.split_with(piece, &self.normalized)
+        });
+        pretokenized
+    }
+
+    fn split_with(&self, piece: NormalizedString, matcher: &Matcher) -> Vec<Split> {
+        self.find_matches(piece.get(), matcher)
+            .into_iter()
+            .filter_map(|(id, (start, end))| {
+                let slice = piece.slice(start..end)?;
+                let tokens = id.map(|id| {
+                    let value = slice.get().to_string();
+                    let len = value.len();
+                    vec![ModelToken { id, value, offsets: (0, len) }]
+                });
+                Some(Split { normalized: slice, tokens })
+            })
+            .collect()
+    }
+
+    fn find_matches(&self, sentence: &str, matcher: &Matcher) -> Vec<(Option<u32>, Offsets)> {
+        if sentence.is_empty() {
+            return vec![(None, (0, 0))];
+        }
+
+        let mut start_offset = 0;
+        let mut splits = Vec::new();
+        for m in matcher.automaton.find_iter(sentence) {
+            let id = matcher.ids[m.pattern().as_usize()];
+            let Some(token) = self.by_id.get(&id) else { continue };
+            let (mut start, mut stop) = (m.start(), m.end());
+
+            if token.single_word {
+                let start_space = start == 0 || !self.ends_with_word.is_match(&sentence[..start]);
+                let stop_space = stop == sentence.len() || !self.starts_with_word.is_match(&sentence[stop..]);
+                if !start_space || !stop_space {
+                    continue;
+                }
+            }
+            if token.lstrip {
+                let before = &sentence[..start];
+                start = before.trim_end_matches(char::is_whitespace).len().max(start_offset);
+            }
+            if token.rstrip {
+                let after = &sentence[stop..];
+                stop += after.len() - after.trim_start_matches(char::is_whitespace).len();
+            }
+
+            if start_offset < start {
+                splits.push((None, (start_offset, start)));
+            }
+            splits.push((Some(id), (start, stop)));
+            start_offset = stop;
+        }
+
+        if start_offset != sentence.len() {
+            splits.push((None, (start_offset, sentence.len())));
+        }
+        splits
+    }
+}
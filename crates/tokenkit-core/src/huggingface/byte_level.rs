@@ -0,0 +1,49 @@
+//! GPT-2's byte-to-character table: every byte maps to a printable
+//! character, so byte-level vocabularies never contain raw control or
+//! whitespace bytes. Printable Latin-1 bytes map to themselves; the other
+//! 68 bytes map, in order, to U+0100 and up.
+
+/// The character byte `b` is written as, e.g. `Ġ` for a space.
+pub(crate) fn byte_char(b: u8) -> char {
+    let code = match b {
+        b'!'..=b'~' | 0xA1..=0xAC | 0xAE..=0xFF => u32::from(b),
+        0x00..=0x20 => 0x100 + u32::from(b),
+        0x7F..=0xA0 => 0x121 + u32::from(b - 0x7F),
+        0xAD => 0x143,
+    };
+    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// The byte `c` stands for, if it is in the table.
+pub(crate) fn char_byte(c: char) -> Option<u8> {
+    match u32::from(c) {
+        code @ (0x21..=0x7E | 0xA1..=0xAC | 0xAE..=0xFF) => u8::try_from(code).ok(),
+        code @ 0x100..=0x120 => u8::try_from(code - 0x100).ok(),
+        code @ 0x121..=0x142 => u8::try_from(code - 0x121 + 0x7F).ok(),
+        0x143 => Some(0xAD),
+        _ => None,
+    }
+}
+
+/// Shrinks offsets so they don't cover the spaces byte-level tokens carry,
+/// keeping the one a prefix space was added for.
+pub(crate) fn trim_offsets(tokens: &[String], offsets: &mut [(usize, usize)], add_prefix_space: bool) {
+    let space = byte_char(b' ');
+    let is_space = |c: &char| *c == space || c.is_whitespace();
+
+    for (i, (token, offsets)) in tokens.iter().zip(offsets.iter_mut()).enumerate() {
+        let mut leading = token.chars().take_while(is_space).count();
+        let trailing = token.chars().rev().take_while(is_space).count();
+
+        if leading > 0 {
+            let is_first = i == 0 || offsets.0 == 0;
+            if is_first && add_prefix_space && leading == 1 {
+                leading = 0;
+            }
+            offsets.0 = (offsets.0 + leading).min(offsets.1);
+        }
+        if trailing > 0 && offsets.1 >= trailing {
+            offsets.1 = (offsets.1 - trailing).max(offsets.0);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Decoders: turning model tokens back into text.
+
+use super::byte_level;
+use super::loader::{Loader, Object};
+use super::normalized::Pattern;
+use super::pre_tokenizer::{load_metaspace, PrependScheme};
+use fancy_regex::Regex;
+
+pub(crate) enum Decoder {
+    WordPiece {
+        prefix: String,
+        cleanup: bool,
+    },
+    Bpe {
+        suffix: String,
+    },
+    ByteLevel,
+    Metaspace {
+        replacement: char,
+        prepend_scheme: PrependScheme,
+    },
+    ByteFallback,
+    Fuse,
+    Strip {
+        content: char,
+        start: usize,
+        stop: usize,
+    },
+    Replace {
+        pattern: Regex,
+        content: String,
+    },
+    Sequence(Vec<Decoder>),
+}
+
+impl Decoder {
+    pub(crate) fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let decoder = match loader.kind(path, definition)? {
+            "WordPiece" => Self::WordPiece {
+                prefix: loader.optional_string(path, definition, "prefix")?.unwrap_or_else(|| "##".to_string()),
+                cleanup: loader.bool(path, definition, "cleanup", true)?,
+            },
+            "BPEDecoder" => Self::Bpe {
+                suffix: loader.optional_string(path, definition, "suffix")?.unwrap_or_else(|| "</w>".to_string()),
+            },
+            "ByteLevel" => Self::ByteLevel,
+            "Metaspace" => {
+                let (replacement, prepend_scheme) = load_metaspace(loader, path, definition)?;
+                Self::Metaspace {
+                    replacement,
+                    prepend_scheme,
+                }
+            }
+            "ByteFallback" => Self::ByteFallback,
+            "Fuse" => Self::Fuse,
+            "Strip" => Self::Strip {
+                content: loader.char(path, definition, "content")?,
+                start: loader.usize(path, definition, "start", 0)?,
+                stop: loader.usize(path, definition, "stop", 0)?,
+            },
+            "Replace" => Self::Replace {
+                pattern: loader.pattern(path, definition)?,
+                content: loader.string(path, definition, "content")?,
+            },
+            "Sequence" => Self::Sequence(loader.list(path, definition, "decoders", Self::load)?),
+            other => return loader.unsupported(path, "decoder", other),
+        };
+        Some(decoder)
+    }
+
+    pub(crate) fn decode_chain(&self, tokens: Vec<String>) -> Vec<String> {
+        match self {
+            Self::WordPiece { prefix, cleanup } => tokens
+                .into_iter()
+                .enumerate()
+                .map(|(i, token)| {
+                    let token = match token.strip_prefix(prefix.as_str()) {
+                        _ if i == 0 => token,
+                        Some(rest) => rest.to_string(),
+                        None => format!(" {}", token),
+                    };
+                    if *cleanup {
+                        cleanup_spaces(&token)
+                    } else {
+                        token
+                    }
+                })
+                .collect(),
+            Self::Bpe { suffix } => {
+                let last = tokens.len().saturating_sub(1);
+                tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, token)| token.replace(suffix.as_str(), if i == last { "" } else { " " }))
+                    .collect()
+            }
+            Self::ByteLevel => {
+                let bytes: Vec<u8> = tokens
+                    .iter()
+                    .flat_map(|token| {
+                        token
+                            .chars()
+                            .map(byte_level::char_byte)
+                            .collect::<Option<Vec<u8>>>()
+                            .unwrap_or_else(|| token.as_bytes().to_vec())
+                    })
+                    .collect();
+                vec![String::from_utf8_lossy(&bytes).into_owned()]
+            }
+            Self::Metaspace {
+                replacement,
+                prepend_scheme,
+            } => tokens
+                .iter()
+                .enumerate()
+                .map(|(i, token)| {
+                    token
+                        .chars()
+                        .filter_map(|c| match c {
+                            c if c != *replacement => Some(c),
+                            _ if i == 0 && *prepend_scheme != PrependScheme::Never => None,
+                            _ => Some(' '),
+                        })
+                        .collect()
+                })
+                .collect(),
+            Self::ByteFallback => decode_byte_fallback(tokens),
+            Self::Fuse => vec![tokens.concat()],
+            Self::Strip { content, start, stop } => tokens
+                .iter()
+                .map(|token| {
+                    let chars: Vec<char> = token.chars().collect();
+                    let start_cut = chars.iter().take(*start).take_while(|c| *c == content).count();
+                    let stop_cut = chars.len() - chars.iter().rev().take(*stop).take_while(|c| *c == content).count();
+                    chars[start_cut..stop_cut.max(start_cut)].iter().collect()
+                })
+                .collect(),
+            Self::Replace { pattern, content } => tokens
+                .iter()
+                .map(|token| {
+                    pattern
+                        .find_matches(token)
+                        .into_iter()
+                        .map(|((start, end), is_match)| if is_match { content.as_str() } else { &token[start..end] })
+                        .collect()
+                })
+                .collect(),
+            Self::Sequence(decoders) => decoders
+                .iter()
+                .fold(tokens, |tokens, decoder| decoder.decode_chain(tokens)),
+        }
+    }
+}
+
+// Removes the spaces WordPiece puts before punctuation and contractions
+fn cleanup_spaces(dirty: &str) -> String {
+    dirty
+        .replace(" .", ".")
+        .replace(" ?", "?")
+        .replace(" !", "!")
+        .replace(" ,", ",")
+        .replace(" ' ", "'")
+        .replace(" n't", "n't")
+        .replace(" 'm", "'m")
+        .replace(" do not", " don't")
+        .replace(" 's", "'s")
+        .replace(" 've", "'ve")
+        .replace(" 're", "'re")
+}
+
+// Runs of `<0xXX>` tokens are joined and read as UTF-8; an invalid run
+// becomes one replacement character per byte
+fn decode_byte_fallback(tokens: Vec<String>) -> Vec<String> {
+    fn flush(bytes: &mut Vec<u8>, decoded: &mut Vec<String>) {
+        if bytes.is_empty() {
+            return;
+        }
+        match String::from_utf8(std::mem::take(bytes)) {
+            Ok(text) => decoded.push(text),
+            Err(e) => decoded.extend(std::iter::repeat_n("\u{fffd}".to_string(), e.as_bytes().len())),
+        }
+    }
+
+    let mut decoded = Vec::with_capacity(tokens.len());
+    let mut bytes = Vec::new();
+    for token in tokens {
+        let byte = match token.as_bytes() {
+            [b'<', b'0', b'x', _, _, b'>'] => u8::from_str_radix(&token[3..5], 16).ok(),
+            _ => None,
+        };
+        match byte {
+            Some(byte) => bytes.push(byte),
+            None => {
+                flush(&mut bytes, &mut decoded);
+                decoded.push(token);
+            }
+        }
+    }
+    flush(&mut bytes, &mut decoded);
+    decoded
+}
@@ -0,0 +1,198 @@
+//! Reading `tokenizer.json` components, collecting every problem with the
+//! JSON path it was found at so they can all be reported at once.
+
+use super::normalized::SplitBehavior;
+use fancy_regex::Regex;
+use serde_json::{Map, Value};
+use std::fmt::Display;
+
+pub(crate) type Object = Map<String, Value>;
+
+#[derive(Default)]
+pub(crate) struct Loader {
+    pub(crate) problems: Vec<String>,
+}
+
+pub(crate) fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+impl Loader {
+    pub(crate) fn problem(&mut self, path: &str, message: impl Display) {
+        self.problems.push(format!("{}: {}", path, message));
+    }
+
+    pub(crate) fn unsupported<T>(&mut self, path: &str, component: &str, kind: &str) -> Option<T> {
+        self.problem(path, format!("unsupported {} '{}'", component, kind));
+        None
+    }
+
+    pub(crate) fn object<'v>(&mut self, path: &str, value: &'v Value) -> Option<&'v Object> {
+        match value {
+            Value::Object(object) => Some(object),
+            _ => {
+                self.problem(path, "expected an object");
+                None
+            }
+        }
+    }
+
+    /// The `type` of a component.
+    pub(crate) fn kind<'v>(&mut self, path: &str, definition: &'v Object) -> Option<&'v str> {
+        match definition.get("type") {
+            Some(Value::String(kind)) => Some(kind),
+            _ => {
+                self.problem(path, "missing component type");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn field<'v>(&mut self, path: &str, definition: &'v Object, key: &str) -> Option<&'v Value> {
+        match definition.get(key) {
+            Some(Value::Null) | None => {
+                self.problem(&join(path, key), "missing");
+                None
+            }
+            Some(value) => Some(value),
+        }
+    }
+
+    pub(crate) fn string(&mut self, path: &str, definition: &Object, key: &str) -> Option<String> {
+        match self.field(path, definition, key)? {
+            Value::String(value) => Some(value.clone()),
+            _ => {
+                self.problem(&join(path, key), "expected a string");
+                None
+            }
+        }
+    }
+
+    /// A string that may be missing or null.
+    pub(crate) fn optional_string(&mut self, path: &str, definition: &Object, key: &str) -> Option<Option<String>> {
+        match definition.get(key) {
+            Some(Value::Null) | None => Some(None),
+            Some(_) => self.string(path, definition, key).map(Some),
+        }
+    }
+
+    pub(crate) fn char(&mut self, path: &str, definition: &Object, key: &str) -> Option<char> {
+        let value = self.string(path, definition, key)?;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Some(c),
+            _ => {
+                self.problem(&join(path, key), "expected a single character");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn bool(&mut self, path: &str, definition: &Object, key: &str, default: bool) -> Option<bool> {
+        match definition.get(key) {
+            Some(Value::Null) | None => Some(default),
+            Some(Value::Bool(value)) => Some(*value),
+            Some(_) => {
+                self.problem(&join(path, key), "expected true or false");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn usize(&mut self, path: &str, definition: &Object, key: &str, default: usize) -> Option<usize> {
+        match definition.get(key) {
+            Some(Value::Null) | None => Some(default),
+            Some(value) => self.integer(&join(path, key), value),
+        }
+    }
+
+    pub(crate) fn integer<T: TryFrom<u64>>(&mut self, path: &str, value: &Value) -> Option<T> {
+        match value.as_u64().and_then(|n| T::try_from(n).ok()) {
+            Some(n) => Some(n),
+            None => {
+                self.problem(path, "expected a non-negative integer");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn regex(&mut self, path: &str, pattern: &str) -> Option<Regex> {
+        match Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                self.problem(path, format!("invalid regex '{}': {}", pattern, e));
+                None
+            }
+        }
+    }
+
+    /// The `pattern` of a Split or Replace: `{"String": ...}` or `{"Regex": ...}`.
+    pub(crate) fn pattern(&mut self, path: &str, definition: &Object) -> Option<Regex> {
+        let path = join(path, "pattern");
+        let pattern = match definition.get("pattern") {
+            Some(Value::Object(pattern)) if pattern.len() == 1 => pattern,
+            _ => {
+                self.problem(&path, "expected {\"String\": ...} or {\"Regex\": ...}");
+                return None;
+            }
+        };
+        match pattern.iter().next() {
+            Some((kind, Value::String(literal))) if kind == "String" => self.regex(&path, &regex::escape(literal)),
+            Some((kind, Value::String(regex))) if kind == "Regex" => self.regex(&path, regex),
+            _ => {
+                self.problem(&path, "expected {\"String\": ...} or {\"Regex\": ...}");
+                None
+            }
+        }
+    }
+
+    pub(crate) fn behavior(&mut self, path: &str, definition: &Object, default: SplitBehavior) -> Option<SplitBehavior> {
+        let behavior = match definition.get("behavior") {
+            Some(Value::Null) | None => return Some(default),
+            Some(Value::String(behavior)) => behavior.as_str(),
+            Some(_) => "",
+        };
+        let behavior = match behavior {
+            "Removed" => SplitBehavior::Removed,
+            "Isolated" => SplitBehavior::Isolated,
+            "MergedWithPrevious" => SplitBehavior::MergedWithPrevious,
+            "MergedWithNext" => SplitBehavior::MergedWithNext,
+            "Contiguous" => SplitBehavior::Contiguous,
+            other => {
+                self.problem(&join(path, "behavior"), format!("unknown split behavior '{}'", other));
+                return None;
+            }
+        };
+        Some(behavior)
+    }
+
+    /// Loads every component of the list at `key`, reporting the problems of
+    /// all of them.
+    pub(crate) fn list<T>(
+        &mut self,
+        path: &str,
+        definition: &Object,
+        key: &str,
+        mut load: impl FnMut(&mut Self, &str, &Object) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        let Value::Array(items) = self.field(path, definition, key)? else {
+            self.problem(&join(path, key), "expected a list");
+            return None;
+        };
+
+        let mut loaded = Vec::with_capacity(items.len());
+        let mut complete = true;
+        for (i, item) in items.iter().enumerate() {
+            let path = format!("{}[{}]", join(path, key), i);
+            match self.object(&path, item).and_then(|item| load(self, &path, item)) {
+                Some(component) => loaded.push(component),
+                None => complete = false,
+            }
+        }
+        complete.then_some(loaded)
+    }
+}
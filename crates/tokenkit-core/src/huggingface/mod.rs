@@ -0,0 +1,259 @@
+//! Load Hugging Face `tokenizer.json` files.
+//!
+//! [`HuggingFaceTokenizer`] reads the file written by the `tokenizers`
+//! library (`tokenizer.save("tokenizer.json")`, or the one shipped with most
+//! models on the Hub) and reproduces its ids, tokens and offsets, so
+//! subword tokenization for a model can run without Python:
+//!
+//! ```no_run
+//! use tokenkit_core::huggingface::HuggingFaceTokenizer;
+//!
+//! let tokenizer = HuggingFaceTokenizer::from_file("bert-base-uncased/tokenizer.json").unwrap();
+//! let encoding = tokenizer.encode("Hello, world!", true);
+//! assert_eq!(encoding.tokens, ["[CLS]", "hello", ",", "world", "!", "[SEP]"]);
+//! assert_eq!(tokenizer.decode(&encoding.ids, true), "hello, world!");
+//! ```
+//!
+//! Supported components:
+//!
+//! - models: `WordPiece`, `BPE` and `Unigram`
+//! - normalizers: `BertNormalizer`, `Lowercase`, `NFC`, `NFD`, `NFKC`,
+//!   `NFKD`, `StripAccents`, `Strip`, `Replace`, `Prepend` and `Sequence`
+//! - pre-tokenizers: `BertPreTokenizer`, `Whitespace`, `WhitespaceSplit`,
+//!   `ByteLevel`, `Metaspace`, `Punctuation`, `Digits`, `Split`,
+//!   `CharDelimiterSplit` and `Sequence`
+//! - post-processors: `TemplateProcessing`, `RobertaProcessing`,
+//!   `BertProcessing`, `ByteLevel` and `Sequence`
+//! - decoders: `WordPiece`, `BPEDecoder`, `ByteLevel`, `Metaspace`,
+//!   `ByteFallback`, `Fuse`, `Strip`, `Replace` and `Sequence`
+//!
+//! Anything else, including truncation, padding and BPE dropout, is reported
+//! by name and JSON path when the file is loaded rather than approximated.
+//! Offsets are byte offsets into the input text, like every other tokenizer
+//! in this crate.
+
+mod added_vocabulary;
+mod byte_level;
+mod decoder;
+mod loader;
+mod model;
+mod normalized;
+mod normalizer;
+mod pre_tokenizer;
+mod processor;
+
+use crate::error::{Result, TokenizerError};
+use crate::tokenizer::{Token, Tokenizer};
+use added_vocabulary::{AddedToken, AddedVocabulary};
+use decoder::Decoder;
+use loader::{Loader, Object};
+use model::Model;
+use normalizer::Normalizer;
+use pre_tokenizer::PreTokenizer;
+use processor::PostProcessor;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// The result of encoding one text: parallel lists of ids, tokens and the
+/// byte range of the input each token came from. Special tokens added by the
+/// post-processor have the empty range `(0, 0)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Encoding {
+    pub ids: Vec<u32>,
+    pub tokens: Vec<String>,
+    pub offsets: Vec<(usize, usize)>,
+}
+
+impl Encoding {
+    pub(crate) fn extend(&mut self, other: &Encoding) {
+        self.ids.extend(&other.ids);
+        self.tokens.extend(other.tokens.iter().cloned());
+        self.offsets.extend(&other.offsets);
+    }
+}
+
+/// A tokenizer loaded from a Hugging Face `tokenizer.json`.
+pub struct HuggingFaceTokenizer {
+    added_vocabulary: AddedVocabulary,
+    normalizer: Option<Normalizer>,
+    pre_tokenizer: Option<PreTokenizer>,
+    model: Model,
+    post_processor: Option<PostProcessor>,
+    decoder: Option<Decoder>,
+    add_special_tokens: bool,
+}
+
+impl HuggingFaceTokenizer {
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            TokenizerError::InvalidHuggingFaceTokenizer(format!("cannot read {}: {}", path.display(), e))
+        })?;
+        Self::from_json(&json)
+    }
+
+    /// Loads a tokenizer from the contents of a `tokenizer.json`. Every
+    /// unsupported or invalid component is reported at once.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let root: Value = serde_json::from_str(json)
+            .map_err(|e| TokenizerError::InvalidHuggingFaceTokenizer(format!("not valid JSON: {}", e)))?;
+        let Value::Object(root) = root else {
+            return Err(TokenizerError::InvalidHuggingFaceTokenizer(
+                "expected a JSON object".to_string(),
+            ));
+        };
+
+        let mut loader = Loader::default();
+        let tokenizer = Self::load(&mut loader, &root);
+        match tokenizer {
+            Some(tokenizer) if loader.problems.is_empty() => Ok(tokenizer),
+            _ => Err(TokenizerError::InvalidHuggingFaceTokenizer(loader.problems.join("; "))),
+        }
+    }
+
+    fn load(loader: &mut Loader, root: &Object) -> Option<Self> {
+        for key in ["truncation", "padding"] {
+            if !matches!(root.get(key), None | Some(Value::Null)) {
+                loader.problem(key, format!("{} is not supported", key));
+            }
+        }
+
+        // Load every component before giving up, so all problems are reported
+        let normalizer = optional(loader, root, "normalizer", Normalizer::load);
+        let pre_tokenizer = optional(loader, root, "pre_tokenizer", PreTokenizer::load);
+        let model = loader
+            .field("", root, "model")
+            .and_then(|model| loader.object("model", model))
+            .and_then(|model| Model::load(loader, "model", model));
+        let post_processor = optional(loader, root, "post_processor", PostProcessor::load);
+        let decoder = optional(loader, root, "decoder", Decoder::load);
+        let added_tokens = match root.get("added_tokens") {
+            None | Some(Value::Null) => Some(Vec::new()),
+            Some(_) => loader.list("", root, "added_tokens", load_added_token),
+        };
+
+        let normalizer = normalizer?;
+        let added_vocabulary = match AddedVocabulary::new(added_tokens?, normalizer.as_ref()) {
+            Ok(added_vocabulary) => added_vocabulary,
+            Err(e) => {
+                loader.problem("added_tokens", e);
+                return None;
+            }
+        };
+
+        Some(Self {
+            added_vocabulary,
+            normalizer,
+            pre_tokenizer: pre_tokenizer?,
+            model: model?,
+            post_processor: post_processor?,
+            decoder: decoder?,
+            add_special_tokens: true,
+        })
+    }
+
+    /// Whether [`Tokenizer`] methods add the post-processor's special
+    /// tokens, such as `[CLS]` and `[SEP]`. Defaults to `true`.
+    pub fn with_special_tokens(mut self, add_special_tokens: bool) -> Self {
+        self.add_special_tokens = add_special_tokens;
+        self
+    }
+
+    pub fn encode(&self, text: &str, add_special_tokens: bool) -> Encoding {
+        let mut pretokenized = self.added_vocabulary.extract_and_normalize(self.normalizer.as_ref(), text);
+        if let Some(pre_tokenizer) = &self.pre_tokenizer {
+            pre_tokenizer.pre_tokenize(&mut pretokenized);
+        }
+
+        let mut encoding = Encoding::default();
+        for split in &mut pretokenized.splits {
+            let tokens = split
+                .tokens
+                .take()
+                .unwrap_or_else(|| self.model.tokenize(split.normalized.get()));
+            for token in tokens {
+                encoding.ids.push(token.id);
+                encoding.offsets.push(split.normalized.input_offsets(token.offsets));
+                encoding.tokens.push(token.value);
+            }
+        }
+
+        match &self.post_processor {
+            Some(post_processor) => post_processor.process(encoding, add_special_tokens),
+            None => encoding,
+        }
+    }
+
+    /// The text `ids` stand for. Unknown ids are skipped.
+    pub fn decode(&self, ids: &[u32], skip_special_tokens: bool) -> String {
+        let tokens: Vec<String> = ids
+            .iter()
+            .filter_map(|id| self.id_to_token(*id))
+            .filter(|token| !skip_special_tokens || !self.added_vocabulary.is_special(token))
+            .map(str::to_string)
+            .collect();
+
+        match &self.decoder {
+            Some(decoder) => decoder.decode_chain(tokens).concat(),
+            None => tokens.join(" "),
+        }
+    }
+
+    pub fn token_to_id(&self, token: &str) -> Option<u32> {
+        self.added_vocabulary
+            .token_to_id(token)
+            .or_else(|| self.model.token_to_id(token))
+    }
+
+    pub fn id_to_token(&self, id: u32) -> Option<&str> {
+        self.added_vocabulary.id_to_token(id).or_else(|| self.model.id_to_token(id))
+    }
+}
+
+impl Tokenizer for HuggingFaceTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let encoding = self.encode(text, self.add_special_tokens);
+        encoding
+            .tokens
+            .into_iter()
+            .zip(encoding.offsets)
+            .map(|(token, (start, end))| Token::new(Cow::Owned(token), start, end))
+            .collect()
+    }
+
+    // Normalizers and pre-tokenizers can look at the whole input, and special
+    // tokens wrap it, so chunks are not independent
+    fn stream_boundary(&self, _text: &str) -> Option<usize> {
+        None
+    }
+}
+
+// A component that may be null
+fn optional<T>(
+    loader: &mut Loader,
+    root: &Object,
+    key: &str,
+    load: impl FnOnce(&mut Loader, &str, &Object) -> Option<T>,
+) -> Option<Option<T>> {
+    match root.get(key) {
+        None | Some(Value::Null) => Some(None),
+        Some(definition) => {
+            let definition = loader.object(key, definition)?;
+            load(loader, key, definition).map(Some)
+        }
+    }
+}
+
+fn load_added_token(loader: &mut Loader, path: &str, definition: &Object) -> Option<AddedToken> {
+    let id = loader.field(path, definition, "id")?;
+    Some(AddedToken {
+        id: loader.integer(&loader::join(path, "id"), id)?,
+        content: loader.string(path, definition, "content")?,
+        single_word: loader.bool(path, definition, "single_word", false)?,
+        lstrip: loader.bool(path, definition, "lstrip", false)?,
+        rstrip: loader.bool(path, definition, "rstrip", false)?,
+        normalized: loader.bool(path, definition, "normalized", true)?,
+        special: loader.bool(path, definition, "special", false)?,
+    })
+}
@@ -0,0 +1,583 @@
+//! The WordPiece, BPE and Unigram models, which turn each pre-tokenized
+//! piece of text into vocabulary tokens.
+
+use super::loader::{join, Loader, Object};
+use super::normalized::ModelToken;
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+pub(crate) enum Model {
+    WordPiece(WordPiece),
+    Bpe(Bpe),
+    Unigram(Unigram),
+}
+
+impl Model {
+    pub(crate) fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let model = match loader.kind(path, definition)? {
+            "WordPiece" => Self::WordPiece(WordPiece::load(loader, path, definition)?),
+            "BPE" => Self::Bpe(Bpe::load(loader, path, definition)?),
+            "Unigram" => Self::Unigram(Unigram::load(loader, path, definition)?),
+            other => return loader.unsupported(path, "model", other),
+        };
+        Some(model)
+    }
+
+    pub(crate) fn tokenize(&self, sequence: &str) -> Vec<ModelToken> {
+        match self {
+            Self::WordPiece(model) => model.tokenize(sequence),
+            Self::Bpe(model) => model.tokenize(sequence),
+            Self::Unigram(model) => model.tokenize(sequence),
+        }
+    }
+
+    pub(crate) fn token_to_id(&self, token: &str) -> Option<u32> {
+        match self {
+            Self::WordPiece(model) => model.vocab.get(token).copied(),
+            Self::Bpe(model) => model.vocab.get(token).copied(),
+            Self::Unigram(model) => model.token_to_id.get(token).copied(),
+        }
+    }
+
+    pub(crate) fn id_to_token(&self, id: u32) -> Option<&str> {
+        match self {
+            Self::WordPiece(model) => model.vocab_r.get(&id).map(String::as_str),
+            Self::Bpe(model) => model.vocab_r.get(&id).map(String::as_str),
+            Self::Unigram(model) => model.vocab.get(id as usize).map(|(token, _)| token.as_str()),
+        }
+    }
+}
+
+type Vocab = HashMap<String, u32>;
+
+fn load_vocab(loader: &mut Loader, path: &str, definition: &Object) -> Option<(Vocab, HashMap<u32, String>)> {
+    let path = join(path, "vocab");
+    let Some(Value::Object(entries)) = definition.get("vocab") else {
+        loader.problem(&path, "expected an object of token ids");
+        return None;
+    };
+
+    let mut vocab = Vocab::with_capacity(entries.len());
+    for (token, id) in entries {
+        vocab.insert(token.clone(), loader.integer(&format!("{}.{}", path, token), id)?);
+    }
+    let vocab_r = vocab.iter().map(|(token, id)| (*id, token.clone())).collect();
+    Some((vocab, vocab_r))
+}
+
+fn check_in_vocab(loader: &mut Loader, path: &str, vocab: &Vocab, token: &str) -> Option<u32> {
+    let id = vocab.get(token).copied();
+    if id.is_none() {
+        loader.problem(path, format!("'{}' is not in the vocabulary", token));
+    }
+    id
+}
+
+/// Greedy longest-match-first subwords, as in BERT.
+pub(crate) struct WordPiece {
+    vocab: Vocab,
+    vocab_r: HashMap<u32, String>,
+    unk_token: String,
+    unk_id: u32,
+    continuing_subword_prefix: String,
+    max_input_chars_per_word: usize,
+}
+
+impl WordPiece {
+    fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let (vocab, vocab_r) = load_vocab(loader, path, definition)?;
+        let unk_token = loader.optional_string(path, definition, "unk_token")?.unwrap_or_else(|| "[UNK]".to_string());
+        let unk_id = check_in_vocab(loader, &join(path, "unk_token"), &vocab, &unk_token)?;
+        let continuing_subword_prefix = loader
+            .optional_string(path, definition, "continuing_subword_prefix")?
+            .unwrap_or_else(|| "##".to_string());
+
+        Some(Self {
+            max_input_chars_per_word: loader.usize(path, definition, "max_input_chars_per_word", 100)?,
+            vocab,
+            vocab_r,
+            unk_token,
+            unk_id,
+            continuing_subword_prefix,
+        })
+    }
+
+    fn tokenize(&self, sequence: &str) -> Vec<ModelToken> {
+        let unknown = || {
+            vec![ModelToken {
+                id: self.unk_id,
+                value: self.unk_token.clone(),
+                offsets: (0, sequence.len()),
+            }]
+        };
+        if sequence.chars().count() > self.max_input_chars_per_word {
+            return unknown();
+        }
+
+        let mut tokens = Vec::new();
+        let mut start = 0;
+        while start < sequence.len() {
+            let mut end = sequence.len();
+            let mut found = None;
+            while start < end {
+                let piece = &sequence[start..end];
+                let candidate = if start > 0 {
+                    format!("{}{}", self.continuing_subword_prefix, piece)
+                } else {
+                    piece.to_string()
+                };
+                if let Some(&id) = self.vocab.get(&candidate) {
+                    found = Some(ModelToken {
+                        id,
+                        value: candidate,
+                        offsets: (start, end),
+                    });
+                    break;
+                }
+                end -= piece.chars().next_back().map_or(1, char::len_utf8);
+            }
+
+            match found {
+                Some(token) => tokens.push(token),
+                None => return unknown(),
+            }
+            start = end;
+        }
+        tokens
+    }
+}
+
+/// Byte-pair encoding: characters merged pairwise in the order the merges
+/// were learned.
+pub(crate) struct Bpe {
+    vocab: Vocab,
+    vocab_r: HashMap<u32, String>,
+    // (left, right) -> (rank, merged id)
+    merges: HashMap<(u32, u32), (u32, u32)>,
+    unk_id: Option<u32>,
+    continuing_subword_prefix: Option<String>,
+    end_of_word_suffix: Option<String>,
+    fuse_unk: bool,
+    byte_fallback: bool,
+    ignore_merges: bool,
+}
+
+impl Bpe {
+    fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        if let Some(dropout) = definition.get("dropout").and_then(Value::as_f64) {
+            if dropout != 0.0 {
+                loader.problem(&join(path, "dropout"), "BPE dropout is not supported, it makes tokenization random");
+                return None;
+            }
+        }
+
+        let (vocab, vocab_r) = load_vocab(loader, path, definition)?;
+        let unk_id = match loader.optional_string(path, definition, "unk_token")? {
+            Some(unk_token) => Some(check_in_vocab(loader, &join(path, "unk_token"), &vocab, &unk_token)?),
+            None => None,
+        };
+        let continuing_subword_prefix = loader.optional_string(path, definition, "continuing_subword_prefix")?;
+        let merges = Self::load_merges(loader, path, definition, &vocab, continuing_subword_prefix.as_deref())?;
+
+        Some(Self {
+            fuse_unk: loader.bool(path, definition, "fuse_unk", false)?,
+            byte_fallback: loader.bool(path, definition, "byte_fallback", false)?,
+            ignore_merges: loader.bool(path, definition, "ignore_merges", false)?,
+            end_of_word_suffix: loader.optional_string(path, definition, "end_of_word_suffix")?,
+            vocab,
+            vocab_r,
+            merges,
+            unk_id,
+            continuing_subword_prefix,
+        })
+    }
+
+    // Merges are "left right" strings in older files and [left, right] pairs in newer ones
+    fn load_merges(
+        loader: &mut Loader,
+        path: &str,
+        definition: &Object,
+        vocab: &Vocab,
+        prefix: Option<&str>,
+    ) -> Option<HashMap<(u32, u32), (u32, u32)>> {
+        let path = join(path, "merges");
+        let Some(Value::Array(entries)) = definition.get("merges") else {
+            loader.problem(&path, "expected a list of merges");
+            return None;
+        };
+        let prefix_len = prefix.map_or(0, str::len);
+
+        let mut merges = HashMap::with_capacity(entries.len());
+        for (rank, entry) in entries.iter().enumerate() {
+            let path = format!("{}[{}]", path, rank);
+            let pair = match entry {
+                Value::String(merge) => merge.split_once(' '),
+                Value::Array(pair) => match pair.as_slice() {
+                    [Value::String(left), Value::String(right)] => Some((left.as_str(), right.as_str())),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let Some((left, right)) = pair else {
+                loader.problem(&path, "expected a pair of tokens");
+                return None;
+            };
+
+            let merged = format!("{}{}", left, right.get(prefix_len..).unwrap_or(right));
+            let left = check_in_vocab(loader, &path, vocab, left)?;
+            let right = check_in_vocab(loader, &path, vocab, right)?;
+            let merged = check_in_vocab(loader, &path, vocab, &merged)?;
+            merges.insert((left, right), (u32::try_from(rank).ok()?, merged));
+        }
+        Some(merges)
+    }
+
+    fn tokenize(&self, sequence: &str) -> Vec<ModelToken> {
+        if sequence.is_empty() {
+            return Vec::new();
+        }
+        if self.ignore_merges {
+            if let Some(&id) = self.vocab.get(sequence) {
+                return vec![ModelToken {
+                    id,
+                    value: sequence.to_string(),
+                    offsets: (0, sequence.len()),
+                }];
+            }
+        }
+
+        let mut word = self.word(sequence);
+        word.merge_all(&self.merges);
+
+        let mut offset = 0;
+        word.symbols
+            .iter()
+            .filter(|symbol| symbol.len > 0)
+            .map(|symbol| {
+                let token = ModelToken {
+                    id: symbol.id,
+                    value: self.vocab_r.get(&symbol.id).cloned().unwrap_or_default(),
+                    offsets: (offset, offset + symbol.len),
+                };
+                offset += symbol.len;
+                token
+            })
+            .collect()
+    }
+
+    // The symbols of `sequence` before any merge
+    fn word(&self, sequence: &str) -> Word {
+        let mut word = Word::default();
+        let mut unk: Option<(u32, usize)> = None;
+        let mut chars = sequence.char_indices().peekable();
+
+        while let Some((i, c)) = chars.next() {
+            let is_last = chars.peek().is_none();
+            let byte_len = c.len_utf8();
+            let mut symbol = sequence[i..i + byte_len].to_string();
+            if i > 0 {
+                if let Some(prefix) = &self.continuing_subword_prefix {
+                    symbol.insert_str(0, prefix);
+                }
+            }
+            if is_last {
+                if let Some(suffix) = &self.end_of_word_suffix {
+                    symbol.push_str(suffix);
+                }
+            }
+
+            if let Some(&id) = self.vocab.get(&symbol) {
+                if let Some((unk_id, unk_len)) = unk.take() {
+                    word.add(unk_id, unk_len);
+                }
+                word.add(id, byte_len);
+                continue;
+            }
+
+            if self.byte_fallback {
+                let bytes: Option<Vec<u32>> = symbol
+                    .bytes()
+                    .map(|b| self.vocab.get(&format!("<0x{:02X}>", b)).copied())
+                    .collect();
+                if let Some(bytes) = bytes {
+                    // A pending unknown is only flushed by the next known symbol
+                    for id in bytes {
+                        word.add(id, 1);
+                    }
+                    continue;
+                }
+            }
+
+            if let Some(unk_id) = self.unk_id {
+                unk = match unk {
+                    Some((unk_id, unk_len)) if self.fuse_unk => Some((unk_id, unk_len + byte_len)),
+                    Some((pending, unk_len)) => {
+                        word.add(pending, unk_len);
+                        Some((unk_id, byte_len))
+                    }
+                    None => Some((unk_id, byte_len)),
+                };
+            }
+        }
+        if let Some((unk_id, unk_len)) = unk {
+            word.add(unk_id, unk_len);
+        }
+        word
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Symbol {
+    id: u32,
+    prev: Option<usize>,
+    next: Option<usize>,
+    // Bytes of the sequence covered; 0 once merged into the previous symbol
+    len: usize,
+}
+
+#[derive(Default)]
+struct Word {
+    symbols: Vec<Symbol>,
+}
+
+impl Word {
+    fn add(&mut self, id: u32, len: usize) {
+        let index = self.symbols.len();
+        let prev = self.symbols.last_mut().map(|last| {
+            last.next = Some(index);
+            index - 1
+        });
+        self.symbols.push(Symbol { id, prev, next: None, len });
+    }
+
+    // Applies the lowest-ranked merge available, leftmost first, until none is left
+    fn merge_all(&mut self, merges: &HashMap<(u32, u32), (u32, u32)>) {
+        let mut queue: BinaryHeap<Reverse<(u32, usize, u32)>> = self
+            .symbols
+            .windows(2)
+            .enumerate()
+            .filter_map(|(pos, pair)| {
+                merges
+                    .get(&(pair[0].id, pair[1].id))
+                    .map(|&(rank, merged)| Reverse((rank, pos, merged)))
+            })
+            .collect();
+
+        while let Some(Reverse((_, pos, merged))) = queue.pop() {
+            let current = self.symbols[pos];
+            if current.len == 0 {
+                continue;
+            }
+            let Some(next_pos) = current.next else { continue };
+            let right = self.symbols[next_pos];
+
+            // Skip merges made stale by an earlier merge
+            if merges.get(&(current.id, right.id)).is_none_or(|&(_, id)| id != merged) {
+                continue;
+            }
+
+            let symbol = &mut self.symbols[pos];
+            symbol.id = merged;
+            symbol.len += right.len;
+            symbol.next = right.next;
+            self.symbols[next_pos].len = 0;
+            if let Some(next) = right.next {
+                self.symbols[next].prev = Some(pos);
+            }
+
+            let current = self.symbols[pos];
+            if let Some(prev) = current.prev {
+                if let Some(&(rank, merged)) = merges.get(&(self.symbols[prev].id, current.id)) {
+                    queue.push(Reverse((rank, prev, merged)));
+                }
+            }
+            if let Some(next) = current.next {
+                if let Some(&(rank, merged)) = merges.get(&(current.id, self.symbols[next].id)) {
+                    queue.push(Reverse((rank, pos, merged)));
+                }
+            }
+        }
+    }
+}
+
+// Penalty for characters no vocabulary piece covers, relative to the rarest piece
+const UNK_PENALTY: f64 = 10.0;
+
+/// Unigram language model: the segmentation with the highest total score.
+pub(crate) struct Unigram {
+    vocab: Vec<(String, f64)>,
+    token_to_id: Vocab,
+    unk_id: u32,
+    min_score: f64,
+    max_piece_len: usize,
+    byte_fallback: bool,
+}
+
+impl Unigram {
+    fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let vocab_path = join(path, "vocab");
+        let Some(Value::Array(entries)) = definition.get("vocab") else {
+            loader.problem(&vocab_path, "expected a list of [token, score] pairs");
+            return None;
+        };
+
+        let mut vocab = Vec::with_capacity(entries.len());
+        for (i, entry) in entries.iter().enumerate() {
+            match entry.as_array().map(Vec::as_slice) {
+                Some([Value::String(token), score]) if score.is_number() => {
+                    vocab.push((token.clone(), score.as_f64().unwrap_or_default()));
+                }
+                _ => {
+                    loader.problem(&format!("{}[{}]", vocab_path, i), "expected a [token, score] pair");
+                    return None;
+                }
+            }
+        }
+
+        // Without an unknown token, text the vocabulary can't cover can't be encoded
+        let unk_id: usize = match definition.get("unk_id") {
+            Some(Value::Null) | None => {
+                loader.problem(&join(path, "unk_id"), "Unigram models without an unknown token are not supported");
+                return None;
+            }
+            Some(id) => loader.integer(&join(path, "unk_id"), id)?,
+        };
+        if unk_id >= vocab.len() {
+            loader.problem(&join(path, "unk_id"), format!("{} is not in the vocabulary", unk_id));
+            return None;
+        }
+
+        let mut token_to_id = Vocab::with_capacity(vocab.len());
+        for (id, (token, _)) in vocab.iter().enumerate() {
+            token_to_id.insert(token.clone(), u32::try_from(id).ok()?);
+        }
+
+        Some(Self {
+            token_to_id,
+            unk_id: u32::try_from(unk_id).ok()?,
+            min_score: vocab.iter().map(|(_, score)| *score).fold(f64::INFINITY, f64::min),
+            max_piece_len: vocab.iter().map(|(token, _)| token.len()).max().unwrap_or(0),
+            byte_fallback: loader.bool(path, definition, "byte_fallback", false)?,
+            vocab,
+        })
+    }
+
+    fn tokenize(&self, sequence: &str) -> Vec<ModelToken> {
+        let mut tokens = Vec::new();
+        let mut offset = 0;
+        for piece in self.encode(sequence) {
+            let offsets = (offset, offset + piece.len());
+            offset += piece.len();
+
+            if let Some(&id) = self.token_to_id.get(piece) {
+                tokens.push(ModelToken {
+                    id,
+                    value: piece.to_string(),
+                    offsets,
+                });
+                continue;
+            }
+            if self.byte_fallback {
+                let bytes: Option<Vec<ModelToken>> = piece
+                    .bytes()
+                    .map(|b| {
+                        let value = format!("<0x{:02X}>", b);
+                        let id = *self.token_to_id.get(&value)?;
+                        Some(ModelToken { id, value, offsets })
+                    })
+                    .collect();
+                if let Some(bytes) = bytes {
+                    tokens.extend(bytes);
+                    continue;
+                }
+            }
+            tokens.push(ModelToken {
+                id: self.unk_id,
+                value: piece.to_string(),
+                offsets,
+            });
+        }
+        tokens
+    }
+
+    // Viterbi over every vocabulary piece starting at each character; runs of
+    // unknown characters are fused into one piece
+    fn encode<'s>(&self, sentence: &'s str) -> Vec<&'s str> {
+        #[derive(Clone, Copy)]
+        struct Best {
+            id: u32,
+            score: f64,
+            starts_at: Option<usize>,
+        }
+
+        let size = sentence.len();
+        let unk_score = self.min_score - UNK_PENALTY;
+        let mut best = vec![
+            Best {
+                id: 0,
+                score: 0.0,
+                starts_at: None,
+            };
+            size + 1
+        ];
+
+        for (start, c) in sentence.char_indices() {
+            let score_here = best[start].score;
+            let char_len = c.len_utf8();
+            let mut has_single_char_piece = false;
+
+            let longest = (size - start).min(self.max_piece_len);
+            for end in (start + 1..=start + longest).filter(|&end| sentence.is_char_boundary(end)) {
+                let Some(&id) = self.token_to_id.get(&sentence[start..end]) else {
+                    continue;
+                };
+                let score = score_here + self.vocab[id as usize].1;
+                let target = &mut best[end];
+                if target.starts_at.is_none() || score > target.score {
+                    *target = Best {
+                        id,
+                        score,
+                        starts_at: Some(start),
+                    };
+                }
+                has_single_char_piece |= end - start == char_len;
+            }
+
+            if !has_single_char_piece {
+                let score = score_here + unk_score;
+                let target = &mut best[start + char_len];
+                if target.starts_at.is_none() || score > target.score {
+                    *target = Best {
+                        id: self.unk_id,
+                        score,
+                        starts_at: Some(start),
+                    };
+                }
+            }
+        }
+
+        let mut pieces = Vec::new();
+        let mut unk_start = None;
+        let mut end = size;
+        while end > 0 {
+            let node = best[end];
+            let start = node.starts_at.unwrap_or(0);
+            if node.id == self.unk_id {
+                // Extend the run of unknowns back to `start`
+                unk_start.get_or_insert(end);
+            } else {
+                if let Some(unk_end) = unk_start.take() {
+                    pieces.push(&sentence[end..unk_end]);
+                }
+                pieces.push(&sentence[start..end]);
+            }
+            end = start;
+        }
+        if let Some(unk_end) = unk_start {
+            pieces.push(&sentence[0..unk_end]);
+        }
+        pieces.reverse();
+        pieces
+    }
+}
@@ -0,0 +1,490 @@
+//! Text that remembers where every byte came from.
+//!
+//! A port of the `tokenizers` `NormalizedString` and `PreTokenizedString`.
+//! Normalizers and pre-tokenizers rewrite and cut the text, and each
+//! normalized byte keeps the byte range of the original input it was produced
+//! from, which is what final token offsets are computed from. The alignment
+//! rules (what an inserted or removed character maps to) follow the reference
+//! implementation exactly, because that is where offsets would drift.
+
+use std::ops::Range;
+use unicode_normalization_alignments::UnicodeNormalization;
+
+pub(crate) type Offsets = (usize, usize);
+
+/// What happens to the delimiters found by a split pattern.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SplitBehavior {
+    Removed,
+    Isolated,
+    MergedWithPrevious,
+    MergedWithNext,
+    Contiguous,
+}
+
+/// Finds the pieces of `inside`, flagging the ones that matched.
+/// Together the pieces always cover the whole input.
+pub(crate) trait Pattern {
+    fn find_matches(&self, inside: &str) -> Vec<(Offsets, bool)>;
+}
+
+impl Pattern for char {
+    fn find_matches(&self, inside: &str) -> Vec<(Offsets, bool)> {
+        (|c: char| c == *self).find_matches(inside)
+    }
+}
+
+// Every matching character is its own match
+impl<F: Fn(char) -> bool> Pattern for F {
+    fn find_matches(&self, inside: &str) -> Vec<(Offsets, bool)> {
+        if inside.is_empty() {
+            return vec![((0, 0), false)];
+        }
+
+        let mut matches = Vec::new();
+        let mut last_offset = 0;
+        for (b, c) in inside.char_indices() {
+            if self(c) {
+                if last_offset < b {
+                    matches.push(((last_offset, b), false));
+                }
+                matches.push(((b, b + c.len_utf8()), true));
+                last_offset = b + c.len_utf8();
+            }
+        }
+        if last_offset < inside.len() {
+            matches.push(((last_offset, inside.len()), false));
+        }
+        matches
+    }
+}
+
+impl Pattern for fancy_regex::Regex {
+    fn find_matches(&self, inside: &str) -> Vec<(Offsets, bool)> {
+        if inside.is_empty() {
+            return vec![((0, 0), false)];
+        }
+
+        let mut matches = Vec::new();
+        let mut prev = 0;
+        // A match that hits the backtracking limit ends the search; the rest
+        // of the input is left unmatched
+        for m in self.find_iter(inside).map_while(Result::ok) {
+            if prev != m.start() {
+                matches.push(((prev, m.start()), false));
+            }
+            matches.push(((m.start(), m.end()), true));
+            prev = m.end();
+        }
+        if prev != inside.len() {
+            matches.push(((prev, inside.len()), false));
+        }
+        matches
+    }
+}
+
+/// Flips which pieces of the inner pattern count as matches.
+pub(crate) struct Invert<'p, P: Pattern + ?Sized>(pub &'p P);
+
+impl<P: Pattern + ?Sized> Pattern for Invert<'_, P> {
+    fn find_matches(&self, inside: &str) -> Vec<(Offsets, bool)> {
+        self.0
+            .find_matches(inside)
+            .into_iter()
+            .map(|(offsets, is_match)| (offsets, !is_match))
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct NormalizedString {
+    original: String,
+    normalized: String,
+    // The original byte range of each normalized byte
+    alignments: Vec<Offsets>,
+    // Where `original` starts in the text this string was sliced from
+    original_shift: usize,
+}
+
+impl From<&str> for NormalizedString {
+    fn from(text: &str) -> Self {
+        let alignments = text
+            .char_indices()
+            .flat_map(|(b, c)| std::iter::repeat_n((b, b + c.len_utf8()), c.len_utf8()))
+            .collect();
+        Self {
+            original: text.to_string(),
+            normalized: text.to_string(),
+            alignments,
+            original_shift: 0,
+        }
+    }
+}
+
+impl NormalizedString {
+    pub(crate) fn get(&self) -> &str {
+        &self.normalized
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.normalized.is_empty()
+    }
+
+    /// Range of the whole input this string covers.
+    pub(crate) fn offsets_original(&self) -> Offsets {
+        (self.original_shift, self.original_shift + self.original.len())
+    }
+
+    /// Range of the whole input that the normalized bytes `range` came from.
+    pub(crate) fn input_offsets(&self, range: Offsets) -> Offsets {
+        match self.original_range(range.0..range.1) {
+            Some(original) => (self.original_shift + original.start, self.original_shift + original.end),
+            None => range,
+        }
+    }
+
+    fn original_range(&self, range: Range<usize>) -> Option<Range<usize>> {
+        if range.start == range.end {
+            return Some(range);
+        }
+        if range.start > range.end {
+            return None;
+        }
+        let alignments = self.alignments.get(range)?;
+        Some(alignments.first()?.0..alignments.last()?.1)
+    }
+
+    fn normalized_range(&self, target: Range<usize>) -> Option<Range<usize>> {
+        if target.start == target.end {
+            return Some(target);
+        }
+        if target.start > target.end {
+            return None;
+        }
+
+        let (mut start, mut end) = (None, None);
+        for (i, alignment) in self.alignments.iter().enumerate() {
+            if target.end < alignment.1 {
+                break;
+            }
+            // Zero-width alignments can't start a range
+            if start.is_none() && target.start <= alignment.0 && alignment.0 != alignment.1 {
+                start = Some(i);
+            }
+            end = Some(i + 1);
+        }
+
+        match (start, end) {
+            (Some(s), None) => Some(s..s),
+            (None, Some(e)) => Some(e..e),
+            (Some(s), Some(e)) => Some(s..e),
+            (None, None) => None,
+        }
+    }
+
+    /// The part of this string at normalized bytes `range`.
+    pub(crate) fn slice(&self, range: Range<usize>) -> Option<NormalizedString> {
+        if !(self.normalized.is_char_boundary(range.start) && self.normalized.is_char_boundary(range.end)) {
+            return None;
+        }
+        let original_range = self.original_range(range.clone())?;
+        let shift = original_range.start;
+
+        Some(Self {
+            original: self.original.get(original_range).unwrap_or_default().to_string(),
+            normalized: self.normalized.get(range.clone()).unwrap_or_default().to_string(),
+            alignments: self
+                .alignments
+                .get(range)?
+                .iter()
+                .map(|(start, end)| (start.saturating_sub(shift), end.saturating_sub(shift)))
+                .collect(),
+            original_shift: self.original_shift + shift,
+        })
+    }
+
+    /// Replaces the normalized bytes `range` with `dest`, whose entries are a
+    /// character and a change: 0 replaces the next character, 1 inserts a new
+    /// one, and -n replaces the next character and removes the n after it.
+    /// `initial_offset` characters are removed before anything else.
+    fn transform_range(&mut self, range: Range<usize>, dest: impl IntoIterator<Item = (char, isize)>, initial_offset: usize) {
+        let mut replaced = self.normalized[range.clone()].chars();
+        let initial_removed: usize = (&mut replaced).take(initial_offset).map(char::len_utf8).sum();
+
+        let mut offset = range.start + initial_removed;
+        let mut normalized = String::with_capacity(range.len());
+        let mut alignments = Vec::with_capacity(range.len());
+        for (c, change) in dest {
+            let alignment = if change > 0 {
+                // An inserted character shares the alignment of the one before it
+                if offset == 0 {
+                    (0, 0)
+                } else {
+                    self.alignments[offset - 1]
+                }
+            } else {
+                self.alignments[offset]
+            };
+
+            if change <= 0 {
+                offset += replaced.next().map_or(0, char::len_utf8);
+            }
+            if change < 0 {
+                offset += (&mut replaced).take(change.unsigned_abs()).map(char::len_utf8).sum::<usize>();
+            }
+
+            alignments.extend(std::iter::repeat_n(alignment, c.len_utf8()));
+            normalized.push(c);
+        }
+
+        self.alignments.splice(range.clone(), alignments);
+        self.normalized.replace_range(range, &normalized);
+    }
+
+    fn transform(&mut self, dest: impl IntoIterator<Item = (char, isize)>, initial_offset: usize) {
+        if let Some(range) = self.normalized_range(0..self.original.len()) {
+            self.transform_range(range, dest, initial_offset);
+        }
+    }
+
+    pub(crate) fn nfd(&mut self) {
+        let dest: Vec<_> = self.normalized.as_str().nfd().collect();
+        self.transform(dest, 0);
+    }
+
+    pub(crate) fn nfkd(&mut self) {
+        let dest: Vec<_> = self.normalized.as_str().nfkd().collect();
+        self.transform(dest, 0);
+    }
+
+    pub(crate) fn nfc(&mut self) {
+        let dest: Vec<_> = self.normalized.as_str().nfc().collect();
+        self.transform(dest, 0);
+    }
+
+    pub(crate) fn nfkc(&mut self) {
+        let dest: Vec<_> = self.normalized.as_str().nfkc().collect();
+        self.transform(dest, 0);
+    }
+
+    pub(crate) fn filter(&mut self, keep: impl Fn(char) -> bool) {
+        let mut removed = 0;
+        let mut removed_start = 0;
+        let mut dest = Vec::with_capacity(self.normalized.len());
+        let mut last = None;
+        for c in self.normalized.chars() {
+            if keep(c) {
+                match last {
+                    Some(last) => dest.push((last, -removed)),
+                    None => removed_start = removed as usize,
+                }
+                last = Some(c);
+                removed = 0;
+            } else {
+                removed += 1;
+            }
+        }
+        if let Some(last) = last {
+            dest.push((last, -removed));
+        }
+        self.transform(dest, removed_start);
+    }
+
+    pub(crate) fn map(&mut self, f: impl Fn(char) -> char) {
+        let dest: Vec<_> = self.normalized.chars().map(|c| (f(c), 0)).collect();
+        self.transform(dest, 0);
+    }
+
+    /// Rewrites every character into the characters `f` pushes for it, the
+    /// first with change 0 and the others as insertions.
+    pub(crate) fn flat_map(&mut self, f: impl Fn(char, &mut Vec<(char, isize)>)) {
+        let mut dest = Vec::with_capacity(self.normalized.len());
+        for c in self.normalized.chars() {
+            f(c, &mut dest);
+        }
+        self.transform(dest, 0);
+    }
+
+    pub(crate) fn lowercase(&mut self) {
+        self.flat_map(|c, dest| {
+            dest.extend(c.to_lowercase().enumerate().map(|(i, c)| (c, isize::from(i > 0))));
+        });
+    }
+
+    pub(crate) fn prepend(&mut self, s: &str) {
+        if let Some(next) = self.normalized.chars().next() {
+            let dest = s
+                .chars()
+                .enumerate()
+                .map(|(i, c)| (c, isize::from(i != 0)))
+                .chain(std::iter::once((next, 1)));
+            self.transform_range(0..next.len_utf8(), dest, 0);
+        }
+    }
+
+    /// Replaces every match of `pattern` with `content`, which maps to the
+    /// last byte of the text it replaced.
+    pub(crate) fn replace(&mut self, pattern: &(impl Pattern + ?Sized), content: &str) {
+        let mut normalized = String::with_capacity(self.normalized.len());
+        let mut alignments = Vec::with_capacity(self.alignments.len());
+        let mut last_end = 0;
+
+        for ((start, end), is_match) in pattern.find_matches(&self.normalized) {
+            if !is_match {
+                continue;
+            }
+            normalized.push_str(&self.normalized[last_end..start]);
+            alignments.extend_from_slice(&self.alignments[last_end..start]);
+
+            let alignment = if end == 0 { (0, 0) } else { self.alignments[end - 1] };
+            alignments.extend(std::iter::repeat_n(alignment, content.len()));
+            normalized.push_str(content);
+            last_end = end;
+        }
+        normalized.push_str(&self.normalized[last_end..]);
+        alignments.extend_from_slice(&self.alignments[last_end..]);
+
+        self.normalized = normalized;
+        self.alignments = alignments;
+    }
+
+    pub(crate) fn split(&self, pattern: &(impl Pattern + ?Sized), behavior: SplitBehavior) -> Vec<NormalizedString> {
+        let matches = pattern.find_matches(&self.normalized);
+
+        // (piece, whether to drop it)
+        let pieces: Vec<(Offsets, bool)> = match behavior {
+            SplitBehavior::Isolated => matches.into_iter().map(|(offsets, _)| (offsets, false)).collect(),
+            SplitBehavior::Removed => matches,
+            SplitBehavior::Contiguous => {
+                let mut previous_match = false;
+                let mut pieces: Vec<(Offsets, bool)> = Vec::new();
+                for (offsets, is_match) in matches {
+                    match pieces.last_mut() {
+                        Some(((_, end), _)) if is_match == previous_match => *end = offsets.1,
+                        _ => pieces.push((offsets, false)),
+                    }
+                    previous_match = is_match;
+                }
+                pieces
+            }
+            SplitBehavior::MergedWithPrevious => {
+                let mut previous_match = false;
+                let mut pieces: Vec<(Offsets, bool)> = Vec::new();
+                for (offsets, is_match) in matches {
+                    match pieces.last_mut() {
+                        Some(((_, end), _)) if is_match && !previous_match => *end = offsets.1,
+                        _ => pieces.push((offsets, false)),
+                    }
+                    previous_match = is_match;
+                }
+                pieces
+            }
+            SplitBehavior::MergedWithNext => {
+                let mut previous_match = false;
+                let mut pieces: Vec<(Offsets, bool)> = Vec::new();
+                for (offsets, is_match) in matches.into_iter().rev() {
+                    match pieces.last_mut() {
+                        Some(((start, _), _)) if is_match && !previous_match => *start = offsets.0,
+                        _ => pieces.push((offsets, false)),
+                    }
+                    previous_match = is_match;
+                }
+                pieces.reverse();
+                pieces
+            }
+        };
+
+        pieces
+            .into_iter()
+            .filter(|(_, remove)| !remove)
+            .filter_map(|((start, end), _)| self.slice(start..end))
+            .collect()
+    }
+
+    pub(crate) fn strip(&mut self, left: bool, right: bool) {
+        let leading = if left {
+            self.normalized.chars().take_while(|c| c.is_whitespace()).count()
+        } else {
+            0
+        };
+        let trailing = if right {
+            self.normalized.chars().rev().take_while(|c| c.is_whitespace()).count()
+        } else {
+            0
+        };
+        if leading == 0 && trailing == 0 {
+            return;
+        }
+
+        let count = self.normalized.chars().count();
+        // The reference compares a character index with a byte length here,
+        // so trailing whitespace after non-ASCII text keeps its alignment
+        let last = self.normalized.len().checked_sub(trailing + 1);
+        let dest: Vec<_> = self
+            .normalized
+            .chars()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                if i < leading || i >= count - trailing {
+                    None
+                } else if Some(i) == last {
+                    Some((c, -(trailing as isize)))
+                } else {
+                    Some((c, 0))
+                }
+            })
+            .collect();
+        self.transform(dest, leading);
+    }
+}
+
+/// A piece of pre-tokenized text, and its tokens once the model has run.
+pub(crate) struct Split {
+    pub(crate) normalized: NormalizedString,
+    pub(crate) tokens: Option<Vec<ModelToken>>,
+}
+
+impl From<NormalizedString> for Split {
+    fn from(normalized: NormalizedString) -> Self {
+        Self { normalized, tokens: None }
+    }
+}
+
+/// A token produced by a model, with offsets into its split's normalized text.
+#[derive(Clone, Debug)]
+pub(crate) struct ModelToken {
+    pub(crate) id: u32,
+    pub(crate) value: String,
+    pub(crate) offsets: Offsets,
+}
+
+pub(crate) struct PreTokenizedString {
+    pub(crate) splits: Vec<Split>,
+}
+
+impl PreTokenizedString {
+    /// Replaces every split that has no tokens yet with the splits `f`
+    /// returns for it, dropping empty ones.
+    pub(crate) fn split<S: Into<Split>>(&mut self, mut f: impl FnMut(NormalizedString) -> Vec<S>) {
+        let mut splits = Vec::with_capacity(self.splits.len());
+        for split in self.splits.drain(..) {
+            if split.tokens.is_some() {
+                splits.push(split);
+                continue;
+            }
+            splits.extend(
+                f(split.normalized)
+                    .into_iter()
+                    .map(Into::into)
+                    .filter(|split: &Split| !split.normalized.is_empty()),
+            );
+        }
+        self.splits = splits;
+    }
+
+    pub(crate) fn normalize(&mut self, f: impl Fn(&mut NormalizedString)) {
+        for split in self.splits.iter_mut().filter(|split| split.tokens.is_none()) {
+            f(&mut split.normalized);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use super::loader::{Loader, Object};
+use super::normalized::NormalizedString;
+use fancy_regex::Regex;
+use unicode_categories::UnicodeCategories;
+use unicode_normalization_alignments::char::is_combining_mark;
+
+pub(crate) enum Normalizer {
+    Bert {
+        clean_text: bool,
+        handle_chinese_chars: bool,
+        strip_accents: bool,
+        lowercase: bool,
+    },
+    Lowercase,
+    Nfc,
+    Nfd,
+    Nfkc,
+    Nfkd,
+    StripAccents,
+    Strip {
+        left: bool,
+        right: bool,
+    },
+    Replace {
+        pattern: Regex,
+        content: String,
+    },
+    Prepend(String),
+    Sequence(Vec<Normalizer>),
+}
+
+impl Normalizer {
+    pub(crate) fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let normalizer = match loader.kind(path, definition)? {
+            "BertNormalizer" => {
+                let lowercase = loader.bool(path, definition, "lowercase", true)?;
+                Self::Bert {
+                    clean_text: loader.bool(path, definition, "clean_text", true)?,
+                    handle_chinese_chars: loader.bool(path, definition, "handle_chinese_chars", true)?,
+                    // Follows `lowercase` unless set
+                    strip_accents: loader.bool(path, definition, "strip_accents", lowercase)?,
+                    lowercase,
+                }
+            }
+            "Lowercase" => Self::Lowercase,
+            "NFC" => Self::Nfc,
+            "NFD" => Self::Nfd,
+            "NFKC" => Self::Nfkc,
+            "NFKD" => Self::Nfkd,
+            "StripAccents" => Self::StripAccents,
+            "Strip" => Self::Strip {
+                left: loader.bool(path, definition, "strip_left", true)?,
+                right: loader.bool(path, definition, "strip_right", true)?,
+            },
+            "Replace" => Self::Replace {
+                pattern: loader.pattern(path, definition)?,
+                content: loader.string(path, definition, "content")?,
+            },
+            "Prepend" => Self::Prepend(loader.string(path, definition, "prepend")?),
+            "Sequence" => Self::Sequence(loader.list(path, definition, "normalizers", Self::load)?),
+            other => return loader.unsupported(path, "normalizer", other),
+        };
+        Some(normalizer)
+    }
+
+    pub(crate) fn normalize(&self, normalized: &mut NormalizedString) {
+        match self {
+            Self::Bert {
+                clean_text,
+                handle_chinese_chars,
+                strip_accents,
+                lowercase,
+            } => {
+                if *clean_text {
+                    normalized.filter(|c| !(c == '\0' || c == '\u{fffd}' || is_control(c)));
+                    normalized.map(|c| if is_bert_whitespace(c) { ' ' } else { c });
+                }
+                if *handle_chinese_chars {
+                    normalized.flat_map(|c, dest| {
+                        if is_chinese_char(c) {
+                            dest.extend([(' ', 0), (c, 1), (' ', 1)]);
+                        } else {
+                            dest.push((c, 0));
+                        }
+                    });
+                }
+                if *strip_accents {
+                    normalized.nfd();
+                    normalized.filter(|c| !c.is_mark_nonspacing());
+                }
+                if *lowercase {
+                    normalized.lowercase();
+                }
+            }
+            Self::Lowercase => normalized.lowercase(),
+            Self::Nfc => normalized.nfc(),
+            Self::Nfd => normalized.nfd(),
+            Self::Nfkc => normalized.nfkc(),
+            Self::Nfkd => normalized.nfkd(),
+            Self::StripAccents => normalized.filter(|c| !is_combining_mark(c)),
+            Self::Strip { left, right } => normalized.strip(*left, *right),
+            Self::Replace { pattern, content } => normalized.replace(pattern, content),
+            Self::Prepend(prepend) => {
+                if !normalized.is_empty() {
+                    normalized.prepend(prepend);
+                }
+            }
+            Self::Sequence(normalizers) => {
+                for normalizer in normalizers {
+                    normalizer.normalize(normalized);
+                }
+            }
+        }
+    }
+}
+
+fn is_bert_whitespace(c: char) -> bool {
+    matches!(c, '\t' | '\n' | '\r') || c.is_whitespace()
+}
+
+fn is_control(c: char) -> bool {
+    !matches!(c, '\t' | '\n' | '\r') && c.is_other()
+}
+
+// The CJK Unified Ideographs blocks, as in BERT's original tokenizer
+fn is_chinese_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x4E00..=0x9FFF
+            | 0x3400..=0x4DBF
+            | 0x20000..=0x2A6DF
+            | 0x2A700..=0x2B73F
+            | 0x2B740..=0x2B81F
+            | 0x2B920..=0x2CEAF
+            | 0xF900..=0xFAFF
+            | 0x2F800..=0x2FA1F
+    )
+}
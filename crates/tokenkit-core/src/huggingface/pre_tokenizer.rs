@@ -0,0 +1,189 @@
+use super::byte_level;
+use super::loader::{Loader, Object};
+use super::normalized::{Invert, PreTokenizedString, SplitBehavior};
+use fancy_regex::Regex;
+use unicode_categories::UnicodeCategories;
+
+// GPT-2's split into contractions, letters, numbers, other symbols and spaces
+const BYTE_LEVEL_PATTERN: &str = r"'s|'t|'re|'ve|'m|'ll|'d| ?\p{L}+| ?\p{N}+| ?[^\s\p{L}\p{N}]+|\s+(?!\S)|\s+";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrependScheme {
+    First,
+    Never,
+    Always,
+}
+
+pub(crate) enum PreTokenizer {
+    Bert,
+    Whitespace(Regex),
+    WhitespaceSplit,
+    ByteLevel {
+        add_prefix_space: bool,
+        regex: Option<Regex>,
+    },
+    Metaspace {
+        replacement: char,
+        prepend_scheme: PrependScheme,
+        split: bool,
+    },
+    Punctuation(SplitBehavior),
+    Digits {
+        individual_digits: bool,
+    },
+    Split {
+        pattern: Regex,
+        behavior: SplitBehavior,
+        invert: bool,
+    },
+    CharDelimiterSplit(char),
+    Sequence(Vec<PreTokenizer>),
+}
+
+impl PreTokenizer {
+    pub(crate) fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let pre_tokenizer = match loader.kind(path, definition)? {
+            "BertPreTokenizer" => Self::Bert,
+            "Whitespace" => Self::Whitespace(loader.regex(path, r"\w+|[^\w\s]+")?),
+            "WhitespaceSplit" => Self::WhitespaceSplit,
+            "ByteLevel" => {
+                let add_prefix_space = loader.bool(path, definition, "add_prefix_space", true)?;
+                let regex = if loader.bool(path, definition, "use_regex", true)? {
+                    Some(loader.regex(path, BYTE_LEVEL_PATTERN)?)
+                } else {
+                    None
+                };
+                Self::ByteLevel { add_prefix_space, regex }
+            }
+            "Metaspace" => {
+                let (replacement, prepend_scheme) = load_metaspace(loader, path, definition)?;
+                Self::Metaspace {
+                    replacement,
+                    prepend_scheme,
+                    split: loader.bool(path, definition, "split", true)?,
+                }
+            }
+            "Punctuation" => Self::Punctuation(loader.behavior(path, definition, SplitBehavior::Isolated)?),
+            "Digits" => Self::Digits {
+                individual_digits: loader.bool(path, definition, "individual_digits", false)?,
+            },
+            "Split" => Self::Split {
+                pattern: loader.pattern(path, definition)?,
+                behavior: loader.behavior(path, definition, SplitBehavior::Removed)?,
+                invert: loader.bool(path, definition, "invert", false)?,
+            },
+            "CharDelimiterSplit" => Self::CharDelimiterSplit(loader.char(path, definition, "delimiter")?),
+            "Sequence" => Self::Sequence(loader.list(path, definition, "pretokenizers", Self::load)?),
+            other => return loader.unsupported(path, "pre-tokenizer", other),
+        };
+        Some(pre_tokenizer)
+    }
+
+    pub(crate) fn pre_tokenize(&self, pretokenized: &mut PreTokenizedString) {
+        match self {
+            Self::Bert => {
+                pretokenized.split(|s| s.split(&char::is_whitespace, SplitBehavior::Removed));
+                pretokenized.split(|s| s.split(&is_punctuation, SplitBehavior::Isolated));
+            }
+            Self::Whitespace(regex) => pretokenized.split(|s| s.split(&Invert(regex), SplitBehavior::Removed)),
+            Self::WhitespaceSplit => pretokenized.split(|s| s.split(&char::is_whitespace, SplitBehavior::Removed)),
+            Self::ByteLevel { add_prefix_space, regex } => {
+                pretokenized.split(|mut s| {
+                    if *add_prefix_space && !s.get().starts_with(' ') {
+                        s.prepend(" ");
+                    }
+                    match regex {
+                        Some(regex) => s.split(regex, SplitBehavior::Isolated),
+                        None => vec![s],
+                    }
+                });
+                pretokenized.normalize(|s| {
+                    s.flat_map(|c, dest| {
+                        let mut bytes = [0; 4];
+                        for (i, byte) in c.encode_utf8(&mut bytes).bytes().enumerate() {
+                            dest.push((byte_level::byte_char(byte), isize::from(i > 0)));
+                        }
+                    })
+                });
+            }
+            Self::Metaspace {
+                replacement,
+                prepend_scheme,
+                split,
+            } => {
+                let mut str_rep = [0; 4];
+                let str_rep = &*replacement.encode_utf8(&mut str_rep);
+                pretokenized.split(|mut s| {
+                    s.replace(&' ', str_rep);
+                    let prepend = match prepend_scheme {
+                        PrependScheme::Always => true,
+                        PrependScheme::First => s.offsets_original().0 == 0,
+                        PrependScheme::Never => false,
+                    };
+                    if prepend && !s.get().starts_with(*replacement) {
+                        s.prepend(str_rep);
+                    }
+                    if *split {
+                        s.split(replacement, SplitBehavior::MergedWithNext)
+                    } else {
+                        vec![s]
+                    }
+                });
+            }
+            Self::Punctuation(behavior) => pretokenized.split(|s| s.split(&is_punctuation, *behavior)),
+            Self::Digits { individual_digits } => {
+                let behavior = if *individual_digits {
+                    SplitBehavior::Isolated
+                } else {
+                    SplitBehavior::Contiguous
+                };
+                pretokenized.split(|s| s.split(&char::is_numeric, behavior));
+            }
+            Self::Split {
+                pattern,
+                behavior,
+                invert,
+            } => {
+                if *invert {
+                    pretokenized.split(|s| s.split(&Invert(pattern), *behavior));
+                } else {
+                    pretokenized.split(|s| s.split(pattern, *behavior));
+                }
+            }
+            Self::CharDelimiterSplit(delimiter) => pretokenized.split(|s| s.split(delimiter, SplitBehavior::Removed)),
+            Self::Sequence(pre_tokenizers) => {
+                for pre_tokenizer in pre_tokenizers {
+                    pre_tokenizer.pre_tokenize(pretokenized);
+                }
+            }
+        }
+    }
+}
+
+/// The replacement character and prepend scheme shared by the Metaspace
+/// pre-tokenizer and decoder. Older files use `add_prefix_space` instead of
+/// `prepend_scheme`.
+pub(crate) fn load_metaspace(loader: &mut Loader, path: &str, definition: &Object) -> Option<(char, PrependScheme)> {
+    let replacement = loader.char(path, definition, "replacement")?;
+    let mut prepend_scheme = match loader.optional_string(path, definition, "prepend_scheme")?.as_deref() {
+        None | Some("always") => PrependScheme::Always,
+        Some("first") => PrependScheme::First,
+        Some("never") => PrependScheme::Never,
+        Some(other) => {
+            loader.problem(&format!("{}.prepend_scheme", path), format!("unknown prepend scheme '{}'", other));
+            return None;
+        }
+    };
+    if !loader.bool(path, definition, "add_prefix_space", true)? {
+        if prepend_scheme != PrependScheme::Never {
+            loader.problem(path, "add_prefix_space does not match prepend_scheme");
+            return None;
+        }
+        prepend_scheme = PrependScheme::Never;
+    }
+    Some((replacement, prepend_scheme))
+}
+
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation() || c.is_punctuation()
+}
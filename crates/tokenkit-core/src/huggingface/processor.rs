@@ -0,0 +1,193 @@
+//! Post-processors: the special tokens wrapped around a single sequence,
+//! and the offset trimming byte-level models need.
+
+use super::byte_level;
+use super::loader::{join, Loader, Object};
+use super::Encoding;
+use serde_json::Value;
+
+// A special token to emit, or the encoded sequence itself
+pub(crate) enum Piece {
+    Special { ids: Vec<u32>, tokens: Vec<String> },
+    Sequence,
+}
+
+pub(crate) enum PostProcessor {
+    Template(Vec<Piece>),
+    Roberta {
+        cls: (String, u32),
+        sep: (String, u32),
+        trim_offsets: bool,
+        add_prefix_space: bool,
+    },
+    Bert {
+        cls: (String, u32),
+        sep: (String, u32),
+    },
+    ByteLevel {
+        trim_offsets: bool,
+        add_prefix_space: bool,
+    },
+    Sequence(Vec<PostProcessor>),
+}
+
+impl PostProcessor {
+    pub(crate) fn load(loader: &mut Loader, path: &str, definition: &Object) -> Option<Self> {
+        let processor = match loader.kind(path, definition)? {
+            "TemplateProcessing" => Self::Template(load_template(loader, path, definition)?),
+            "RobertaProcessing" => Self::Roberta {
+                cls: load_special(loader, path, definition, "cls")?,
+                sep: load_special(loader, path, definition, "sep")?,
+                trim_offsets: loader.bool(path, definition, "trim_offsets", true)?,
+                add_prefix_space: loader.bool(path, definition, "add_prefix_space", true)?,
+            },
+            "BertProcessing" => Self::Bert {
+                cls: load_special(loader, path, definition, "cls")?,
+                sep: load_special(loader, path, definition, "sep")?,
+            },
+            "ByteLevel" => Self::ByteLevel {
+                trim_offsets: loader.bool(path, definition, "trim_offsets", true)?,
+                add_prefix_space: loader.bool(path, definition, "add_prefix_space", true)?,
+            },
+            "Sequence" => Self::Sequence(loader.list(path, definition, "processors", Self::load)?),
+            other => return loader.unsupported(path, "post-processor", other),
+        };
+        Some(processor)
+    }
+
+    pub(crate) fn process(&self, mut encoding: Encoding, add_special_tokens: bool) -> Encoding {
+        match self {
+            Self::Template(pieces) => {
+                if !add_special_tokens {
+                    return encoding;
+                }
+                let mut processed = Encoding::default();
+                for piece in pieces {
+                    match piece {
+                        Piece::Special { ids, tokens } => {
+                            processed.ids.extend(ids);
+                            processed.tokens.extend(tokens.iter().cloned());
+                            processed.offsets.extend(ids.iter().map(|_| (0, 0)));
+                        }
+                        Piece::Sequence => processed.extend(&encoding),
+                    }
+                }
+                processed
+            }
+            Self::Roberta {
+                cls,
+                sep,
+                trim_offsets,
+                add_prefix_space,
+            } => {
+                if *trim_offsets {
+                    byte_level::trim_offsets(&encoding.tokens, &mut encoding.offsets, *add_prefix_space);
+                }
+                if add_special_tokens {
+                    wrap(encoding, cls, sep)
+                } else {
+                    encoding
+                }
+            }
+            Self::Bert { cls, sep } => {
+                if add_special_tokens {
+                    wrap(encoding, cls, sep)
+                } else {
+                    encoding
+                }
+            }
+            Self::ByteLevel {
+                trim_offsets,
+                add_prefix_space,
+            } => {
+                if *trim_offsets {
+                    byte_level::trim_offsets(&encoding.tokens, &mut encoding.offsets, *add_prefix_space);
+                }
+                encoding
+            }
+            Self::Sequence(processors) => processors
+                .iter()
+                .fold(encoding, |encoding, processor| processor.process(encoding, add_special_tokens)),
+        }
+    }
+}
+
+fn wrap(encoding: Encoding, cls: &(String, u32), sep: &(String, u32)) -> Encoding {
+    let mut wrapped = Encoding {
+        ids: vec![cls.1],
+        tokens: vec![cls.0.clone()],
+        offsets: vec![(0, 0)],
+    };
+    wrapped.extend(&encoding);
+    wrapped.ids.push(sep.1);
+    wrapped.tokens.push(sep.0.clone());
+    wrapped.offsets.push((0, 0));
+    wrapped
+}
+
+// `cls` and `sep` are written as [token, id]
+fn load_special(loader: &mut Loader, path: &str, definition: &Object, key: &str) -> Option<(String, u32)> {
+    match loader.field(path, definition, key)?.as_array().map(Vec::as_slice) {
+        Some([Value::String(token), id]) => Some((token.clone(), loader.integer(&join(path, key), id)?)),
+        _ => {
+            loader.problem(&join(path, key), "expected a [token, id] pair");
+            None
+        }
+    }
+}
+
+// Only the `single` template is used: tokenkit encodes one sequence at a time
+fn load_template(loader: &mut Loader, path: &str, definition: &Object) -> Option<Vec<Piece>> {
+    let specials = match loader.field(path, definition, "special_tokens")? {
+        Value::Object(specials) => specials,
+        _ => {
+            loader.problem(&join(path, "special_tokens"), "expected an object");
+            return None;
+        }
+    };
+
+    let specials_path = join(path, "special_tokens");
+    loader.list(path, definition, "single", |loader, path, piece| {
+        if let Some(Value::Object(sequence)) = piece.get("Sequence") {
+            if sequence.get("id").and_then(Value::as_str) != Some("A") {
+                loader.problem(path, "only the first sequence ('A') can be used in a single template");
+                return None;
+            }
+            return Some(Piece::Sequence);
+        }
+
+        let Some(name) = piece.get("SpecialToken").and_then(|special| special.get("id")).and_then(Value::as_str) else {
+            loader.problem(path, "expected a SpecialToken or Sequence");
+            return None;
+        };
+        let Some(Value::Object(special)) = specials.get(name) else {
+            loader.problem(&format!("{}.SpecialToken.id", path), format!("'{}' is not in special_tokens", name));
+            return None;
+        };
+        let special_path = join(&specials_path, name);
+        let ids = match special.get("ids") {
+            Some(Value::Array(ids)) => ids
+                .iter()
+                .map(|id| loader.integer(&join(&special_path, "ids"), id))
+                .collect::<Option<Vec<u32>>>()?,
+            _ => {
+                loader.problem(&join(&special_path, "ids"), "expected a list of ids");
+                return None;
+            }
+        };
+        let tokens = match special.get("tokens") {
+            Some(Value::Array(tokens)) => tokens
+                .iter()
+                .map(|token| token.as_str().map(str::to_string))
+                .collect::<Option<Vec<String>>>(),
+            _ => None,
+        };
+        match tokens {
+            Some(tokens) if tokens.len() == ids.len() => Some(Piece::Special { ids, tokens }),
+            _ => {
+                loader.problem(&join(&special_path, "tokens"), "expected one token per id");
+                None
+            }
+        }
+    })
+}
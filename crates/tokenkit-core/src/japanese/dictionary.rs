@@ -0,0 +1,431 @@
+use crate::error::{Result, TokenizerError};
+use crate::tokenizer::TokenAttributes;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A word the lattice can use: its context ids, its cost and the feature
+/// columns describing it.
+pub(crate) struct Entry {
+    pub left_id: u16,
+    pub right_id: u16,
+    pub cost: i32,
+    // Separated by FEATURE_SEPARATOR, which no CSV field contains
+    features: Box<str>,
+}
+
+const FEATURE_SEPARATOR: char = '\u{1f}';
+
+impl Entry {
+    /// IPADIC features: four part-of-speech levels, conjugation type and
+    /// form, base form, reading and pronunciation. `*` means none.
+    pub fn attributes(&self) -> TokenAttributes {
+        let features: Vec<&str> = self.features.split(FEATURE_SEPARATOR).collect();
+        let given = |i: usize| features.get(i).filter(|f| !f.is_empty() && **f != "*").map(|f| f.to_string());
+
+        TokenAttributes {
+            part_of_speech: (0..4).filter_map(given).collect::<Vec<_>>().join("-"),
+            base_form: given(6),
+            reading: given(7),
+        }
+    }
+}
+
+/// One `char.def` category.
+pub(crate) struct Category {
+    name: String,
+    /// Try unknown words here even where dictionary words start.
+    pub invoke: bool,
+    /// Offer the whole run of characters in this category as one word.
+    pub group: bool,
+    /// Also offer each of the first `length` characters of the run.
+    pub length: usize,
+}
+
+/// A system dictionary in MeCab's source format, as read by [`Dictionary::load`].
+pub struct Dictionary {
+    entries: Vec<Entry>,
+    words: HashMap<Box<str>, Vec<u32>>,
+    longest_word: usize,
+    right_ids: usize,
+    left_ids: usize,
+    costs: Vec<i16>,
+    categories: Vec<Category>,
+    // Sorted, non-overlapping code point ranges and the categories of their
+    // characters, the first being the main one
+    ranges: Vec<(u32, u32, Vec<usize>)>,
+    default_category: usize,
+    unknown: Vec<Vec<Entry>>,
+}
+
+impl Dictionary {
+    /// Reads a dictionary directory: `matrix.def`, `char.def`, `unk.def` and
+    /// every `*.csv` lexicon file in it, all UTF-8. This is the layout of
+    /// MeCab's IPADIC source distribution (converted to UTF-8, as in
+    /// `mecab-ipadic-utf8`); see `docs/JAPANESE.md` for each file's format.
+    pub fn load(directory: impl AsRef<Path>) -> Result<Self> {
+        let directory = directory.as_ref();
+
+        let (right_ids, left_ids, costs) = load_matrix(&directory.join("matrix.def"))?;
+        let (categories, ranges) = load_char_def(&directory.join("char.def"))?;
+        let default_category = categories
+            .iter()
+            .position(|category| category.name == "DEFAULT")
+            .ok_or_else(|| invalid(&directory.join("char.def"), "no DEFAULT category"))?;
+
+        let mut dictionary = Self {
+            entries: Vec::new(),
+            words: HashMap::new(),
+            longest_word: 0,
+            right_ids,
+            left_ids,
+            costs,
+            unknown: categories.iter().map(|_| Vec::new()).collect(),
+            categories,
+            ranges,
+            default_category,
+        };
+
+        let mut lexicons: Vec<_> = std::fs::read_dir(directory)
+            .map_err(|e| invalid(directory, e))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "csv"))
+            .collect();
+        if lexicons.is_empty() {
+            return Err(invalid(directory, "no *.csv lexicon files"));
+        }
+        lexicons.sort();
+        for path in &lexicons {
+            dictionary.load_lexicon(path)?;
+        }
+        dictionary.load_unknown(&directory.join("unk.def"))?;
+
+        Ok(dictionary)
+    }
+
+    fn load_lexicon(&mut self, path: &Path) -> Result<()> {
+        for (number, line) in lines(path)? {
+            let (surface, entry) = self.parse_entry(path, number, &line)?;
+            let id = self.entries.len() as u32;
+            self.entries.push(entry);
+            self.longest_word = self.longest_word.max(surface.chars().count());
+            self.words.entry(surface.into()).or_default().push(id);
+        }
+        Ok(())
+    }
+
+    fn load_unknown(&mut self, path: &Path) -> Result<()> {
+        for (number, line) in lines(path)? {
+            let (name, entry) = self.parse_entry(path, number, &line)?;
+            let category = self
+                .categories
+                .iter()
+                .position(|category| category.name == name)
+                .ok_or_else(|| invalid(path, format!("line {}: unknown category '{}'", number, name)))?;
+            self.unknown[category].push(entry);
+        }
+
+        // Every character must be able to start a word, or text using it
+        // couldn't be segmented at all
+        match self.unknown.iter().position(Vec::is_empty) {
+            Some(category) => Err(invalid(
+                path,
+                format!("no entry for category '{}'", self.categories[category].name),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // `surface,left_id,right_id,cost,features...`
+    fn parse_entry(&self, path: &Path, number: usize, line: &str) -> Result<(String, Entry)> {
+        let malformed = |what: &str| invalid(path, format!("line {}: {}", number, what));
+
+        let mut fields = split_csv(line);
+        if fields.len() < 4 {
+            return Err(malformed("expected surface,left_id,right_id,cost,features..."));
+        }
+        let features = fields.split_off(4);
+        let id = |field: &str, size: usize| match field.parse::<u16>() {
+            Ok(id) if usize::from(id) < size => Ok(id),
+            _ => Err(malformed(&format!("context id '{}' is not below {}", field, size))),
+        };
+
+        let entry = Entry {
+            left_id: id(&fields[1], self.left_ids)?,
+            right_id: id(&fields[2], self.right_ids)?,
+            cost: fields[3]
+                .parse()
+                .map_err(|_| malformed(&format!("cost '{}' is not a whole number", fields[3])))?,
+            features: features.join(&FEATURE_SEPARATOR.to_string()).into(),
+        };
+        Ok((fields.swap_remove(0), entry))
+    }
+
+    /// Cost of `right_id` (the end of one word) followed by `left_id` (the
+    /// start of the next). Id 0 is the start and end of the text.
+    pub(crate) fn connection_cost(&self, right_id: u16, left_id: u16) -> i32 {
+        i32::from(self.costs[usize::from(right_id) * self.left_ids + usize::from(left_id)])
+    }
+
+    pub(crate) fn entry(&self, id: u32) -> &Entry {
+        &self.entries[id as usize]
+    }
+
+    /// Dictionary words that `text` starts with, as (byte length, entry id).
+    pub(crate) fn prefixes<'a>(&'a self, text: &'a str) -> impl Iterator<Item = (usize, u32)> + 'a {
+        text.char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .take(self.longest_word)
+            .filter_map(move |end| self.words.get(&text[..end]).map(|ids| (end, ids)))
+            .flat_map(|(end, ids)| ids.iter().map(move |&id| (end, id)))
+    }
+
+    pub(crate) fn category(&self, c: char) -> &Category {
+        &self.categories[self.categories_of(c)[0]]
+    }
+
+    pub(crate) fn unknown_entries(&self, c: char) -> &[Entry] {
+        &self.unknown[self.categories_of(c)[0]]
+    }
+
+    /// Whether `c` belongs to the main category of `first`, so both can be
+    /// part of one unknown word.
+    pub(crate) fn same_category(&self, first: char, c: char) -> bool {
+        self.categories_of(c).contains(&self.categories_of(first)[0])
+    }
+
+    fn categories_of(&self, c: char) -> &[usize] {
+        let c = u32::from(c);
+        let i = self.ranges.partition_point(|&(_, last, _)| last < c);
+        match self.ranges.get(i) {
+            Some((first, _, categories)) if *first <= c => categories,
+            _ => std::slice::from_ref(&self.default_category),
+        }
+    }
+}
+
+/// One segment of a user dictionary word, at a byte range of the word.
+pub(crate) struct Segment {
+    pub start: usize,
+    pub end: usize,
+    pub attributes: TokenAttributes,
+}
+
+/// Words that are always segmented as given, read from a CSV file with lines
+/// `surface,segments,readings,part_of_speech`. Segments and readings are
+/// separated by spaces, e.g.
+/// `関西国際空港,関西 国際 空港,カンサイ コクサイ クウコウ,カスタム名詞`;
+/// lines starting with `#` are comments.
+pub(crate) struct UserDictionary {
+    words: HashMap<Box<str>, Vec<Segment>>,
+    longest_word: usize,
+}
+
+impl UserDictionary {
+    pub fn new() -> Self {
+        Self {
+            words: HashMap::new(),
+            longest_word: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn load(&mut self, path: &Path) -> Result<()> {
+        for (number, line) in lines(path)?.filter(|(_, line)| !line.starts_with('#')) {
+            let malformed = |what: &str| invalid(path, format!("line {}: {}", number, what));
+
+            let fields = split_csv(&line);
+            let [surface, segments, readings, part_of_speech] = &fields[..] else {
+                return Err(malformed("expected surface,segments,readings,part_of_speech"));
+            };
+            let segments: Vec<&str> = segments.split_whitespace().collect();
+            let readings: Vec<&str> = readings.split_whitespace().collect();
+            if segments.concat() != *surface {
+                return Err(malformed("segments don't add up to the surface"));
+            }
+            if segments.len() != readings.len() {
+                return Err(malformed("expected one reading per segment"));
+            }
+
+            let mut start = 0;
+            let mut words = Vec::new();
+            for (segment, reading) in segments.iter().zip(readings) {
+                let attributes = TokenAttributes {
+                    part_of_speech: part_of_speech.clone(),
+                    base_form: Some(segment.to_string()),
+                    reading: Some(reading.to_string()),
+                };
+                words.push(Segment {
+                    start,
+                    end: start + segment.len(),
+                    attributes,
+                });
+                start += segment.len();
+            }
+
+            self.longest_word = self.longest_word.max(surface.chars().count());
+            self.words.insert(surface.as_str().into(), words);
+        }
+        Ok(())
+    }
+
+    /// The longest user word `text` starts with, as its byte length and
+    /// segments.
+    pub fn longest_prefix(&self, text: &str) -> Option<(usize, &[Segment])> {
+        text.char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .take(self.longest_word)
+            .filter_map(|end| self.words.get(&text[..end]).map(|words| (end, words.as_slice())))
+            .last()
+    }
+}
+
+fn load_matrix(path: &Path) -> Result<(usize, usize, Vec<i16>)> {
+    let mut lines = lines(path)?;
+    let (right_ids, left_ids) = match lines.next() {
+        Some((_, header)) => match numbers::<usize>(&header)[..] {
+            [Some(right_ids), Some(left_ids)] => (right_ids, left_ids),
+            _ => return Err(invalid(path, "line 1: expected '<right ids> <left ids>'")),
+        },
+        None => return Err(invalid(path, "empty file")),
+    };
+
+    let mut costs = vec![0; right_ids * left_ids];
+    for (number, line) in lines {
+        match numbers::<i64>(&line)[..] {
+            [Some(right), Some(left), Some(cost)]
+                if (0..right_ids as i64).contains(&right) && (0..left_ids as i64).contains(&left) =>
+            {
+                costs[right as usize * left_ids + left as usize] =
+                    i16::try_from(cost).map_err(|_| invalid(path, format!("line {}: cost {} is out of range", number, cost)))?;
+            }
+            _ => return Err(invalid(path, format!("line {}: expected '<right id> <left id> <cost>'", number))),
+        }
+    }
+
+    Ok((right_ids, left_ids, costs))
+}
+
+type CharRanges = Vec<(u32, u32, Vec<usize>)>;
+
+// Category lines `NAME INVOKE GROUP LENGTH`, then code point lines
+// `0x3041..0x3096 HIRAGANA` or `0x3005 KANJI NUMERIC`
+fn load_char_def(path: &Path) -> Result<(Vec<Category>, CharRanges)> {
+    let mut categories: Vec<Category> = Vec::new();
+    let mut ranges = Vec::new();
+
+    for (number, line) in lines(path)? {
+        let malformed = |what: &str| invalid(path, format!("line {}: {}", number, what));
+        let fields: Vec<&str> = line.split('#').next().unwrap_or("").split_whitespace().collect();
+        if fields.is_empty() {
+            continue;
+        }
+
+        if fields[0].starts_with("0x") {
+            let (first, last) = fields[0].split_once("..").unwrap_or((fields[0], fields[0]));
+            let code_point = |hex: &str| {
+                u32::from_str_radix(hex.trim_start_matches("0x"), 16)
+                    .map_err(|_| malformed(&format!("'{}' is not a hex code point", hex)))
+            };
+            let (first, last) = (code_point(first)?, code_point(last)?);
+
+            let names = &fields[1..];
+            if names.is_empty() {
+                return Err(malformed("code points without a category"));
+            }
+            let ids = names
+                .iter()
+                .map(|name| {
+                    categories
+                        .iter()
+                        .position(|category| category.name == *name)
+                        .ok_or_else(|| malformed(&format!("unknown category '{}'", name)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            ranges.push((first, last, ids));
+        } else {
+            let [name, invoke, group, length] = fields[..] else {
+                return Err(malformed("expected 'NAME INVOKE GROUP LENGTH'"));
+            };
+            let (Ok(invoke), Ok(group), Ok(length)) = (invoke.parse::<u8>(), group.parse::<u8>(), length.parse()) else {
+                return Err(malformed("INVOKE and GROUP must be 0 or 1 and LENGTH a whole number"));
+            };
+            categories.push(Category {
+                name: name.to_string(),
+                invoke: invoke == 1,
+                group: group == 1,
+                length,
+            });
+        }
+    }
+
+    // Later lines win where ranges overlap, as in MeCab
+    let mut sorted: CharRanges = Vec::new();
+    for (first, last, ids) in ranges {
+        let mut kept = Vec::new();
+        for (a, b, other) in sorted.drain(..) {
+            if b < first || a > last {
+                kept.push((a, b, other));
+                continue;
+            }
+            if a < first {
+                kept.push((a, first - 1, other.clone()));
+            }
+            if b > last {
+                kept.push((last + 1, b, other));
+            }
+        }
+        kept.push((first, last, ids));
+        kept.sort_by_key(|&(a, _, _)| a);
+        sorted = kept;
+    }
+
+    Ok((categories, sorted))
+}
+
+// Non-blank lines with their 1-based numbers
+fn lines(path: &Path) -> Result<impl Iterator<Item = (usize, String)>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| invalid(path, e))?;
+    let lines: Vec<_> = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| (i + 1, line.trim_end_matches('\r').to_string()))
+        .collect();
+    Ok(lines.into_iter())
+}
+
+fn numbers<T: std::str::FromStr>(line: &str) -> Vec<Option<T>> {
+    line.split_whitespace().map(|field| field.parse().ok()).collect()
+}
+
+/// Fields of a CSV line; a field in double quotes may contain commas, and
+/// `""` inside it stands for one quote.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().expect("at least one field");
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted || field.is_empty() => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => field.push(c),
+        }
+    }
+    fields
+}
+
+fn invalid(path: &Path, error: impl std::fmt::Display) -> TokenizerError {
+    TokenizerError::InvalidDictionary {
+        path: path.display().to_string(),
+        message: error.to_string(),
+    }
+}
@@ -0,0 +1,113 @@
+use super::dictionary::{Dictionary, Entry};
+
+/// A candidate word in the lattice, from `start` to `end` (byte offsets).
+struct Node<'d> {
+    start: usize,
+    end: usize,
+    entry: &'d Entry,
+    // Cheapest total cost of a path from the start of the text through this
+    // node, and the node before it on that path
+    best_cost: i32,
+    previous: Option<usize>,
+}
+
+/// Cuts `text` (without whitespace) into the words of the cheapest path
+/// through the lattice of dictionary and unknown words: each word costs its
+/// own cost plus the connection cost from the word before it.
+pub(crate) fn segment<'d>(dictionary: &'d Dictionary, text: &str) -> Vec<(usize, usize, &'d Entry)> {
+    if text.is_empty() {
+        return Vec::new();
+    }
+
+    let mut nodes: Vec<Node<'d>> = Vec::new();
+    // Nodes ending at each byte offset
+    let mut ends_at: Vec<Vec<usize>> = vec![Vec::new(); text.len() + 1];
+
+    for (start, c) in text.char_indices() {
+        // Unreachable offsets, inside a word that started earlier, are left out
+        if start > 0 && ends_at[start].is_empty() {
+            continue;
+        }
+
+        for (end, entry) in candidates(dictionary, &text[start..], c) {
+            let (best_cost, previous) = best_previous(dictionary, &nodes, &ends_at[start], entry);
+            ends_at[start + end].push(nodes.len());
+            nodes.push(Node {
+                start,
+                end: start + end,
+                entry,
+                best_cost: best_cost.saturating_add(entry.cost),
+                previous,
+            });
+        }
+    }
+
+    // The end of the text connects like a word with context id 0
+    let mut last = ends_at[text.len()]
+        .iter()
+        .copied()
+        .min_by_key(|&i| nodes[i].best_cost.saturating_add(dictionary.connection_cost(nodes[i].entry.right_id, 0)));
+
+    let mut words = Vec::new();
+    while let Some(i) = last {
+        words.push((nodes[i].start, nodes[i].end, nodes[i].entry));
+        last = nodes[i].previous;
+    }
+    words.reverse();
+    words
+}
+
+// The cheapest way to reach a word with `entry`, from the start of the text
+// or one of the nodes ending where it starts
+fn best_previous(dictionary: &Dictionary, nodes: &[Node], previous: &[usize], entry: &Entry) -> (i32, Option<usize>) {
+    if previous.is_empty() {
+        return (dictionary.connection_cost(0, entry.left_id), None);
+    }
+
+    previous
+        .iter()
+        .map(|&i| {
+            let cost = nodes[i].best_cost.saturating_add(dictionary.connection_cost(nodes[i].entry.right_id, entry.left_id));
+            (cost, Some(i))
+        })
+        .min_by_key(|&(cost, _)| cost)
+        .expect("at least one previous node")
+}
+
+// Words starting at the beginning of `text`, as (byte length, entry).
+// Unknown words are added as MeCab does: where no dictionary word starts, or
+// always when the first character's category says to invoke them
+fn candidates<'d>(dictionary: &'d Dictionary, text: &str, first: char) -> Vec<(usize, &'d Entry)> {
+    let mut found: Vec<(usize, &'d Entry)> = dictionary
+        .prefixes(text)
+        .map(|(end, id)| (end, dictionary.entry(id)))
+        .collect();
+
+    let category = dictionary.category(first);
+    if !found.is_empty() && !category.invoke {
+        return found;
+    }
+
+    // Byte length after each character of the leading run in `first`'s category
+    let run: Vec<usize> = text
+        .char_indices()
+        .take_while(|&(_, c)| dictionary.same_category(first, c))
+        .map(|(i, c)| i + c.len_utf8())
+        .collect();
+
+    let mut lengths: Vec<usize> = run.iter().copied().take(category.length).collect();
+    if category.group {
+        lengths.extend(run.last());
+    }
+    if lengths.is_empty() {
+        lengths.push(first.len_utf8());
+    }
+    lengths.sort_unstable();
+    lengths.dedup();
+
+    let entries = dictionary.unknown_entries(first);
+    for end in lengths {
+        found.extend(entries.iter().map(|entry| (end, entry)));
+    }
+    found
+}
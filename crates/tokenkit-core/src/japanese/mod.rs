@@ -0,0 +1,134 @@
+//! Japanese morphological analysis.
+//!
+//! [`JapaneseTokenizer`] segments text the way MeCab and Kuromoji do: every
+//! dictionary word (and guessed unknown word) starting at each character
+//! becomes a node of a lattice, and the text is cut along the path with the
+//! lowest total of word costs and connection costs between neighbouring
+//! words. Each token carries its part of speech, base form and reading in
+//! [`Token::attributes`]:
+//!
+//! ```no_run
+//! use tokenkit_core::japanese::JapaneseTokenizer;
+//! use tokenkit_core::{Tokenizer, TokenizerConfig};
+//!
+//! let tokenizer = JapaneseTokenizer::new(TokenizerConfig::default(), "mecab-ipadic-utf8", &[]).unwrap();
+//! let tokens = tokenizer.tokenize_with_offsets("東京で食べた");
+//! let attributes = tokens[2].attributes.as_ref().unwrap();
+//! assert_eq!(attributes.base_form.as_deref(), Some("食べる"));
+//! ```
+//!
+//! The dictionary is a directory in MeCab's source format, such as IPADIC
+//! converted to UTF-8; see [`Dictionary::load`] and `docs/JAPANESE.md`.
+//! Whitespace always separates words, and tokens without letters or digits
+//! (punctuation and symbols) are left out.
+
+mod dictionary;
+mod lattice;
+
+pub use dictionary::Dictionary;
+
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use crate::tokenizer::{
+    apply_preserve_patterns_with_tokenizer, offset_in, post_process, sentence_boundary, BaseTokenizerFields, Token,
+    Tokenizer,
+};
+use dictionary::UserDictionary;
+use std::path::Path;
+use std::sync::Arc;
+
+pub struct JapaneseTokenizer {
+    base: BaseTokenizerFields,
+    dictionary: Arc<Dictionary>,
+    user_dictionary: UserDictionary,
+}
+
+impl JapaneseTokenizer {
+    /// Loads the dictionary in `dictionary` and the CSV `user_dictionaries`,
+    /// whose words are always segmented as they say; see `docs/JAPANESE.md`.
+    pub fn new(config: TokenizerConfig, dictionary: impl AsRef<Path>, user_dictionaries: &[String]) -> Result<Self> {
+        Self::with_dictionary(config, Arc::new(Dictionary::load(dictionary)?), user_dictionaries)
+    }
+
+    /// Like `new`, sharing a dictionary that is already loaded.
+    pub fn with_dictionary(
+        config: TokenizerConfig,
+        dictionary: Arc<Dictionary>,
+        user_dictionaries: &[String],
+    ) -> Result<Self> {
+        let mut user_dictionary = UserDictionary::new();
+        for path in user_dictionaries {
+            user_dictionary.load(Path::new(path))?;
+        }
+
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+            dictionary,
+            user_dictionary,
+        })
+    }
+
+    // Words of `text` with their attributes, before any filters
+    fn segment<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let mut tokens = Vec::new();
+        for run in text.split(char::is_whitespace).filter(|run| !run.is_empty()) {
+            self.segment_run(run, offset_in(text, run), &mut tokens);
+        }
+        tokens.retain(|token| token.text.chars().any(char::is_alphanumeric));
+        tokens
+    }
+
+    // User dictionary words win wherever they start, longest first; the text
+    // between them goes through the lattice
+    fn segment_run<'a>(&self, run: &'a str, offset: usize, tokens: &mut Vec<Token<'a>>) {
+        let mut pending = 0;
+        let mut i = 0;
+
+        while i < run.len() && !self.user_dictionary.is_empty() {
+            let Some((len, words)) = self.user_dictionary.longest_prefix(&run[i..]) else {
+                i += run[i..].chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+
+            self.segment_unmatched(&run[pending..i], offset + pending, tokens);
+            for segment in words {
+                let (start, end) = (i + segment.start, i + segment.end);
+                let token = Token::new(&run[start..end], offset + start, offset + end);
+                tokens.push(token.with_attributes(segment.attributes.clone()));
+            }
+            i += len;
+            pending = i;
+        }
+
+        self.segment_unmatched(&run[pending..], offset + pending, tokens);
+    }
+
+    fn segment_unmatched<'a>(&self, text: &'a str, offset: usize, tokens: &mut Vec<Token<'a>>) {
+        for (start, end, entry) in lattice::segment(&self.dictionary, text) {
+            let token = Token::new(&text[start..end], offset + start, offset + end);
+            tokens.push(token.with_attributes(entry.attributes()));
+        }
+    }
+}
+
+impl Tokenizer for JapaneseTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens = self.segment(text);
+
+        if self.base.has_preserve_patterns() {
+            return apply_preserve_patterns_with_tokenizer(
+                tokens,
+                self.base.preserve_patterns(),
+                text,
+                &self.base.config,
+                |slice| self.segment(slice),
+            );
+        }
+
+        post_process(tokens, &self.base.config)
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, sentence_boundary(text))
+    }
+}
@@ -0,0 +1,57 @@
+//! Language identification.
+//!
+//! Languages are told apart by their writing system and then by how often
+//! character trigrams occur, compared against the profiles of the 69
+//! languages bundled with `whatlang`. Languages are named by their ISO 639-3
+//! code (`eng`, `deu`, `cmn`) or English name (`english`).
+
+use whatlang::Lang;
+
+/// The language a text is most likely written in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Language {
+    /// ISO 639-3 code, such as `eng`
+    pub code: &'static str,
+    /// English name, such as `English`
+    pub name: &'static str,
+    /// How far ahead of the runner-up this language is, from 0.0 to 1.0
+    pub confidence: f64,
+    /// Whether the confidence is high enough to rely on; short texts and
+    /// closely related languages often fall short
+    pub reliable: bool,
+}
+
+/// The most likely language of `text`, or `None` when it has no letters to
+/// go on.
+///
+/// ```
+/// use tokenkit_core::language::detect_language;
+///
+/// let text = "Die Katze schläft auf dem Sofa, während die Kinder im Garten spielen.";
+/// let language = detect_language(text).unwrap();
+/// assert_eq!(language.code, "deu");
+/// assert!(language.reliable);
+/// ```
+pub fn detect_language(text: &str) -> Option<Language> {
+    let info = whatlang::detect(text)?;
+    Some(Language {
+        code: info.lang().code(),
+        name: info.lang().eng_name(),
+        confidence: info.confidence(),
+        reliable: info.is_reliable(),
+    })
+}
+
+/// The ISO 639-3 code of the language named `name`, by its code or English
+/// name, in any case.
+pub fn parse_language(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    Lang::from_code(name.as_str())
+        .or_else(|| Lang::all().iter().copied().find(|lang| lang.eng_name().to_lowercase() == name))
+        .map(|lang| lang.code())
+}
+
+/// The ISO 639-3 codes of every language [`detect_language`] can tell apart.
+pub fn supported_languages() -> impl Iterator<Item = &'static str> {
+    Lang::all().iter().map(|lang| lang.code())
+}
@@ -0,0 +1,46 @@
+//! Fast, Unicode-aware word-level tokenization with pattern preservation.
+//!
+//! This is the engine behind the `tokenkit` Ruby gem, usable from plain Rust.
+//! Build a [`TokenizerConfig`], turn it into a [`Tokenizer`] with
+//! [`from_config`], and reuse that tokenizer for every input:
+//!
+//! ```
+//! use tokenkit_core::{from_config, TokenizerConfig, TokenizerStrategy};
+//!
+//! let config = TokenizerConfig {
+//!     strategy: TokenizerStrategy::Unicode,
+//!     preserve_patterns: vec![r"\d+mg".to_string()],
+//!     ..TokenizerConfig::default()
+//! };
+//! let tokenizer = from_config(config).unwrap();
+//!
+//! let tokens = tokenizer.tokenize("Patient received 100mg DAILY");
+//! assert_eq!(tokens, ["patient", "received", "100mg", "daily"]);
+//! ```
+
+pub mod case;
+pub mod char_filter;
+pub mod config;
+pub mod config_file;
+pub mod elasticsearch;
+pub mod encoding;
+pub mod error;
+pub mod filter;
+pub mod huggingface;
+pub mod japanese;
+pub mod language;
+pub mod registry;
+pub mod script;
+pub mod tokenizer;
+pub mod validation;
+pub mod word_break;
+
+pub use config::{
+    validate_config, CharFilter, InvalidUtf8Policy, Limits, LongTokenPolicy, OverflowPolicy, RegexLimits, Route,
+    TokenFilter, TokenizerConfig, TokenizerStrategy,
+};
+pub use config_file::{ConfigFormat, CONFIG_VERSION};
+pub use error::{Result, TokenizerError};
+pub use registry::{Analyzer, AnalyzerRegistry};
+pub use tokenizer::{from_config, Token, TokenAttributes, TokenStream, Tokenizer};
+pub use validation::Diagnostic;
@@ -0,0 +1,99 @@
+use crate::config::TokenizerConfig;
+use crate::error::{Result, TokenizerError};
+use crate::tokenizer::{from_config, Tokenizer};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, PoisonError, RwLock};
+
+/// Tokenizers registered under names such as `"title"` or `"sku"`.
+///
+/// Each analyzer is built once, when it is registered, and handed out as a
+/// shared `Arc`, so tokenizing never holds the registry lock. Lookups vastly
+/// outnumber registrations, hence a read-write lock rather than a mutex.
+#[derive(Default)]
+pub struct AnalyzerRegistry {
+    analyzers: RwLock<HashMap<String, Analyzer>>,
+}
+
+/// A registered tokenizer together with the config it was built from, for
+/// settings applied outside the tokenizer such as `invalid_utf8`.
+#[derive(Clone)]
+pub struct Analyzer {
+    pub config: Arc<TokenizerConfig>,
+    pub tokenizer: Arc<dyn Tokenizer>,
+}
+
+impl AnalyzerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a tokenizer for `config` and registers it as `name`, replacing
+    /// any analyzer already registered under that name.
+    pub fn register(&self, name: impl Into<String>, config: TokenizerConfig) -> Result<()> {
+        // Compile outside the lock so slow regexes don't block readers
+        let analyzer = Analyzer {
+            tokenizer: Arc::from(from_config(config.clone())?),
+            config: Arc::new(config),
+        };
+
+        // Map updates are single inserts/removes, so a panic elsewhere can't
+        // leave it half-written; a poisoned lock is safe to keep using
+        self.analyzers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.into(), analyzer);
+        Ok(())
+    }
+
+    /// Removes `name`, returning whether it was registered.
+    pub fn unregister(&self, name: &str) -> bool {
+        self.analyzers
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(name)
+            .is_some()
+    }
+
+    pub fn clear(&self) {
+        self.analyzers.write().unwrap_or_else(PoisonError::into_inner).clear();
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Tokenizer>> {
+        self.analyzer(name).map(|analyzer| analyzer.tokenizer)
+    }
+
+    pub fn analyzer(&self, name: &str) -> Option<Analyzer> {
+        self.analyzers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.analyzers.read().unwrap_or_else(PoisonError::into_inner).contains_key(name)
+    }
+
+    /// Registered names in sorted order.
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .analyzers
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Tokenizes `text` with the analyzer registered as `name`, enforcing its
+    /// limits.
+    pub fn tokenize<'a>(&self, name: &str, text: &'a str) -> Result<Vec<Cow<'a, str>>> {
+        let tokenizer = self
+            .get(name)
+            .ok_or_else(|| TokenizerError::UnknownAnalyzer(name.to_string()))?;
+        tokenizer.try_tokenize(text)
+    }
+}
@@ -0,0 +1,108 @@
+//! Unicode script detection (UAX #24).
+//!
+//! Characters shared between scripts count for each script in their
+//! `Script_Extensions`: the prolonged sound mark `ー` is both Hiragana and
+//! Katakana, while digits and most punctuation (`Common`) and combining marks
+//! (`Inherited`) go with any script.
+
+pub use unicode_script::Script;
+use unicode_script::{ScriptExtension, UnicodeScript};
+
+/// The scripts CJK bigrams are made of.
+pub const CJK_SCRIPTS: [Script; 4] = [Script::Han, Script::Hiragana, Script::Katakana, Script::Hangul];
+
+/// Whether `c` is a letter written in one of [`CJK_SCRIPTS`]. Ideographic
+/// punctuation such as `、`, shared by these scripts, is not, and neither
+/// are characters of any script, such as digits.
+pub fn is_cjk(c: char) -> bool {
+    let scripts = c.script_extension();
+    c.is_alphanumeric()
+        && !scripts.is_common()
+        && !scripts.is_inherited()
+        && CJK_SCRIPTS.iter().any(|&script| scripts.contains_script(script))
+}
+
+/// The Brahmic scripts of India that [`IndicNormalizationFilter`] normalizes.
+///
+/// [`IndicNormalizationFilter`]: crate::filter::IndicNormalizationFilter
+pub const INDIC_SCRIPTS: [Script; 9] = [
+    Script::Devanagari,
+    Script::Bengali,
+    Script::Gurmukhi,
+    Script::Gujarati,
+    Script::Oriya,
+    Script::Tamil,
+    Script::Telugu,
+    Script::Kannada,
+    Script::Malayalam,
+];
+
+/// Whether `c` belongs to one of [`INDIC_SCRIPTS`], including their vowel
+/// signs, viramas and digits.
+pub fn is_indic(c: char) -> bool {
+    if c.is_ascii() {
+        return false;
+    }
+    let scripts = c.script_extension();
+    !scripts.is_common()
+        && !scripts.is_inherited()
+        && INDIC_SCRIPTS.iter().any(|&script| scripts.contains_script(script))
+}
+
+/// Scripts written without spaces between words, which the Unicode strategy
+/// breaks into words with a dictionary.
+pub const DICTIONARY_SCRIPTS: [Script; 4] = [Script::Thai, Script::Lao, Script::Khmer, Script::Myanmar];
+
+/// Whether `c` belongs to one of [`DICTIONARY_SCRIPTS`], including their
+/// vowel signs and digits.
+pub fn needs_dictionary(c: char) -> bool {
+    if c.is_ascii() {
+        return false;
+    }
+    let scripts = c.script_extension();
+    !scripts.is_common()
+        && !scripts.is_inherited()
+        && DICTIONARY_SCRIPTS.iter().any(|&script| scripts.contains_script(script))
+}
+
+/// Splits `text` into its runs of characters that do and don't
+/// [`needs_dictionary`], in order, each with whether it does.
+pub fn dictionary_runs(text: &str) -> impl Iterator<Item = (&str, bool)> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let needs = needs_dictionary(rest.chars().next()?);
+        let end = rest
+            .char_indices()
+            .find(|&(_, c)| needs_dictionary(c) != needs)
+            .map_or(rest.len(), |(i, _)| i);
+        let (run, tail) = rest.split_at(end);
+        rest = tail;
+        Some((run, needs))
+    })
+}
+
+/// The script named `name`, by its Unicode full name (`latin`, `old_italic`)
+/// or ISO 15924 code (`Latn`), in any case.
+pub fn parse_script(name: &str) -> Option<Script> {
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect::<String>())
+            .unwrap_or_default()
+    };
+    let full_name = name.split('_').map(capitalize).collect::<Vec<_>>().join("_");
+
+    Script::from_full_name(&full_name).or_else(|| Script::from_short_name(&capitalize(name)))
+}
+
+/// The one script every character of `text` can be written in, such as
+/// `Katakana` for `タワー`. `None` for text mixing scripts (`東京タワー`) or
+/// made only of characters of any script (`2024`).
+pub fn token_script(text: &str) -> Option<Script> {
+    let scripts = ScriptExtension::for_str(text);
+    if scripts.is_common() || scripts.is_inherited() {
+        return None;
+    }
+    scripts.iter().next()
+}
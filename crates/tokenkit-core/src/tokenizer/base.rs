@@ -0,0 +1,90 @@
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+/// Common functionality for tokenizers that support preserve_patterns.
+/// Patterns are checked in validate_config(), but a failure here is still
+/// returned rather than unwrapped.
+pub fn create_preserve_patterns(config: &TokenizerConfig) -> Result<Vec<Regex>> {
+    config
+        .preserve_patterns
+        .iter()
+        .map(|p| config.regex_limits.compile(p))
+        .collect()
+}
+
+/// The characters any of a set of regexes can match, to tell whether a
+/// match could straddle the point where a stream is cut.
+pub struct RegexChars(Vec<Option<Hir>>);
+
+impl RegexChars {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        // A pattern that doesn't parse here is assumed to match anything
+        Self(patterns.into_iter().map(|pattern| regex_syntax::parse(pattern).ok()).collect())
+    }
+
+    /// Whether some match of these regexes can contain `c`.
+    pub fn may_match(&self, c: char) -> bool {
+        self.0.iter().any(|hir| hir.as_ref().is_none_or(|hir| hir_may_match(hir, c)))
+    }
+
+    /// `boundary`, a point where the strategy could cut a stream, or `None`
+    /// when the character before it could be part of a match, which might
+    /// then continue in the next chunk.
+    pub fn hold_back(&self, text: &str, boundary: Option<usize>) -> Option<usize> {
+        let boundary = boundary?;
+        match text[..boundary].chars().next_back() {
+            Some(c) if self.may_match(c) => None,
+            _ => Some(boundary),
+        }
+    }
+}
+
+fn hir_may_match(hir: &Hir, c: char) -> bool {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => false,
+        HirKind::Literal(literal) => String::from_utf8_lossy(&literal.0).contains(c),
+        HirKind::Class(Class::Unicode(class)) => class.ranges().iter().any(|range| (range.start()..=range.end()).contains(&c)),
+        HirKind::Class(Class::Bytes(class)) => {
+            c.is_ascii() && class.ranges().iter().any(|range| (range.start()..=range.end()).contains(&(c as u8)))
+        }
+        HirKind::Repetition(repetition) => hir_may_match(&repetition.sub, c),
+        HirKind::Capture(capture) => hir_may_match(&capture.sub, c),
+        HirKind::Concat(hirs) | HirKind::Alternation(hirs) => hirs.iter().any(|hir| hir_may_match(hir, c)),
+    }
+}
+
+/// Base fields that most tokenizers need
+pub struct BaseTokenizerFields {
+    pub config: TokenizerConfig,
+    pub preserve_patterns: Vec<Regex>,
+    pub preserve_chars: RegexChars,
+}
+
+impl BaseTokenizerFields {
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        let preserve_patterns = create_preserve_patterns(&config)?;
+        let preserve_chars = RegexChars::new(config.preserve_patterns.iter().map(String::as_str));
+        Ok(Self {
+            config,
+            preserve_patterns,
+            preserve_chars,
+        })
+    }
+
+    /// `boundary` unless a preserved match could straddle it; see
+    /// [`RegexChars::hold_back`].
+    pub fn stream_boundary(&self, text: &str, boundary: Option<usize>) -> Option<usize> {
+        self.preserve_chars.hold_back(text, boundary)
+    }
+
+    pub fn has_preserve_patterns(&self) -> bool {
+        !self.preserve_patterns.is_empty()
+    }
+
+    pub fn preserve_patterns(&self) -> &[Regex] {
+        &self.preserve_patterns
+    }
+}
+
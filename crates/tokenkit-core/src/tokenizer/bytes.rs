@@ -0,0 +1,50 @@
+use super::{strip_punctuation, Token, Tokenizer};
+use crate::config::TokenizerConfig;
+use std::borrow::Cow;
+
+/// Splits on ASCII whitespace and lowercases ASCII letters only, so text
+/// decoded with `encoding::decode_bytes` keeps every non-ASCII byte as is.
+pub struct BytesTokenizer {
+    config: TokenizerConfig,
+}
+
+impl BytesTokenizer {
+    pub fn new(config: TokenizerConfig) -> Self {
+        Self { config }
+    }
+
+    fn filter<'a>(&self, token: Token<'a>) -> Option<Token<'a>> {
+        let mut token = token;
+
+        if self.config.lowercase && token.text.bytes().any(|b| b.is_ascii_uppercase()) {
+            token = token.map_text(|text| Cow::Owned(text.to_ascii_lowercase()));
+        }
+
+        if self.config.remove_punctuation {
+            token = token.map_text(|text| strip_punctuation(text, None));
+            if token.text.is_empty() {
+                return None;
+            }
+        }
+
+        Some(token)
+    }
+}
+
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| c.is_ascii_whitespace()).filter(|s| !s.is_empty())
+}
+
+impl Tokenizer for BytesTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        words(text).filter_map(|s| self.filter(Token::slice(text, s))).collect()
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        Box::new(words(text).filter_map(move |s| self.filter(Token::slice(text, s))))
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        text.bytes().rposition(|b| b.is_ascii_whitespace()).map(|i| i + 1)
+    }
+}
@@ -0,0 +1,71 @@
+use super::{result_tokens, Token, Tokenizer};
+use crate::char_filter::{Filter, FilteredText};
+use crate::error::Result;
+
+/// Runs a config's `char_filters` over the input before the tokenizer for
+/// its strategy. Input the filters leave alone is tokenized as it is;
+/// tokens of changed input own their text, with offsets into the input.
+pub struct CharFilteredTokenizer {
+    inner: Box<dyn Tokenizer>,
+    filters: Vec<Box<dyn Filter>>,
+}
+
+impl CharFilteredTokenizer {
+    pub fn new(inner: Box<dyn Tokenizer>, filters: Vec<Box<dyn Filter>>) -> Self {
+        Self { inner, filters }
+    }
+
+    fn tokenize_filtered(&self, filtered: &FilteredText) -> Vec<Token<'static>> {
+        input_offsets(filtered, self.inner.tokenize_with_offsets(filtered.as_str()))
+    }
+
+    fn try_tokenize_filtered(&self, filtered: &FilteredText) -> Result<Vec<Token<'static>>> {
+        Ok(input_offsets(filtered, self.inner.try_tokenize_with_offsets(filtered.as_str())?))
+    }
+}
+
+// Owned tokens of the filtered text, with offsets into the input
+fn input_offsets(filtered: &FilteredText, tokens: Vec<Token<'_>>) -> Vec<Token<'static>> {
+    tokens
+        .into_iter()
+        .map(|token| Token {
+            start: filtered.input_start(token.start),
+            end: filtered.input_end(token.end),
+            ..token.into_owned()
+        })
+        .collect()
+}
+
+impl Tokenizer for CharFilteredTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        match FilteredText::new(text, &self.filters) {
+            Some(filtered) => self.tokenize_filtered(&filtered),
+            None => self.inner.tokenize_with_offsets(text),
+        }
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        match FilteredText::new(text, &self.filters) {
+            Some(filtered) => Box::new(self.tokenize_filtered(&filtered).into_iter()),
+            None => self.inner.tokens(text),
+        }
+    }
+
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        match FilteredText::new(text, &self.filters) {
+            Some(filtered) => self.try_tokenize_filtered(&filtered),
+            None => self.inner.try_tokenize_with_offsets(text),
+        }
+    }
+
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        match FilteredText::new(text, &self.filters) {
+            Some(filtered) => result_tokens(self.try_tokenize_filtered(&filtered)),
+            None => self.inner.try_tokens(text),
+        }
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.inner.stream_boundary(text)
+    }
+}
@@ -0,0 +1,73 @@
+use super::{
+    apply_preserve_patterns_with_tokenizer, post_process, post_process_token, BaseTokenizerFields, Token, Tokenizer,
+};
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use std::collections::HashSet;
+
+pub struct CharGroupTokenizer {
+    base: BaseTokenizerFields,
+    split_chars: HashSet<char>,
+}
+
+impl CharGroupTokenizer {
+    pub fn new(config: TokenizerConfig, split_on_chars: String) -> Result<Self> {
+        // Note: Empty split_on_chars is valid - it makes the tokenizer behave like
+        // a keyword tokenizer (no splitting, returns whole text as single token)
+        let split_chars: HashSet<char> = split_on_chars.chars().collect();
+
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+            split_chars,
+        })
+    }
+
+    fn split<'s, 'a: 's>(&'s self, text: &'a str) -> impl Iterator<Item = &'a str> + 's {
+        text.split(move |c: char| self.split_chars.contains(&c))
+            .filter(|s| !s.is_empty())
+    }
+
+    fn tokenize_text<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.split(text).map(|s| Token::slice(text, s)).collect()
+    }
+}
+
+impl Tokenizer for CharGroupTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens = self.tokenize_text(text);
+
+        if self.base.has_preserve_patterns() {
+            apply_preserve_patterns_with_tokenizer(
+                tokens,
+                self.base.preserve_patterns(),
+                text,
+                &self.base.config,
+                |t| self.tokenize_text(t),
+            )
+        } else {
+            post_process(tokens, &self.base.config)
+        }
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        if self.base.has_preserve_patterns() {
+            return Box::new(self.tokenize_with_offsets(text).into_iter());
+        }
+
+        Box::new(
+            self.split(text)
+                .filter_map(move |s| post_process_token(Token::slice(text, s), &self.base.config)),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        // Tokens only ever end at one of the split characters
+        let boundary = text
+            .char_indices()
+            .rev()
+            .find(|(_, c)| self.split_chars.contains(c))
+            .map(|(i, c)| i + c.len_utf8());
+        self.base.stream_boundary(text, boundary)
+    }
+
+}
@@ -0,0 +1,151 @@
+use super::{apply_preserve_patterns_with_tokenizer, post_process_token, BaseTokenizerFields, Token, Tokenizer};
+use crate::config::TokenizerConfig;
+use crate::error::{Result, TokenizerError};
+use jieba_rs::Jieba;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::sync::{Arc, OnceLock};
+
+/// Chinese word segmentation with a frequency dictionary. Every run of Han
+/// characters is turned into a DAG of the dictionary words starting at each
+/// character, and cut along the path with the highest total probability.
+///
+/// Dictionary files have one entry per line: the word, then optionally its
+/// frequency and a part-of-speech tag, separated by whitespace. Words in user
+/// dictionaries without a frequency get one just high enough to be kept
+/// whole.
+pub struct CjkTokenizer {
+    base: BaseTokenizerFields,
+    jieba: Arc<Jieba>,
+    search_mode: bool,
+    hmm: bool,
+}
+
+impl CjkTokenizer {
+    /// `dictionary` replaces the bundled dictionary; `user_dictionaries` are
+    /// added on top of whichever is used.
+    pub fn new(config: TokenizerConfig, dictionary: Option<&str>, user_dictionaries: &[String]) -> Result<Self> {
+        let mut jieba = match dictionary {
+            Some(path) => Arc::new(Jieba::with_dict(&mut open(path)?).map_err(|e| invalid(path, e))?),
+            None => bundled(),
+        };
+
+        for path in user_dictionaries {
+            load_user_dictionary(Arc::make_mut(&mut jieba), path)?;
+        }
+
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+            jieba,
+            search_mode: false,
+            hmm: true,
+        })
+    }
+
+    pub fn with_search_mode(mut self, search_mode: bool) -> Self {
+        self.search_mode = search_mode;
+        self
+    }
+
+    pub fn with_hmm(mut self, hmm: bool) -> Self {
+        self.hmm = hmm;
+        self
+    }
+
+    // Words of `text`, leaving out the whitespace and punctuation that come
+    // back as segments of their own
+    fn segment<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let words = if self.search_mode {
+            self.jieba.cut_for_search(text, self.hmm)
+        } else {
+            self.jieba.cut(text, self.hmm)
+        };
+
+        words
+            .into_iter()
+            .filter(|word| word.chars().any(char::is_alphanumeric))
+            .map(|word| Token::slice(text, word))
+            .collect()
+    }
+}
+
+// Parsed once and shared by every tokenizer that doesn't bring its own
+fn bundled() -> Arc<Jieba> {
+    static BUNDLED: OnceLock<Arc<Jieba>> = OnceLock::new();
+    BUNDLED.get_or_init(|| Arc::new(Jieba::new())).clone()
+}
+
+fn open(path: &str) -> Result<BufReader<File>> {
+    File::open(path).map(BufReader::new).map_err(|e| invalid(path, e))
+}
+
+fn invalid(path: &str, error: impl std::fmt::Display) -> TokenizerError {
+    TokenizerError::InvalidDictionary {
+        path: path.to_string(),
+        message: error.to_string(),
+    }
+}
+
+fn load_user_dictionary(jieba: &mut Jieba, path: &str) -> Result<()> {
+    for (number, line) in open(path)?.lines().enumerate() {
+        let line = line.map_err(|e| invalid(path, e))?;
+        let mut fields = line.split_whitespace();
+        let Some(word) = fields.next() else {
+            continue;
+        };
+
+        // Either field may be left out, as in jieba's own user dictionaries
+        let mut field = fields.next();
+        let freq = match field.map(str::parse::<usize>) {
+            Some(Ok(freq)) => {
+                field = fields.next();
+                Some(freq)
+            }
+            _ => None,
+        };
+        let tag = match field {
+            Some(tag) if tag.chars().all(|c| c.is_ascii_alphabetic()) => Some(tag),
+            Some(other) => {
+                let message = format!("line {}: '{}' is neither a frequency nor a tag", number + 1, other);
+                return Err(invalid(path, message));
+            }
+            None => None,
+        };
+        jieba.add_word(word, freq, tag);
+    }
+    Ok(())
+}
+
+impl Tokenizer for CjkTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens = self.segment(text);
+
+        if self.base.has_preserve_patterns() {
+            return apply_preserve_patterns_with_tokenizer(
+                tokens,
+                self.base.preserve_patterns(),
+                text,
+                &self.base.config,
+                |slice| self.segment(slice),
+            );
+        }
+
+        tokens
+            .into_iter()
+            .filter_map(|token| post_process_token(token, &self.base.config))
+            .collect()
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, sentence_boundary(text))
+    }
+}
+
+/// Cuts after the last whitespace or sentence-ending punctuation, which
+/// never joins the words on either side, for scripts without spaces.
+pub(crate) fn sentence_boundary(text: &str) -> Option<usize> {
+    text.char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace() || matches!(c, '。' | '！' | '？' | '；' | '…'))
+        .map(|(i, c)| i + c.len_utf8())
+}
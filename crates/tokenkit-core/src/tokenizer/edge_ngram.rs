@@ -0,0 +1,73 @@
+use super::{lowercase_token, offset_in, Token, Tokenizer};
+use crate::config::TokenizerConfig;
+use std::borrow::Cow;
+
+pub struct EdgeNgramTokenizer {
+    config: TokenizerConfig,
+    min_gram: usize,
+    max_gram: usize,
+}
+
+impl EdgeNgramTokenizer {
+    pub fn new(config: TokenizerConfig, min_gram: usize, max_gram: usize) -> Self {
+        // Validate and sanitize parameters
+        let min_gram = min_gram.max(1); // Minimum 1 character
+        let max_gram = max_gram.max(min_gram); // Ensure max >= min
+
+        Self { config, min_gram, max_gram }
+    }
+
+    /// Char ranges of the prefixes of a word `len` chars long, from `min_gram`
+    /// to `max_gram` characters.
+    fn gram_ranges(&self, len: usize) -> Vec<(usize, usize)> {
+        let max = self.max_gram.min(len);
+        (self.min_gram..=max).map(|gram_size| (0, gram_size)).collect()
+    }
+
+    /// Grams of one whitespace-separated `word` of `text` with the configured
+    /// filters applied. Offsets span the original characters of each gram,
+    /// including any punctuation removed from inside it.
+    fn word_ngrams<'a>(&self, text: &'a str, word: &'a str) -> Vec<Token<'a>> {
+        let word_start = offset_in(text, word);
+
+        // Byte range within `word` of every character that survives the filters
+        let chars: Vec<(usize, usize)> = word
+            .char_indices()
+            .filter(|(_, c)| !(self.config.remove_punctuation && c.is_ascii_punctuation()))
+            .map(|(i, c)| (i, i + c.len_utf8()))
+            .collect();
+        let stripped = chars.len() != word.chars().count();
+
+        self.gram_ranges(chars.len())
+            .into_iter()
+            .map(|(first, last)| {
+                let (start, end) = (chars[first].0, chars[last - 1].1);
+
+                // Grams borrow from the input unless punctuation removal rewrote the word
+                let gram = if stripped {
+                    Cow::Owned(chars[first..last].iter().map(|&(s, e)| &word[s..e]).collect())
+                } else {
+                    Cow::Borrowed(&word[start..end])
+                };
+
+                let gram = if self.config.lowercase { lowercase_token(gram, self.config.case_locale()) } else { gram };
+                Token::new(gram, word_start + start, word_start + end)
+            })
+            .collect()
+    }
+}
+
+impl Tokenizer for EdgeNgramTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        text.split_whitespace()
+            .flat_map(|word| self.word_ngrams(text, word))
+            .collect()
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        // Grams are generated one word at a time so only a single word's
+        // grams are ever held in memory
+        Box::new(text.split_whitespace().flat_map(move |word| self.word_ngrams(text, word)))
+    }
+
+}
@@ -0,0 +1,59 @@
+use super::{Token, Tokenizer};
+use crate::error::{Result, TokenizerError};
+use crate::filter::{Filter, Tokens};
+use std::cell::Cell;
+use std::iter;
+use std::rc::Rc;
+
+/// Runs a config's `filters` over the tokens of the tokenizer for its
+/// strategy. Filters pull tokens lazily, so stopping early, as `max_tokens`
+/// does, leaves the rest of the input untokenized.
+pub struct FilteredTokenizer {
+    inner: Box<dyn Tokenizer>,
+    filters: Vec<Box<dyn Filter>>,
+}
+
+impl FilteredTokenizer {
+    pub fn new(inner: Box<dyn Tokenizer>, filters: Vec<Box<dyn Filter>>) -> Self {
+        Self { inner, filters }
+    }
+
+    fn filter<'a>(&self, text: &'a str, tokens: Tokens<'a>) -> Tokens<'a> {
+        self.filters.iter().fold(tokens, |tokens, filter| filter.filter(text, tokens))
+    }
+}
+
+impl Tokenizer for FilteredTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.filter(text, Box::new(self.inner.tokenize_with_offsets(text).into_iter())).collect()
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        self.filter(text, self.inner.tokens(text))
+    }
+
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        let tokens = self.inner.try_tokenize_with_offsets(text)?;
+        Ok(self.filter(text, Box::new(tokens.into_iter())).collect())
+    }
+
+    // Filters see the tokens before the inner tokenizer's first error, which
+    // then ends the stream
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        let error: Rc<Cell<Option<TokenizerError>>> = Rc::default();
+        let found = Rc::clone(&error);
+        let tokens = self.inner.try_tokens(text).map_while(move |token| {
+            token.map_err(|e| found.set(Some(e))).ok()
+        });
+
+        Box::new(
+            self.filter(text, Box::new(tokens))
+                .map(Ok)
+                .chain(iter::from_fn(move || error.take().map(Err))),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.inner.stream_boundary(text)
+    }
+}
@@ -0,0 +1,38 @@
+use super::{post_process, post_process_token, Token, Tokenizer};
+use crate::config::TokenizerConfig;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub struct GraphemeTokenizer {
+    config: TokenizerConfig,
+    extended: bool,
+}
+
+impl GraphemeTokenizer {
+    pub fn new(config: TokenizerConfig, extended: bool) -> Self {
+        Self { config, extended }
+    }
+}
+
+impl Tokenizer for GraphemeTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let graphemes: Vec<Token<'a>> = text
+            .grapheme_indices(self.extended)
+            .map(|(i, s)| Token::new(s, i, i + s.len()))
+            .collect();
+
+        post_process(graphemes, &self.config)
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        Box::new(
+            text.grapheme_indices(self.extended)
+                .filter_map(move |(i, s)| post_process_token(Token::new(s, i, i + s.len()), &self.config)),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        // The last cluster may still grow with combining marks from the next chunk
+        text.grapheme_indices(self.extended).next_back().map(|(i, _)| i)
+    }
+
+}
@@ -1,4 +1,4 @@
-use super::{post_process, Tokenizer};
+use super::{post_process, Token, Tokenizer};
 use crate::config::TokenizerConfig;
 
 pub struct KeywordTokenizer {
@@ -12,14 +12,19 @@ impl KeywordTokenizer {
 }
 
 impl Tokenizer for KeywordTokenizer {
-    fn tokenize(&self, text: &str) -> Vec<String> {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
         let trimmed = text.trim();
         if trimmed.is_empty() {
             return vec![];
         }
 
-        let tokens = vec![trimmed.to_string()];
+        let tokens = vec![Token::slice(text, trimmed)];
         post_process(tokens, &self.config)
     }
 
-}
\ No newline at end of file
+    fn stream_boundary(&self, _text: &str) -> Option<usize> {
+        // The whole input is a single token
+        None
+    }
+
+}
@@ -0,0 +1,66 @@
+use super::{
+    apply_preserve_patterns, post_process, post_process_token, whitespace_boundary, BaseTokenizerFields, Token, Tokenizer,
+};
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use unicode_categories::UnicodeCategories;
+
+pub struct LetterTokenizer {
+    base: BaseTokenizerFields,
+}
+
+impl LetterTokenizer {
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+        })
+    }
+}
+
+/// Whether `c` is a combining mark (Mn or Mc), such as the Devanagari
+/// virama `्` or nukta `़`.
+fn is_mark(c: char) -> bool {
+    !c.is_ascii() && (c.is_mark_nonspacing() || c.is_mark_spacing_combining())
+}
+
+/// Whether `c` belongs in a word: a letter, or a combining mark, which is
+/// part of the letter before it. Indic conjuncts are written with a virama
+/// between letters, and would otherwise be split in two.
+pub(crate) fn is_letter(c: char) -> bool {
+    c.is_alphabetic() || is_mark(c)
+}
+
+/// Maximal runs of letters and their marks, borrowed from `text`. Marks with
+/// no letter before them are left out.
+pub(crate) fn letter_runs(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !is_letter(c))
+        .map(|run| run.trim_start_matches(is_mark))
+        .filter(|s| !s.is_empty())
+}
+
+impl Tokenizer for LetterTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens: Vec<Token<'a>> = letter_runs(text).map(|s| Token::slice(text, s)).collect();
+
+        if self.base.has_preserve_patterns() {
+            apply_preserve_patterns(tokens, self.base.preserve_patterns(), text, &self.base.config)
+        } else {
+            post_process(tokens, &self.base.config)
+        }
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        if self.base.has_preserve_patterns() {
+            return Box::new(self.tokenize_with_offsets(text).into_iter());
+        }
+
+        Box::new(
+            letter_runs(text)
+                .filter_map(move |s| post_process_token(Token::slice(text, s), &self.base.config)),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, whitespace_boundary(text))
+    }
+}
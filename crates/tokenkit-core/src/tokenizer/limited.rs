@@ -0,0 +1,176 @@
+use super::{offset_in, Token, Tokenizer};
+use crate::config::{Limits, LongTokenPolicy, OverflowPolicy};
+use crate::error::{Result, TokenizerError};
+use std::borrow::Cow;
+
+/// Enforces a config's [`Limits`] around the tokenizer for its strategy.
+///
+/// Tokens are pulled lazily from the inner tokenizer, so emitting stops at
+/// `max_tokens` instead of producing every token first. The `try_` methods
+/// apply the `overflow` policy; the infallible ones can't report an error, so
+/// they always truncate.
+pub struct LimitedTokenizer {
+    inner: Box<dyn Tokenizer>,
+    limits: Limits,
+}
+
+impl LimitedTokenizer {
+    pub fn new(inner: Box<dyn Tokenizer>, limits: Limits) -> Self {
+        Self { inner, limits }
+    }
+
+    fn limited<'a>(&'a self, text: &'a str, overflow: OverflowPolicy) -> LimitedTokens<'a> {
+        let mut error = None;
+        let text = match self.limits.max_input_bytes {
+            Some(max) if text.len() > max => match overflow {
+                OverflowPolicy::Error => {
+                    error = Some(limit_exceeded("max_input_bytes", max));
+                    ""
+                }
+                OverflowPolicy::Truncate => self.truncate_input(text, max),
+            },
+            _ => text,
+        };
+
+        LimitedTokens {
+            tokens: self.inner.tokens(text),
+            limits: &self.limits,
+            overflow,
+            emitted: 0,
+            error,
+        }
+    }
+
+    // Cuts at the last point the inner tokenizer could end a stream chunk,
+    // so the last token isn't cut in half, falling back to a character boundary
+    fn truncate_input<'a>(&self, text: &'a str, max: usize) -> &'a str {
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let prefix = &text[..end];
+        match self.inner.stream_boundary(prefix) {
+            Some(boundary) if boundary > 0 => &prefix[..boundary],
+            _ => prefix,
+        }
+    }
+}
+
+impl Tokenizer for LimitedTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.limited(text, OverflowPolicy::Truncate)
+            .map_while(std::result::Result::ok)
+            .map(|token| reborrow(text, token))
+            .collect()
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        Box::new(self.limited(text, OverflowPolicy::Truncate).map_while(std::result::Result::ok))
+    }
+
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        self.limited(text, self.limits.overflow)
+            .map(|token| token.map(|token| reborrow(text, token)))
+            .collect()
+    }
+
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        Box::new(self.limited(text, self.limits.overflow))
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.inner.stream_boundary(text)
+    }
+}
+
+struct LimitedTokens<'a> {
+    tokens: Box<dyn Iterator<Item = Token<'a>> + 'a>,
+    limits: &'a Limits,
+    overflow: OverflowPolicy,
+    emitted: usize,
+    // Reported before any token, for input over max_input_bytes
+    error: Option<TokenizerError>,
+}
+
+impl<'a> Iterator for LimitedTokens<'a> {
+    type Item = Result<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        loop {
+            let token = self.tokens.next()?;
+            let Some(token) = self.shorten(token) else {
+                continue;
+            };
+
+            if let Some(max) = self.limits.max_tokens {
+                if self.emitted == max {
+                    // There is at least one token too many; stop producing them
+                    self.tokens = Box::new(std::iter::empty());
+                    return match self.overflow {
+                        OverflowPolicy::Error => Some(Err(limit_exceeded("max_tokens", max))),
+                        OverflowPolicy::Truncate => None,
+                    };
+                }
+            }
+            self.emitted += 1;
+            return Some(Ok(token));
+        }
+    }
+}
+
+impl<'a> LimitedTokens<'a> {
+    // Applies max_token_length; truncated tokens keep the offsets of the whole token
+    fn shorten(&self, token: Token<'a>) -> Option<Token<'a>> {
+        let Some(max) = self.limits.max_token_length else {
+            return Some(token);
+        };
+        let Some((cut, _)) = token.text.char_indices().nth(max) else {
+            return Some(token);
+        };
+
+        match self.limits.long_tokens {
+            LongTokenPolicy::Drop => None,
+            LongTokenPolicy::Truncate => Some(token.map_text(|text| match text {
+                Cow::Borrowed(text) => Cow::Borrowed(&text[..cut]),
+                Cow::Owned(mut text) => {
+                    text.truncate(cut);
+                    Cow::Owned(text)
+                }
+            })),
+        }
+    }
+}
+
+pub(super) fn limit_exceeded(limit: &str, max: usize) -> TokenizerError {
+    TokenizerError::LimitExceeded {
+        limit: limit.to_string(),
+        max,
+    }
+}
+
+// Tokens from `tokens()` are tied to the tokenizer's borrow as well as the
+// text's; rebinds one to the text alone, copying it only when it doesn't point
+// into the text
+fn reborrow<'a>(text: &'a str, token: Token<'_>) -> Token<'a> {
+    let Token {
+        text: token_text,
+        start,
+        end,
+        attributes,
+    } = token;
+    let token_text = match token_text {
+        Cow::Borrowed(slice) if text.as_bytes().as_ptr_range().contains(&slice.as_ptr()) => {
+            let offset = offset_in(text, slice);
+            Cow::Borrowed(&text[offset..offset + slice.len()])
+        }
+        other => Cow::Owned(other.into_owned()),
+    };
+    Token {
+        attributes,
+        ..Token::new(token_text, start, end)
+    }
+}
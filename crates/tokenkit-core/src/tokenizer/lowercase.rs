@@ -1,37 +1,31 @@
-use super::{apply_preserve_patterns, BaseTokenizerFields, Tokenizer};
+use super::letter::letter_runs;
+use super::{apply_preserve_patterns, lowercase_token, whitespace_boundary, BaseTokenizerFields, Token, Tokenizer};
 use crate::config::TokenizerConfig;
+use crate::error::Result;
+use std::borrow::Cow;
 
 pub struct LowercaseTokenizer {
     base: BaseTokenizerFields,
 }
 
 impl LowercaseTokenizer {
-    pub fn new(config: TokenizerConfig) -> Self {
-        Self {
-            base: BaseTokenizerFields::new(config),
-        }
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+        })
     }
 }
 
 impl Tokenizer for LowercaseTokenizer {
-    fn tokenize(&self, text: &str) -> Vec<String> {
-        let mut tokens = Vec::new();
-        let mut current_token = String::new();
-
-        for ch in text.chars() {
-            if ch.is_alphabetic() {
-                for lowercase_ch in ch.to_lowercase() {
-                    current_token.push(lowercase_ch);
-                }
-            } else if !current_token.is_empty() {
-                tokens.push(current_token.clone());
-                current_token.clear();
-            }
-        }
-
-        if !current_token.is_empty() {
-            tokens.push(current_token);
-        }
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let locale = self.base.config.case_locale();
+        let tokens: Vec<Token<'a>> = letter_runs(text)
+            // Whole runs are lowercased, so a word-final sigma becomes `ς`
+            .map(|run| Token {
+                text: lowercase_token(Cow::Borrowed(run), locale),
+                ..Token::slice(text, run)
+            })
+            .collect();
 
         // Lowercase tokenizer always lowercases, ignore config.lowercase
         // Note: remove_punctuation has no effect since we already split on non-alphabetic
@@ -48,4 +42,7 @@ impl Tokenizer for LowercaseTokenizer {
         }
     }
 
-}
\ No newline at end of file
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, whitespace_boundary(text))
+    }
+}
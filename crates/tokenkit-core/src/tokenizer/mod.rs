@@ -0,0 +1,422 @@
+mod base;
+mod whitespace;
+mod unicode;
+mod pattern;
+mod sentence;
+mod grapheme;
+mod keyword;
+mod edge_ngram;
+mod ngram;
+mod path_hierarchy;
+mod url_email;
+mod char_group;
+mod letter;
+mod lowercase;
+mod bytes;
+mod cjk;
+mod char_filtered;
+mod filtered;
+mod limited;
+mod router;
+mod stream;
+mod token;
+
+pub(crate) use base::{create_preserve_patterns, BaseTokenizerFields, RegexChars};
+
+pub use whitespace::WhitespaceTokenizer;
+pub use unicode::UnicodeTokenizer;
+pub use pattern::PatternTokenizer;
+pub use sentence::SentenceTokenizer;
+pub use grapheme::GraphemeTokenizer;
+pub use keyword::KeywordTokenizer;
+pub use edge_ngram::EdgeNgramTokenizer;
+pub use ngram::NgramTokenizer;
+pub use path_hierarchy::PathHierarchyTokenizer;
+pub use url_email::UrlEmailTokenizer;
+pub use char_group::CharGroupTokenizer;
+pub use letter::LetterTokenizer;
+pub use lowercase::LowercaseTokenizer;
+pub use bytes::BytesTokenizer;
+pub use cjk::CjkTokenizer;
+pub use char_filtered::CharFilteredTokenizer;
+pub use filtered::FilteredTokenizer;
+pub use limited::LimitedTokenizer;
+pub use router::RouterTokenizer;
+pub use stream::TokenStream;
+pub use token::{Token, TokenAttributes};
+
+pub(crate) use cjk::sentence_boundary;
+pub(crate) use token::offset_in;
+
+use crate::case::CaseLocale;
+use crate::config::{validate_config, TokenizerConfig, TokenizerStrategy};
+use crate::error::Result;
+use crate::char_filter;
+use crate::filter;
+use crate::huggingface::HuggingFaceTokenizer;
+use crate::japanese::JapaneseTokenizer;
+use regex::Regex;
+use std::borrow::Cow;
+
+/// Tokens borrow from the input text and are only allocated when a filter
+/// (lowercasing, punctuation removal, ...) actually changes them.
+pub trait Tokenizer: Send + Sync {
+    /// Tokens of `text` together with the byte range each one came from.
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>>;
+
+    fn tokenize<'a>(&self, text: &'a str) -> Vec<Cow<'a, str>> {
+        self.tokenize_with_offsets(text)
+            .into_iter()
+            .map(|token| token.text)
+            .collect()
+    }
+
+    /// Lazily yields tokens for `text`.
+    /// The default collects `tokenize_with_offsets`; tokenizers that can produce
+    /// tokens one at a time override this so huge inputs never materialize a full Vec.
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        Box::new(self.tokenize_with_offsets(text).into_iter())
+    }
+
+    /// Like `tokenize_with_offsets`, but fails with
+    /// `TokenizerError::LimitExceeded` when the config's `limits` are exceeded
+    /// and their `overflow` policy is `Error`. Only tokenizers built with
+    /// limits ever fail.
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        Ok(self.tokenize_with_offsets(text))
+    }
+
+    fn try_tokenize<'a>(&self, text: &'a str) -> Result<Vec<Cow<'a, str>>> {
+        Ok(self
+            .try_tokenize_with_offsets(text)?
+            .into_iter()
+            .map(|token| token.text)
+            .collect())
+    }
+
+    /// Like `tokens`, failing like `try_tokenize_with_offsets`. The error is
+    /// the last item.
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        Box::new(self.tokens(text).map(Ok))
+    }
+
+    /// Byte offset up to which `text` can be tokenized independently of
+    /// whatever follows it. Used by `TokenStream` to cut chunked input at a
+    /// point where no token can straddle the cut; `None` means the whole input
+    /// must be buffered. The default cuts after the last whitespace character.
+    /// Strategies with preserve patterns return `None` instead when a pattern
+    /// could match the character before the cut, since a match might continue
+    /// in the next chunk.
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        whitespace_boundary(text)
+    }
+}
+
+/// Yields a collected `try_` result as `try_tokens` does: every token, or
+/// the error alone.
+pub(crate) fn result_tokens<'a>(result: Result<Vec<Token<'a>>>) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+    match result {
+        Ok(tokens) => Box::new(tokens.into_iter().map(Ok)),
+        Err(e) => Box::new(std::iter::once(Err(e))),
+    }
+}
+
+/// Byte offset just past the last whitespace character of `text`.
+pub(crate) fn whitespace_boundary(text: &str) -> Option<usize> {
+    text.char_indices()
+        .rev()
+        .find(|(_, c)| c.is_whitespace())
+        .map(|(i, c)| i + c.len_utf8())
+}
+
+/// Builds the tokenizer for `config.strategy`, validating the config first.
+/// Configs with `filters` get a [`FilteredTokenizer`] running them on the
+/// tokens, configs with `char_filters` a [`CharFilteredTokenizer`] running
+/// them on the input, and configs with `limits` a [`LimitedTokenizer`]
+/// enforcing them on the result. Token filters run inside the char filters,
+/// on the filtered text, so a token they split up still maps each part back
+/// to the input.
+pub fn from_config(config: TokenizerConfig) -> Result<Box<dyn Tokenizer>> {
+    validate_config(&config)?;
+
+    let limits = config.limits.clone();
+    let char_filters: Vec<_> = config.char_filters.iter().map(char_filter::from_config).collect();
+    let filters: Vec<_> = config.filters.iter().map(filter::from_config).collect();
+    let mut tokenizer = strategy_tokenizer(config)?;
+    if !filters.is_empty() {
+        tokenizer = Box::new(FilteredTokenizer::new(tokenizer, filters));
+    }
+    if !char_filters.is_empty() {
+        tokenizer = Box::new(CharFilteredTokenizer::new(tokenizer, char_filters));
+    }
+    if limits.is_unlimited() {
+        Ok(tokenizer)
+    } else {
+        Ok(Box::new(LimitedTokenizer::new(tokenizer, limits)))
+    }
+}
+
+fn strategy_tokenizer(config: TokenizerConfig) -> Result<Box<dyn Tokenizer>> {
+    let tokenizer: Box<dyn Tokenizer> = match config.strategy.clone() {
+        TokenizerStrategy::Whitespace => Box::new(WhitespaceTokenizer::new(config)?),
+        TokenizerStrategy::Unicode => Box::new(UnicodeTokenizer::new(config)?),
+        TokenizerStrategy::Pattern { regex } => Box::new(PatternTokenizer::new(&regex, config)?),
+        TokenizerStrategy::Sentence => Box::new(SentenceTokenizer::new(config)?),
+        TokenizerStrategy::Grapheme { extended } => Box::new(GraphemeTokenizer::new(config, extended)),
+        TokenizerStrategy::Keyword => Box::new(KeywordTokenizer::new(config)),
+        TokenizerStrategy::EdgeNgram { min_gram, max_gram } => {
+            Box::new(EdgeNgramTokenizer::new(config, min_gram, max_gram))
+        }
+        TokenizerStrategy::PathHierarchy { delimiter } => {
+            Box::new(PathHierarchyTokenizer::new(config, delimiter)?)
+        }
+        TokenizerStrategy::UrlEmail => Box::new(UrlEmailTokenizer::new(config)?),
+        TokenizerStrategy::Ngram { min_gram, max_gram } => Box::new(NgramTokenizer::new(config, min_gram, max_gram)),
+        TokenizerStrategy::CharGroup { split_on_chars } => {
+            Box::new(CharGroupTokenizer::new(config, split_on_chars)?)
+        }
+        TokenizerStrategy::Letter => Box::new(LetterTokenizer::new(config)?),
+        TokenizerStrategy::Lowercase => Box::new(LowercaseTokenizer::new(config)?),
+        TokenizerStrategy::Bytes => Box::new(BytesTokenizer::new(config)),
+        TokenizerStrategy::Cjk {
+            dictionary,
+            user_dictionaries,
+            search_mode,
+            hmm,
+        } => Box::new(
+            CjkTokenizer::new(config, dictionary.as_deref(), &user_dictionaries)?
+                .with_search_mode(search_mode)
+                .with_hmm(hmm),
+        ),
+        TokenizerStrategy::Japanese {
+            dictionary,
+            user_dictionaries,
+        } => Box::new(JapaneseTokenizer::new(config, dictionary, &user_dictionaries)?),
+        TokenizerStrategy::HuggingFace { path, add_special_tokens } => {
+            Box::new(HuggingFaceTokenizer::from_file(path)?.with_special_tokens(add_special_tokens))
+        }
+        TokenizerStrategy::Router { routes, fallback } => {
+            Box::new(RouterTokenizer::new(&routes, fallback.as_deref())?)
+        }
+    };
+    Ok(tokenizer)
+}
+
+pub(crate) fn merge_overlapping_spans(mut spans: Vec<(usize, usize, &str)>) -> Vec<(usize, usize, &str)> {
+    if spans.is_empty() {
+        return spans;
+    }
+
+    spans.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| b.1.cmp(&a.1))
+    });
+
+    let mut merged = Vec::new();
+    let mut current = spans[0];
+
+    for span in spans.into_iter().skip(1) {
+        if span.0 < current.1 {
+            if span.1 > current.1 {
+                current = span;
+            }
+        } else {
+            merged.push(current);
+            current = span;
+        }
+    }
+    merged.push(current);
+
+    merged
+}
+
+// Optimized version that works with indices only
+fn merge_overlapping_spans_optimized(mut spans: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    if spans.is_empty() {
+        return spans;
+    }
+
+    spans.sort_unstable_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| b.1.cmp(&a.1))
+    });
+
+    let mut merged = Vec::with_capacity(spans.len());
+    let mut current = spans[0];
+
+    for span in spans.into_iter().skip(1) {
+        if span.0 < current.1 {
+            if span.1 > current.1 {
+                current.1 = span.1;
+            }
+        } else {
+            merged.push(current);
+            current = span;
+        }
+    }
+    merged.push(current);
+    merged
+}
+
+pub(crate) fn apply_preserve_patterns<'a>(
+    tokens: Vec<Token<'a>>,
+    preserve_patterns: &[Regex],
+    original_text: &'a str,
+    config: &TokenizerConfig,
+) -> Vec<Token<'a>> {
+    apply_preserve_patterns_with_tokenizer(
+        tokens,
+        preserve_patterns,
+        original_text,
+        config,
+        tokenize_simple,
+    )
+}
+
+/// `tokenizer_fn` is called on the text between preserved spans and returns
+/// tokens with offsets relative to that slice.
+pub(crate) fn apply_preserve_patterns_with_tokenizer<'a, F>(
+    tokens: Vec<Token<'a>>,
+    preserve_patterns: &[Regex],
+    original_text: &'a str,
+    config: &TokenizerConfig,
+    tokenizer_fn: F,
+) -> Vec<Token<'a>>
+where
+    F: Fn(&'a str) -> Vec<Token<'a>>,
+{
+    let mut tokens = tokens;
+
+    // Use indices instead of allocating strings upfront
+    let mut preserved_spans: Vec<(usize, usize)> = Vec::with_capacity(32);
+    for pattern in preserve_patterns {
+        for mat in pattern.find_iter(original_text) {
+            preserved_spans.push((mat.start(), mat.end()));
+        }
+    }
+
+    if preserved_spans.is_empty() {
+        // Nothing to preserve, the tokens still need the regular filters
+        post_process_in_place(&mut tokens, config);
+        return tokens;
+    }
+
+    let preserved_spans = merge_overlapping_spans_optimized(preserved_spans);
+
+    // Pre-allocate result vector with estimated capacity
+    let mut result = Vec::with_capacity(tokens.len() + preserved_spans.len());
+    let mut pos = 0;
+
+    // Spans are regex matches and so on char boundaries, but a bad span is
+    // skipped rather than allowed to panic
+    for (start, end) in preserved_spans {
+        let Some(preserved) = original_text.get(start..end) else {
+            continue;
+        };
+        if let Some(before) = original_text.get(pos..start).filter(|before| !before.is_empty()) {
+            let mut before_tokens = tokenizer_fn(before);
+            post_process_in_place(&mut before_tokens, config);
+            result.extend(before_tokens.into_iter().map(|t| t.shift(pos)));
+        }
+        // Preserved text is borrowed straight from the input
+        result.push(Token::new(preserved, start, end));
+        pos = end;
+    }
+
+    if let Some(remaining) = original_text.get(pos..).filter(|remaining| !remaining.is_empty()) {
+        let mut remaining_tokens = tokenizer_fn(remaining);
+        post_process_in_place(&mut remaining_tokens, config);
+        result.extend(remaining_tokens.into_iter().map(|t| t.shift(pos)));
+    }
+
+    result
+}
+
+fn tokenize_simple(text: &str) -> Vec<Token<'_>> {
+    text.split_whitespace()
+        .filter(|s| !s.is_empty())
+        .map(|s| Token::slice(text, s))
+        .collect()
+}
+
+/// Lowercases a token with the rules of `locale`, allocating only if some
+/// character actually changes.
+pub(crate) fn lowercase_token(token: Cow<'_, str>, locale: CaseLocale) -> Cow<'_, str> {
+    if token.chars().all(|c| c.to_lowercase().eq([c])) {
+        token
+    } else {
+        Cow::Owned(locale.lowercase(&token))
+    }
+}
+
+/// Strips ASCII punctuation (except `keep`), allocating only if there is any.
+pub(crate) fn strip_punctuation<'a>(token: Cow<'a, str>, keep: Option<&str>) -> Cow<'a, str> {
+    let is_removed = |c: char| c.is_ascii_punctuation() && !keep.is_some_and(|k| k.contains(c));
+
+    if token.contains(is_removed) {
+        Cow::Owned(token.chars().filter(|c| !is_removed(*c)).collect())
+    } else {
+        token
+    }
+}
+
+/// Per-token equivalent of `post_process`, for lazy token iterators.
+/// Returns `None` when the token is removed entirely.
+pub(crate) fn post_process_token<'a>(token: Token<'a>, config: &TokenizerConfig) -> Option<Token<'a>> {
+    let mut token = token;
+
+    if config.lowercase {
+        token = token.map_text(|text| lowercase_token(text, config.case_locale()));
+    }
+
+    if config.remove_punctuation {
+        token = token.map_text(|t| strip_punctuation(t, None));
+        if token.text.is_empty() {
+            return None;
+        }
+    }
+
+    Some(token)
+}
+
+pub(crate) fn post_process<'a>(tokens: Vec<Token<'a>>, config: &TokenizerConfig) -> Vec<Token<'a>> {
+    post_process_with_preserved(tokens, config, None)
+}
+
+// In-place version to avoid allocation
+fn post_process_in_place(tokens: &mut Vec<Token<'_>>, config: &TokenizerConfig) {
+    if config.lowercase {
+        let locale = config.case_locale();
+        for token in tokens.iter_mut() {
+            token.text = lowercase_token(std::mem::take(&mut token.text), locale);
+        }
+    }
+
+    if config.remove_punctuation {
+        tokens.retain_mut(|token| {
+            token.text = strip_punctuation(std::mem::take(&mut token.text), None);
+            !token.text.is_empty()
+        });
+    }
+}
+
+pub(crate) fn post_process_with_preserved<'a>(
+    mut tokens: Vec<Token<'a>>,
+    config: &TokenizerConfig,
+    preserve_chars: Option<&str>,
+) -> Vec<Token<'a>> {
+    if config.lowercase {
+        let locale = config.case_locale();
+        tokens = tokens.into_iter().map(|t| t.map_text(|text| lowercase_token(text, locale))).collect();
+    }
+
+    if config.remove_punctuation {
+        tokens = tokens
+            .into_iter()
+            .map(|t| t.map_text(|text| strip_punctuation(text, preserve_chars)))
+            .filter(|t| !t.text.is_empty())
+            .collect();
+    }
+
+    tokens
+}
@@ -1,5 +1,7 @@
-use super::{post_process_with_preserved, BaseTokenizerFields, Tokenizer};
+use super::{offset_in, post_process_with_preserved, BaseTokenizerFields, Token, Tokenizer};
 use crate::config::TokenizerConfig;
+use crate::error::Result;
+use std::borrow::Cow;
 
 pub struct PathHierarchyTokenizer {
     base: BaseTokenizerFields,
@@ -7,39 +9,49 @@ pub struct PathHierarchyTokenizer {
 }
 
 impl PathHierarchyTokenizer {
-    pub fn new(config: TokenizerConfig, delimiter: String) -> Self {
-        Self {
-            base: BaseTokenizerFields::new(config),
+    pub fn new(config: TokenizerConfig, delimiter: String) -> Result<Self> {
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
             delimiter,
-        }
+        })
     }
 
-    fn generate_hierarchy(&self, path: &str) -> Vec<String> {
+    /// Offsets are relative to `path`; each token ends where its last part does.
+    fn generate_hierarchy<'a>(&self, path: &'a str) -> Vec<Token<'a>> {
         let mut tokens = Vec::new();
-        let parts: Vec<&str> = path.split(&self.delimiter).collect();
 
         let mut current_path = String::new();
         let starts_with_delimiter = path.starts_with(&self.delimiter);
 
-        for part in parts.iter() {
+        let mut part_start = 0;
+
+        for part in path.split(&self.delimiter) {
+            let part_end = part_start + part.len();
+            part_start = part_end + self.delimiter.len();
+
             if part.is_empty() {
                 continue;
             }
 
-            if !current_path.is_empty() {
-                current_path.push_str(&self.delimiter);
-            } else if starts_with_delimiter {
+            if !current_path.is_empty() || starts_with_delimiter {
                 current_path.push_str(&self.delimiter);
             }
 
             current_path.push_str(part);
-            tokens.push(current_path.clone());
+
+            // Borrow the prefix of the input unless repeated delimiters were collapsed
+            let prefix = &path[..part_end];
+            if prefix == current_path {
+                tokens.push(Token::new(prefix, 0, part_end));
+            } else {
+                tokens.push(Token::new(current_path.clone(), 0, part_end));
+            }
         }
 
         tokens
     }
 
-    fn apply_patterns_to_hierarchy(&self, text: &str) -> Vec<String> {
+    fn apply_patterns_to_hierarchy<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
         if self.base.preserve_patterns().is_empty() {
             return self.generate_hierarchy(text);
         }
@@ -51,9 +63,9 @@ impl PathHierarchyTokenizer {
         let mut preserved_tokens = Vec::new();
         for token in &all_tokens {
             for pattern in self.base.preserve_patterns() {
-                if let Some(mat) = pattern.find(token) {
-                    if mat.as_str() == token {
-                        preserved_tokens.push(token.clone());
+                if let Some(mat) = pattern.find(&token.text) {
+                    if mat.as_str() == token.text {
+                        preserved_tokens.push(token.text.clone());
                         break;
                     }
                 }
@@ -63,6 +75,9 @@ impl PathHierarchyTokenizer {
         // Now build the result, applying lowercase where appropriate
         let mut result = Vec::new();
         for token in all_tokens {
+            let (start, end) = (token.start, token.end);
+            let token = token.text;
+
             // Check if this token should be included
             // Include if: it's a preserved token OR it extends beyond a preserved token
             let should_include;
@@ -75,7 +90,7 @@ impl PathHierarchyTokenizer {
                 // Check if this token extends a preserved token
                 let mut extends_preserved = false;
                 for preserved in &preserved_tokens {
-                    if token.starts_with(preserved) && token.len() > preserved.len() {
+                    if token.starts_with(preserved.as_ref()) && token.len() > preserved.len() {
                         extends_preserved = true;
                         break;
                     }
@@ -87,7 +102,7 @@ impl PathHierarchyTokenizer {
                     // Include if no preserved token is a prefix of this one
                     let mut has_preserved_prefix = false;
                     for preserved in &preserved_tokens {
-                        if preserved.starts_with(&token) && preserved != &token {
+                        if preserved.starts_with(token.as_ref()) && preserved != &token {
                             has_preserved_prefix = true;
                             break;
                         }
@@ -112,10 +127,11 @@ impl PathHierarchyTokenizer {
                             continue;
                         }
 
-                        if i > 0 || (i == 0 && starts_with_delim) {
-                            if !lowercased.is_empty() && !lowercased.ends_with(&self.delimiter) {
-                                lowercased.push_str(&self.delimiter);
-                            }
+                        if (i > 0 || starts_with_delim)
+                            && !lowercased.is_empty()
+                            && !lowercased.ends_with(&self.delimiter)
+                        {
+                            lowercased.push_str(&self.delimiter);
                         }
 
                         // Check if this specific part should be preserved
@@ -130,12 +146,12 @@ impl PathHierarchyTokenizer {
                         if preserve_part {
                             lowercased.push_str(part);
                         } else {
-                            lowercased.push_str(&part.to_lowercase());
+                            lowercased.push_str(&self.base.config.case_locale().lowercase(part));
                         }
                     }
-                    result.push(lowercased);
+                    result.push(Token::new(lowercased, start, end));
                 } else {
-                    result.push(token);
+                    result.push(Token::new(token, start, end));
                 }
             }
         }
@@ -145,18 +161,20 @@ impl PathHierarchyTokenizer {
 }
 
 impl Tokenizer for PathHierarchyTokenizer {
-    fn tokenize(&self, text: &str) -> Vec<String> {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
         let trimmed = text.trim();
         if trimmed.is_empty() {
             return vec![];
         }
 
+        let trim_offset = offset_in(text, trimmed);
+
         if self.base.has_preserve_patterns() {
             let mut tokens = self.apply_patterns_to_hierarchy(trimmed);
 
             // Apply remove_punctuation if needed (but preserve delimiters)
             if self.base.config.remove_punctuation {
-                tokens = tokens.into_iter().map(|token| {
+                tokens = tokens.into_iter().map(|token| token.map_text(|token| {
                     let parts: Vec<&str> = token.split(&self.delimiter).collect();
                     let processed: Vec<String> = parts.iter().map(|part| {
                         if part.is_empty() {
@@ -173,15 +191,23 @@ impl Tokenizer for PathHierarchyTokenizer {
                             }
                         }
                     }).collect();
-                    processed.join(&self.delimiter)
-                }).filter(|s| !s.is_empty() && s != &self.delimiter).collect();
+                    Cow::<str>::Owned(processed.join(&self.delimiter))
+                })).filter(|s| !s.text.is_empty() && s.text != self.delimiter).collect();
             }
 
-            tokens
+            tokens.into_iter().map(|t| t.shift(trim_offset)).collect()
         } else {
             let tokens = self.generate_hierarchy(trimmed);
             post_process_with_preserved(tokens, &self.base.config, Some(&self.delimiter))
+                .into_iter()
+                .map(|t| t.shift(trim_offset))
+                .collect()
         }
     }
 
+    fn stream_boundary(&self, _text: &str) -> Option<usize> {
+        // Every token is a prefix of the whole path
+        None
+    }
+
 }
\ No newline at end of file
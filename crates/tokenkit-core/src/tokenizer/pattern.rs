@@ -0,0 +1,61 @@
+use super::{
+    apply_preserve_patterns, post_process, post_process_token, whitespace_boundary, BaseTokenizerFields, RegexChars, Token,
+    Tokenizer,
+};
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use regex::Regex;
+
+pub struct PatternTokenizer {
+    base: BaseTokenizerFields,
+    pattern: Regex,
+    pattern_chars: RegexChars,
+}
+
+impl PatternTokenizer {
+    pub fn new(regex: &str, config: TokenizerConfig) -> Result<Self> {
+        let pattern = config.regex_limits.compile(regex)?;
+
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+            pattern,
+            pattern_chars: RegexChars::new([regex]),
+        })
+    }
+}
+
+impl Tokenizer for PatternTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens: Vec<Token<'a>> = self
+            .pattern
+            .find_iter(text)
+            .map(|mat| Token::new(mat.as_str(), mat.start(), mat.end()))
+            .collect();
+
+        if self.base.has_preserve_patterns() {
+            apply_preserve_patterns(tokens, self.base.preserve_patterns(), text, &self.base.config)
+        } else {
+            post_process(tokens, &self.base.config)
+        }
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        if self.base.has_preserve_patterns() {
+            return Box::new(self.tokenize_with_offsets(text).into_iter());
+        }
+
+        Box::new(
+            self.pattern
+                .find_iter(text)
+                .filter_map(move |mat| {
+                    post_process_token(Token::new(mat.as_str(), mat.start(), mat.end()), &self.base.config)
+                }),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        // Neither a token nor a preserved match may straddle the cut
+        let boundary = self.base.stream_boundary(text, whitespace_boundary(text));
+        self.pattern_chars.hold_back(text, boundary)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,212 @@
+use super::{from_config, result_tokens, Token, Tokenizer};
+use crate::config::{Route, TokenizerConfig};
+use crate::error::Result;
+use crate::language::{detect_language, parse_language};
+use crate::script::{parse_script, Script};
+use std::collections::HashMap;
+use std::mem;
+use std::sync::{Mutex, PoisonError};
+use unicode_script::UnicodeScript;
+
+/// Splits the input into runs of one script each and tokenizes every run
+/// with the tokenizer of the first route naming that script, or with the
+/// fallback. Adjacent runs going to the same route are tokenized together,
+/// so a route for Han, Hiragana and Katakana sees whole Japanese sentences.
+///
+/// Characters of any script (digits, spaces, punctuation) and combining
+/// marks stay in the run before them; at the start of the input they join
+/// the first run. Runs also end after each line break. Token offsets point
+/// into the whole input.
+///
+/// Routes with languages apply to the runs whose own text is detected as
+/// one of them, so an English document with a German paragraph can send
+/// each to its own tokenizer. When streaming, a run split between chunks is
+/// detected in each part on its own.
+pub struct RouterTokenizer {
+    routes: Vec<RouteTokenizer>,
+    fallback: Box<dyn Tokenizer>,
+    // Languages detected by run text in the last call, since a stream asks
+    // for a boundary and then tokenizes the same runs
+    languages: Mutex<HashMap<String, Option<&'static str>>>,
+}
+
+struct RouteTokenizer {
+    // Empty for any script or language
+    scripts: Vec<Script>,
+    languages: Vec<&'static str>,
+    tokenizer: Box<dyn Tokenizer>,
+}
+
+// A run of the input in one script, `None` when it has no characters of a
+// particular script
+struct ScriptRun {
+    start: usize,
+    end: usize,
+    script: Option<Script>,
+}
+
+// A run of the input and the index of its tokenizer, where `routes.len()`
+// stands for the fallback
+struct Run {
+    start: usize,
+    end: usize,
+    route: usize,
+}
+
+impl RouterTokenizer {
+    /// Builds every route's tokenizer; script names must already be valid,
+    /// as `validate_config` checks.
+    pub fn new(routes: &[Route], fallback: Option<&TokenizerConfig>) -> Result<Self> {
+        let routes = routes
+            .iter()
+            .map(|route| {
+                Ok(RouteTokenizer {
+                    scripts: route.scripts.iter().filter_map(|name| parse_script(name)).collect(),
+                    languages: route.languages.iter().filter_map(|name| parse_language(name)).collect(),
+                    tokenizer: from_config(route.tokenizer.clone())?,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            routes,
+            fallback: from_config(fallback.cloned().unwrap_or_default())?,
+            languages: Mutex::default(),
+        })
+    }
+
+    fn tokenizer(&self, route: usize) -> &dyn Tokenizer {
+        self.routes.get(route).map_or(&*self.fallback, |route| &*route.tokenizer)
+    }
+
+    // Whether a route for `script` needs the language of its runs
+    fn detects_language(&self, script: Option<Script>) -> bool {
+        self.routes
+            .iter()
+            .any(|route| !route.languages.is_empty() && matches_script(&route.scripts, script))
+    }
+
+    fn route_of(&self, script: Option<Script>, language: Option<&str>) -> usize {
+        self.routes
+            .iter()
+            .position(|route| {
+                matches_script(&route.scripts, script)
+                    && (route.languages.is_empty() || language.is_some_and(|code| route.languages.contains(&code)))
+            })
+            .unwrap_or(self.routes.len())
+    }
+
+    fn runs(&self, text: &str) -> Vec<Run> {
+        let mut previous = mem::take(&mut *self.languages.lock().unwrap_or_else(PoisonError::into_inner));
+        let mut languages = HashMap::new();
+
+        let mut runs: Vec<Run> = Vec::new();
+        for ScriptRun { start, end, script } in script_runs(text) {
+            let language = if self.detects_language(script) {
+                let run = &text[start..end];
+                let language = previous
+                    .remove(run)
+                    .unwrap_or_else(|| detect_language(run).map(|language| language.code));
+                languages.insert(run.to_string(), language);
+                language
+            } else {
+                None
+            };
+
+            let route = self.route_of(script, language);
+            match runs.last_mut() {
+                Some(run) if run.route == route => run.end = end,
+                _ => runs.push(Run { start, end, route }),
+            }
+        }
+
+        *self.languages.lock().unwrap_or_else(PoisonError::into_inner) = languages;
+        runs
+    }
+}
+
+// Empty `scripts` match any script, including none
+fn matches_script(scripts: &[Script], script: Option<Script>) -> bool {
+    scripts.is_empty() || script.is_some_and(|script| scripts.contains(&script))
+}
+
+// The script of `c`, `None` for characters that go with the run they are in
+fn script_of(c: char) -> Option<Script> {
+    let script = c.script();
+    (script != Script::Common && script != Script::Inherited).then_some(script)
+}
+
+fn script_runs(text: &str) -> Vec<ScriptRun> {
+    let mut runs: Vec<ScriptRun> = Vec::new();
+    let mut after_line_break = false;
+    for (i, c) in text.char_indices() {
+        let script = script_of(c);
+        match runs.last_mut() {
+            None => runs.push(ScriptRun { start: 0, end: 0, script }),
+            Some(_) if script.is_none() => {}
+            Some(run) if run.script.is_none() => run.script = script,
+            Some(run) if run.script == script && !after_line_break => {}
+            Some(run) => {
+                run.end = i;
+                runs.push(ScriptRun { start: i, end: i, script });
+            }
+        }
+        if c == '\n' {
+            after_line_break = true;
+        } else if script.is_some() {
+            after_line_break = false;
+        }
+    }
+
+    if let Some(run) = runs.last_mut() {
+        run.end = text.len();
+    }
+    runs
+}
+
+impl Tokenizer for RouterTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.runs(text)
+            .into_iter()
+            .flat_map(|run| {
+                self.tokenizer(run.route)
+                    .tokenize_with_offsets(&text[run.start..run.end])
+                    .into_iter()
+                    .map(move |token| token.shift(run.start))
+            })
+            .collect()
+    }
+
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        let mut tokens = Vec::new();
+        for run in self.runs(text) {
+            let run_tokens = self.tokenizer(run.route).try_tokenize_with_offsets(&text[run.start..run.end])?;
+            tokens.extend(run_tokens.into_iter().map(|token| token.shift(run.start)));
+        }
+        Ok(tokens)
+    }
+
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        result_tokens(self.try_tokenize_with_offsets(text))
+    }
+
+    /// A cut inside the last run, where its tokenizer allows one, or else at
+    /// the start of that run. Either way the text after the cut starts with
+    /// a character of some script, so it is routed as it would be uncut.
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        let last = self.runs(text).pop()?;
+        let tokenizer = self.tokenizer(last.route);
+
+        let mut run = &text[last.start..last.end];
+        while let Some(cut) = tokenizer.stream_boundary(run).filter(|&cut| cut > 0) {
+            if run[cut..].starts_with(|c| script_of(c).is_some()) {
+                return Some(last.start + cut);
+            }
+            // Try again before the character the cut came after
+            let before = run[..cut].chars().next_back().map_or(0, char::len_utf8);
+            run = &run[..cut - before];
+        }
+
+        Some(last.start).filter(|&start| start > 0)
+    }
+}
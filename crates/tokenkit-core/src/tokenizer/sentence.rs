@@ -1,5 +1,7 @@
-use super::{post_process, BaseTokenizerFields, Tokenizer};
+use super::{post_process, strip_punctuation, BaseTokenizerFields, Token, Tokenizer};
 use crate::config::TokenizerConfig;
+use crate::error::Result;
+use std::borrow::Cow;
 use unicode_segmentation::UnicodeSegmentation;
 
 pub struct SentenceTokenizer {
@@ -7,68 +9,69 @@ pub struct SentenceTokenizer {
 }
 
 impl SentenceTokenizer {
-    pub fn new(config: TokenizerConfig) -> Self {
-        Self {
-            base: BaseTokenizerFields::new(config),
-        }
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+        })
     }
 }
 
 impl SentenceTokenizer {
-    fn apply_patterns_to_sentence(&self, sentence: &str) -> String {
+    fn apply_patterns_to_sentence<'a>(&self, sentence: Cow<'a, str>) -> Cow<'a, str> {
         if self.base.preserve_patterns().is_empty() || !self.base.config.lowercase {
-            return sentence.to_string();
+            return sentence;
         }
 
         // Find all matches in the sentence
-        let mut preserved_spans: Vec<(usize, usize, String)> = Vec::new();
+        let mut preserved_spans: Vec<(usize, usize, &str)> = Vec::new();
         for pattern in self.base.preserve_patterns() {
-            for mat in pattern.find_iter(sentence) {
-                preserved_spans.push((mat.start(), mat.end(), mat.as_str().to_string()));
+            for mat in pattern.find_iter(&sentence) {
+                preserved_spans.push((mat.start(), mat.end(), mat.as_str()));
             }
         }
 
         if preserved_spans.is_empty() {
-            return sentence.to_string();
+            return sentence;
         }
 
         // Sort and merge overlapping spans
         preserved_spans.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
 
+        let locale = self.base.config.case_locale();
         let mut result = String::new();
         let mut pos = 0;
 
         for (start, end, preserved) in preserved_spans {
             if start > pos {
                 // Lowercase the text before the preserved pattern
-                result.push_str(&sentence[pos..start].to_lowercase());
+                result.push_str(&locale.lowercase(&sentence[pos..start]));
             }
             // Keep the preserved pattern as-is
-            result.push_str(&preserved);
+            result.push_str(preserved);
             pos = end.max(pos); // Handle overlaps
         }
 
         if pos < sentence.len() {
             // Lowercase the remaining text
-            result.push_str(&sentence[pos..].to_lowercase());
+            result.push_str(&locale.lowercase(&sentence[pos..]));
         }
 
-        result
+        Cow::Owned(result)
     }
 }
 
 impl Tokenizer for SentenceTokenizer {
-    fn tokenize(&self, text: &str) -> Vec<String> {
-        let mut sentences: Vec<String> = text
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let mut sentences: Vec<Token<'a>> = text
             .unicode_sentences()
-            .map(|s| s.to_string())
+            .map(|s| Token::slice(text, s))
             .collect();
 
         // Apply preserve patterns to each sentence
         if self.base.has_preserve_patterns() && self.base.config.lowercase {
             sentences = sentences
                 .into_iter()
-                .map(|sentence| self.apply_patterns_to_sentence(&sentence))
+                .map(|sentence| sentence.map_text(|s| self.apply_patterns_to_sentence(s)))
                 .collect();
 
             // Don't call post_process since we already handled lowercasing with patterns
@@ -76,8 +79,8 @@ impl Tokenizer for SentenceTokenizer {
             if self.base.config.remove_punctuation {
                 sentences = sentences
                     .into_iter()
-                    .map(|s| s.chars().filter(|c| !c.is_ascii_punctuation()).collect())
-                    .filter(|s: &String| !s.is_empty())
+                    .map(|s| s.map_text(|text| strip_punctuation(text, None)))
+                    .filter(|s| !s.text.is_empty())
                     .collect();
             }
             sentences
@@ -86,4 +89,10 @@ impl Tokenizer for SentenceTokenizer {
         }
     }
 
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        // The last sentence may continue in the next chunk
+        let boundary = text.split_sentence_bound_indices().last().map(|(i, _)| i);
+        self.base.stream_boundary(text, boundary)
+    }
+
 }
\ No newline at end of file
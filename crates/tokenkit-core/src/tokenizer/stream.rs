@@ -0,0 +1,323 @@
+use super::limited::limit_exceeded;
+use super::{Token, Tokenizer};
+use crate::config::{InvalidUtf8Policy, Limits, OverflowPolicy};
+use crate::encoding;
+use crate::error::{Result, TokenizerError};
+
+/// Incremental tokenizer for input that arrives in chunks (e.g. read from an IO).
+///
+/// Chunks are raw bytes, so a multi-byte character split across two reads is
+/// held back until it is complete. Decoded text is buffered up to the
+/// tokenizer's `stream_boundary`, so a token straddling two chunks is emitted
+/// once, whole, when the boundary moves past it.
+///
+/// Token offsets count bytes of the decoded stream from its very start. They
+/// match the input bytes unless invalid UTF-8 was replaced or skipped.
+///
+/// The `try_` methods apply the `invalid_utf8` policy set with
+/// [`TokenStream::with_invalid_utf8`]; the others can't report an error, so
+/// they always replace invalid UTF-8.
+///
+/// Limits set with [`TokenStream::with_limits`] count decoded bytes and
+/// tokens across the whole stream, or input bytes after
+/// [`TokenStream::with_byte_input`]. The `try_` methods apply their `overflow`
+/// policy, and the others always truncate. Once a limit is hit, the rest of
+/// the input is ignored until `finish`.
+pub struct TokenStream {
+    tokenizer: Box<dyn Tokenizer>,
+    buffer: String,
+    pending: Vec<u8>,
+    // Decoded bytes already drained from `buffer`
+    consumed: usize,
+    // Input bytes those came from, for max_input_bytes
+    consumed_input: usize,
+    // Tokens returned so far, for max_tokens
+    emitted: usize,
+    // Whether a limit was hit, so later input is ignored
+    done: bool,
+    invalid_utf8: InvalidUtf8Policy,
+    limits: Limits,
+    // Whether each input byte is read as one character
+    byte_input: bool,
+}
+
+impl TokenStream {
+    pub fn new(tokenizer: Box<dyn Tokenizer>) -> Self {
+        Self {
+            tokenizer,
+            buffer: String::new(),
+            pending: Vec::new(),
+            consumed: 0,
+            consumed_input: 0,
+            emitted: 0,
+            done: false,
+            invalid_utf8: InvalidUtf8Policy::default(),
+            limits: Limits::default(),
+            byte_input: false,
+        }
+    }
+
+    /// Sets what the `try_` methods do with invalid UTF-8, usually the
+    /// tokenizer config's `invalid_utf8`.
+    pub fn with_invalid_utf8(mut self, policy: InvalidUtf8Policy) -> Self {
+        self.invalid_utf8 = policy;
+        self
+    }
+
+    /// Applies `max_input_bytes` and `max_tokens` to the whole stream rather
+    /// than each buffered segment, usually with the tokenizer config's
+    /// `limits`.
+    pub fn with_limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Reads every input byte as one character, as `encoding::decode_bytes`
+    /// does for the bytes strategy, instead of as UTF-8. Offsets still count
+    /// bytes of the decoded text, but `max_input_bytes` counts input bytes.
+    pub fn with_byte_input(mut self) -> Self {
+        self.byte_input = true;
+        self
+    }
+
+    /// Feeds the next chunk and returns every token that can no longer change.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        into_texts(self.push_tokens(chunk))
+    }
+
+    /// Flushes whatever is still buffered once the input is exhausted. The
+    /// stream can then take another input, whose offsets start from 0 again.
+    pub fn finish(&mut self) -> Vec<String> {
+        into_texts(self.finish_tokens())
+    }
+
+    /// Like `push`, keeping each token's offsets in the whole stream.
+    pub fn push_tokens(&mut self, chunk: &[u8]) -> Vec<Token<'static>> {
+        // Replacing and truncating never fail
+        self.push_decoded(chunk, false).unwrap_or_default()
+    }
+
+    /// Like `finish`, keeping each token's offsets in the whole stream.
+    pub fn finish_tokens(&mut self) -> Vec<Token<'static>> {
+        self.finish_decoded(false).unwrap_or_default()
+    }
+
+    /// Like `push`, failing with `TokenizerError::InvalidUtf8` when the
+    /// policy is `Raise`, and with `TokenizerError::LimitExceeded` when the
+    /// stream goes over a limit and `overflow` is `Error`. The position
+    /// counts bytes from the start of the stream.
+    pub fn try_push(&mut self, chunk: &[u8]) -> Result<Vec<String>> {
+        self.try_push_tokens(chunk).map(into_texts)
+    }
+
+    pub fn try_finish(&mut self) -> Result<Vec<String>> {
+        self.try_finish_tokens().map(into_texts)
+    }
+
+    pub fn try_push_tokens(&mut self, chunk: &[u8]) -> Result<Vec<Token<'static>>> {
+        self.push_decoded(chunk, true)
+    }
+
+    pub fn try_finish_tokens(&mut self) -> Result<Vec<Token<'static>>> {
+        self.finish_decoded(true)
+    }
+
+    // `fallible` applies the configured invalid_utf8 and overflow policies
+    // and pulls tokens with `try_tokens`; otherwise invalid UTF-8 is
+    // replaced and limits truncate
+    fn push_decoded(&mut self, chunk: &[u8], fallible: bool) -> Result<Vec<Token<'static>>> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+        if self.byte_input {
+            self.buffer.push_str(&encoding::decode_bytes(chunk));
+        } else {
+            self.pending.extend_from_slice(chunk);
+
+            let complete = complete_utf8_len(&self.pending);
+            if complete > 0 {
+                let bytes: Vec<u8> = self.pending.drain(..complete).collect();
+                self.decode(&bytes, fallible)?;
+            }
+        }
+
+        let end = if self.limit_input(fallible)? {
+            self.buffer.len()
+        } else {
+            match self.tokenizer.stream_boundary(&self.buffer) {
+                Some(boundary) if boundary > 0 => boundary,
+                _ => return Ok(Vec::new()),
+            }
+        };
+        self.drain_tokens(end, fallible)
+    }
+
+    fn finish_decoded(&mut self, fallible: bool) -> Result<Vec<Token<'static>>> {
+        let tokens = self.flush(fallible);
+
+        // Ready for the next input
+        self.buffer.clear();
+        self.pending.clear();
+        self.consumed = 0;
+        self.consumed_input = 0;
+        self.emitted = 0;
+        self.done = false;
+        tokens
+    }
+
+    fn flush(&mut self, fallible: bool) -> Result<Vec<Token<'static>>> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+        if !self.pending.is_empty() {
+            // A truncated character at end of input can never complete
+            let bytes = std::mem::take(&mut self.pending);
+            self.decode(&bytes, fallible)?;
+        }
+
+        self.limit_input(fallible)?;
+        self.drain_tokens(self.buffer.len(), fallible)
+    }
+
+    // Applies max_input_bytes to everything decoded so far. When it truncates,
+    // the buffer is cut where a token can end and is all that's left of the
+    // input; returns whether that happened
+    fn limit_input(&mut self, fallible: bool) -> Result<bool> {
+        let Some(max) = self.limits.max_input_bytes else {
+            return Ok(false);
+        };
+        if self.consumed_input + self.input_len(&self.buffer) <= max {
+            return Ok(false);
+        }
+
+        self.done = true;
+        self.pending.clear();
+        if fallible && self.limits.overflow == OverflowPolicy::Error {
+            self.buffer.clear();
+            return Err(limit_exceeded("max_input_bytes", max));
+        }
+
+        let allowed = max - self.consumed_input;
+        let end = if self.byte_input {
+            self.buffer.char_indices().nth(allowed).map_or(self.buffer.len(), |(i, _)| i)
+        } else {
+            let mut end = allowed;
+            while !self.buffer.is_char_boundary(end) {
+                end -= 1;
+            }
+            end
+        };
+        // As for a whole input, the last token isn't cut in half unless it
+        // is the only one
+        let end = match self.tokenizer.stream_boundary(&self.buffer[..end]) {
+            Some(boundary) if boundary > 0 => boundary,
+            _ if self.consumed > 0 => 0,
+            _ => end,
+        };
+        self.buffer.truncate(end);
+        Ok(true)
+    }
+
+    // Tokenizes the first `end` bytes of the buffer and drains them, counting
+    // tokens towards max_tokens
+    fn drain_tokens(&mut self, end: usize, fallible: bool) -> Result<Vec<Token<'static>>> {
+        let segment = &self.buffer[..end];
+        let items: Box<dyn Iterator<Item = Result<Token<'_>>> + '_> = if fallible {
+            self.tokenizer.try_tokens(segment)
+        } else {
+            Box::new(self.tokenizer.tokens(segment).map(Ok))
+        };
+
+        let mut tokens = Vec::new();
+        let mut error = None;
+        for item in items {
+            let token = match item {
+                Ok(token) => token,
+                Err(e) => {
+                    error = Some(e);
+                    break;
+                }
+            };
+            if let Some(max) = self.limits.max_tokens {
+                if self.emitted == max {
+                    // There is at least one token too many
+                    if fallible && self.limits.overflow == OverflowPolicy::Error {
+                        error = Some(limit_exceeded("max_tokens", max));
+                    }
+                    self.done = true;
+                    break;
+                }
+            }
+            self.emitted += 1;
+            // Tokens borrow from the buffer, which is about to be drained
+            tokens.push(token.into_owned().shift(self.consumed));
+        }
+
+        if error.is_some() || self.done {
+            self.done = true;
+            self.buffer.clear();
+            self.pending.clear();
+        } else {
+            self.consumed_input += self.input_len(&self.buffer[..end]);
+            self.buffer.drain(..end);
+            self.consumed += end;
+        }
+        match error {
+            Some(error) => Err(error),
+            None => Ok(tokens),
+        }
+    }
+
+    // Input bytes `text` of the buffer was decoded from
+    fn input_len(&self, text: &str) -> usize {
+        if self.byte_input {
+            text.chars().count()
+        } else {
+            text.len()
+        }
+    }
+
+    // Appends `bytes` to the buffer, reporting an error's position in the
+    // whole stream
+    fn decode(&mut self, bytes: &[u8], fallible: bool) -> Result<()> {
+        let policy = if fallible { self.invalid_utf8 } else { InvalidUtf8Policy::Replace };
+        match encoding::decode(bytes, policy) {
+            Ok(text) => {
+                self.buffer.push_str(&text);
+                Ok(())
+            }
+            Err(TokenizerError::InvalidUtf8 { position }) => Err(TokenizerError::InvalidUtf8 {
+                position: self.consumed + self.buffer.len() + position,
+            }),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+fn into_texts(tokens: Vec<Token<'static>>) -> Vec<String> {
+    tokens.into_iter().map(|token| token.text.into_owned()).collect()
+}
+
+/// Length of `bytes` without a trailing, still incomplete UTF-8 sequence.
+fn complete_utf8_len(bytes: &[u8]) -> usize {
+    let len = bytes.len();
+
+    // A sequence is at most 4 bytes, so only the last 3 can be an unfinished one
+    for back in 1..=len.min(3) {
+        let byte = bytes[len - back];
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            // Continuation byte, keep looking for the lead byte
+            continue;
+        }
+
+        let needed = match byte {
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => 1,
+        };
+        return if needed > back { len - back } else { len };
+    }
+
+    len
+}
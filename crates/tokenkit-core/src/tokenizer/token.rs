@@ -0,0 +1,81 @@
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// A token plus the byte range of the input text it was produced from.
+///
+/// `text` may differ from `input[start..end]` once filters such as lowercasing
+/// or punctuation removal have been applied; the offsets always refer to the
+/// original input.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub text: Cow<'a, str>,
+    pub start: usize,
+    pub end: usize,
+    /// What a morphological tokenizer (`japanese`) knows about the word;
+    /// `None` for every other tokenizer.
+    #[serde(skip_serializing_if = "Option::is_none", flatten)]
+    pub attributes: Option<Box<TokenAttributes>>,
+}
+
+/// Dictionary details of a token's word.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenAttributes {
+    /// Part-of-speech levels joined with `-`, such as `名詞-固有名詞-地域-一般`.
+    pub part_of_speech: String,
+    /// Dictionary form of an inflected word, such as `食べる` for `食べ`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_form: Option<String>,
+    /// Reading in katakana.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reading: Option<String>,
+}
+
+impl<'a> Token<'a> {
+    pub fn new(text: impl Into<Cow<'a, str>>, start: usize, end: usize) -> Self {
+        Self {
+            text: text.into(),
+            start,
+            end,
+            attributes: None,
+        }
+    }
+
+    pub fn with_attributes(mut self, attributes: TokenAttributes) -> Self {
+        self.attributes = Some(Box::new(attributes));
+        self
+    }
+
+    /// Token borrowing `slice`, which must be a subslice of `text`.
+    pub(crate) fn slice(text: &'a str, slice: &'a str) -> Self {
+        let start = offset_in(text, slice);
+        Self::new(slice, start, start + slice.len())
+    }
+
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            text: Cow::Owned(self.text.into_owned()),
+            start: self.start,
+            end: self.end,
+            attributes: self.attributes,
+        }
+    }
+
+    /// Moves the offsets by `by` bytes, for tokens of a slice that starts there.
+    pub(crate) fn shift(mut self, by: usize) -> Self {
+        self.start += by;
+        self.end += by;
+        self
+    }
+
+    pub(crate) fn map_text(mut self, f: impl FnOnce(Cow<'a, str>) -> Cow<'a, str>) -> Self {
+        self.text = f(self.text);
+        self
+    }
+}
+
+/// Byte offset of `slice` within `text`; `slice` must point into `text`.
+pub(crate) fn offset_in(text: &str, slice: &str) -> usize {
+    let offset = (slice.as_ptr() as usize).wrapping_sub(text.as_ptr() as usize);
+    debug_assert!(offset + slice.len() <= text.len(), "slice is not part of text");
+    offset
+}
@@ -0,0 +1,81 @@
+use super::{
+    apply_preserve_patterns, post_process, post_process_token, whitespace_boundary, BaseTokenizerFields, RegexChars, Token,
+    Tokenizer,
+};
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+use crate::script::dictionary_runs;
+use crate::word_break::WordBreaker;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Unicode word boundaries (UAX #29). Runs of scripts written without
+/// spaces, which UAX #29 can only split into single characters, are broken
+/// into words by a [`WordBreaker`] instead.
+pub struct UnicodeTokenizer {
+    base: BaseTokenizerFields,
+    word_breaker: WordBreaker,
+}
+
+impl UnicodeTokenizer {
+    /// The config's `word_break_dictionaries` add words to the bundled
+    /// Thai, Lao, Khmer and Myanmar word lists.
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        let word_breaker = WordBreaker::new(&config.word_break_dictionaries)?;
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+            word_breaker,
+        })
+    }
+
+    fn words<'s, 'a: 's>(&'s self, text: &'a str) -> impl Iterator<Item = &'a str> + 's {
+        dictionary_runs(text).flat_map(move |(run, needs_dictionary)| {
+            let broken = needs_dictionary.then(|| self.word_breaker.words(run));
+            let words = (!needs_dictionary).then(|| run.unicode_words());
+            words.into_iter().flatten().chain(broken.into_iter().flatten())
+        })
+    }
+}
+
+/// The default configuration, which has no patterns to compile.
+impl Default for UnicodeTokenizer {
+    fn default() -> Self {
+        Self {
+            base: BaseTokenizerFields {
+                config: TokenizerConfig::default(),
+                preserve_patterns: Vec::new(),
+                preserve_chars: RegexChars::new([]),
+            },
+            word_breaker: WordBreaker::default(),
+        }
+    }
+}
+
+impl Tokenizer for UnicodeTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens: Vec<Token<'a>> = self
+            .words(text)
+            .map(|s| Token::slice(text, s))
+            .collect();
+
+        if self.base.has_preserve_patterns() {
+            return apply_preserve_patterns(tokens, self.base.preserve_patterns(), text, &self.base.config);
+        }
+
+        post_process(tokens, &self.base.config)
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        if self.base.has_preserve_patterns() {
+            return Box::new(self.tokenize_with_offsets(text).into_iter());
+        }
+
+        Box::new(
+            self.words(text)
+                .filter_map(move |s| post_process_token(Token::slice(text, s), &self.base.config)),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, whitespace_boundary(text))
+    }
+}
@@ -1,5 +1,6 @@
-use super::{merge_overlapping_spans, post_process, Tokenizer};
+use super::{create_preserve_patterns, lowercase_token, merge_overlapping_spans, post_process, Token, Tokenizer};
 use crate::config::TokenizerConfig;
+use crate::error::Result;
 use linkify::{LinkFinder, LinkKind};
 use regex::Regex;
 use unicode_segmentation::UnicodeSegmentation;
@@ -10,20 +11,16 @@ pub struct UrlEmailTokenizer {
 }
 
 impl UrlEmailTokenizer {
-    pub fn new(config: TokenizerConfig) -> Self {
-        let preserve_patterns = config
-            .preserve_patterns
-            .iter()
-            .filter_map(|p| Regex::new(p).ok())
-            .collect();
-
-        Self {
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        let preserve_patterns = create_preserve_patterns(&config)?;
+
+        Ok(Self {
             config,
             preserve_patterns,
-        }
+        })
     }
 
-    fn extract_url_email_spans(&self, text: &str) -> Vec<(usize, usize, String)> {
+    fn extract_url_email_spans<'a>(&self, text: &'a str) -> Vec<(usize, usize, &'a str)> {
         let finder = LinkFinder::new();
         let mut spans = Vec::new();
 
@@ -31,7 +28,7 @@ impl UrlEmailTokenizer {
             match link.kind() {
                 LinkKind::Url | LinkKind::Email => {
                     let (start, end) = (link.start(), link.end());
-                    spans.push((start, end, link.as_str().to_string()));
+                    spans.push((start, end, link.as_str()));
                 }
                 _ => {}
             }
@@ -42,13 +39,13 @@ impl UrlEmailTokenizer {
 }
 
 impl Tokenizer for UrlEmailTokenizer {
-    fn tokenize(&self, text: &str) -> Vec<String> {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
         let mut spans = self.extract_url_email_spans(text);
 
         // Add preserve_pattern matches to spans
         for pattern in &self.preserve_patterns {
             for mat in pattern.find_iter(text) {
-                spans.push((mat.start(), mat.end(), mat.as_str().to_string()));
+                spans.push((mat.start(), mat.end(), mat.as_str()));
             }
         }
 
@@ -60,9 +57,9 @@ impl Tokenizer for UrlEmailTokenizer {
         };
 
         if spans.is_empty() {
-            let tokens: Vec<String> = text
+            let tokens: Vec<Token<'a>> = text
                 .unicode_words()
-                .map(|s| s.to_string())
+                .map(|s| Token::slice(text, s))
                 .collect();
             return post_process(tokens, &self.config);
         }
@@ -71,11 +68,10 @@ impl Tokenizer for UrlEmailTokenizer {
         let mut pos = 0;
 
         for (start, end, url_or_email) in spans {
-            if start > pos {
-                let before = &text[pos..start];
-                let before_tokens: Vec<String> = before
+            if let Some(before) = text.get(pos..start).filter(|before| !before.is_empty()) {
+                let before_tokens: Vec<Token<'a>> = before
                     .unicode_words()
-                    .map(|s| s.to_string())
+                    .map(|s| Token::slice(text, s))
                     .collect();
                 let before_tokens = post_process(before_tokens, &self.config);
                 result.extend(before_tokens);
@@ -83,20 +79,20 @@ impl Tokenizer for UrlEmailTokenizer {
 
             // Don't lowercase preserved patterns, but do lowercase URLs/emails if config says so
             // unless they are from preserve_patterns
-            let preserved = if self.config.lowercase && !self.preserve_patterns.iter().any(|p| p.is_match(&url_or_email)) {
-                url_or_email.to_lowercase()
+            let preserved = Token::new(url_or_email, start, end);
+            let preserved = if self.config.lowercase && !self.preserve_patterns.iter().any(|p| p.is_match(url_or_email)) {
+                preserved.map_text(|text| lowercase_token(text, self.config.case_locale()))
             } else {
-                url_or_email
+                preserved
             };
             result.push(preserved);
             pos = end;
         }
 
-        if pos < text.len() {
-            let remaining = &text[pos..];
-            let remaining_tokens: Vec<String> = remaining
+        if let Some(remaining) = text.get(pos..).filter(|remaining| !remaining.is_empty()) {
+            let remaining_tokens: Vec<Token<'a>> = remaining
                 .unicode_words()
-                .map(|s| s.to_string())
+                .map(|s| Token::slice(text, s))
                 .collect();
             let remaining_tokens = post_process(remaining_tokens, &self.config);
             result.extend(remaining_tokens);
@@ -0,0 +1,48 @@
+use super::{
+    apply_preserve_patterns, post_process, post_process_token, whitespace_boundary, BaseTokenizerFields, Token, Tokenizer,
+};
+use crate::config::TokenizerConfig;
+use crate::error::Result;
+
+pub struct WhitespaceTokenizer {
+    base: BaseTokenizerFields,
+}
+
+impl WhitespaceTokenizer {
+    pub fn new(config: TokenizerConfig) -> Result<Self> {
+        Ok(Self {
+            base: BaseTokenizerFields::new(config)?,
+        })
+    }
+}
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let tokens: Vec<Token<'a>> = text
+            .split_whitespace()
+            .filter(|s| !s.is_empty())
+            .map(|s| Token::slice(text, s))
+            .collect();
+
+        if self.base.has_preserve_patterns() {
+            apply_preserve_patterns(tokens, self.base.preserve_patterns(), text, &self.base.config)
+        } else {
+            post_process(tokens, &self.base.config)
+        }
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        if self.base.has_preserve_patterns() {
+            return Box::new(self.tokenize_with_offsets(text).into_iter());
+        }
+
+        Box::new(
+            text.split_whitespace()
+                .filter_map(move |s| post_process_token(Token::slice(text, s), &self.base.config)),
+        )
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.base.stream_boundary(text, whitespace_boundary(text))
+    }
+}
@@ -0,0 +1,518 @@
+//! Whole-config validation that reports every problem at once.
+//!
+//! Problems are reported against the flat option names the Ruby gem and the
+//! CLI use, such as `min_gram` or `regex_size_limit`, with an index for list
+//! entries: `preserve_patterns[2]`.
+
+use crate::case::is_valid_locale;
+use crate::config::{TokenizerConfig, TokenizerStrategy};
+use crate::error::{Result, TokenizerError};
+use crate::language::parse_language;
+use crate::script::parse_script;
+use std::fmt;
+
+/// Every strategy name, as written in configs.
+pub const STRATEGIES: &[&str] = &[
+    "whitespace",
+    "unicode",
+    "pattern",
+    "sentence",
+    "grapheme",
+    "keyword",
+    "edge_ngram",
+    "ngram",
+    "path_hierarchy",
+    "url_email",
+    "char_group",
+    "letter",
+    "lowercase",
+    "bytes",
+    "cjk",
+    "japanese",
+    "huggingface",
+    "router",
+];
+
+/// Options every strategy accepts.
+pub const GENERAL_OPTIONS: &[&str] = &[
+    "strategy",
+    "lowercase",
+    "locale",
+    "remove_punctuation",
+    "preserve_patterns",
+    "max_input_bytes",
+    "max_tokens",
+    "max_token_length",
+    "overflow",
+    "long_tokens",
+    "regex_size_limit",
+    "regex_dfa_size_limit",
+    "max_preserve_patterns",
+    "invalid_utf8",
+    "char_filters",
+    "filters",
+];
+
+/// Every token filter type, as written in configs.
+pub const FILTERS: &[&str] = &[
+    "cjk_bigram",
+    "arabic_normalization",
+    "persian_normalization",
+    "hebrew_normalization",
+    "arabic_stem",
+    "indic_normalization",
+    "cjk_width",
+];
+
+/// Every char filter type, as written in configs.
+pub const CHAR_FILTERS: &[&str] = &["cjk_width"];
+
+/// The options of the filter `filter`, besides its `type`.
+pub fn filter_options(filter: &str) -> &'static [&'static str] {
+    match filter {
+        "cjk_bigram" => &["output_unigrams"],
+        _ => &[],
+    }
+}
+
+/// The options only `strategy` uses, empty for strategies without any.
+pub fn strategy_options(strategy: &str) -> &'static [&'static str] {
+    match strategy {
+        "unicode" => &["word_break_dictionaries"],
+        "pattern" => &["regex"],
+        "grapheme" => &["extended"],
+        "edge_ngram" | "ngram" => &["min_gram", "max_gram"],
+        "path_hierarchy" => &["delimiter"],
+        "char_group" => &["split_on_chars"],
+        "cjk" => &["dictionary", "user_dictionaries", "search_mode", "hmm"],
+        "japanese" => &["dictionary", "user_dictionaries"],
+        "huggingface" => &["path", "add_special_tokens"],
+        "router" => &["routes", "fallback"],
+        _ => &[],
+    }
+}
+
+/// One problem with a config, at the option it concerns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: String,
+    pub message: String,
+    /// A likely intended value for misspelled option or strategy names.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// A problem found while validating, before it is turned into a
+/// [`Diagnostic`] or reported as the error itself.
+#[derive(Debug)]
+pub struct Problem {
+    pub path: String,
+    pub error: TokenizerError,
+}
+
+impl Problem {
+    pub fn new(path: impl Into<String>, error: TokenizerError) -> Self {
+        Self {
+            path: path.into(),
+            error,
+        }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        let suggestion = match &self.error {
+            TokenizerError::UnknownOption { suggestion, .. } => suggestion.clone(),
+            TokenizerError::UnknownStrategy(strategy) => suggest(strategy, STRATEGIES.iter().copied()),
+            _ => None,
+        };
+
+        Diagnostic {
+            path: self.path.clone(),
+            message: self.error.to_string(),
+            suggestion,
+        }
+    }
+}
+
+/// Every problem with `config`; empty when it is valid.
+pub fn diagnose(config: &TokenizerConfig) -> Vec<Diagnostic> {
+    problems(config).iter().map(Problem::diagnostic).collect()
+}
+
+/// Reports a single problem as its own error, so callers can still match on
+/// it, and several as [`TokenizerError::ConfigurationProblems`].
+pub fn into_result(problems: Vec<Problem>) -> Result<()> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(into_error(problems))
+    }
+}
+
+/// The error [`into_result`] returns for a non-empty list of problems.
+pub fn into_error(mut problems: Vec<Problem>) -> TokenizerError {
+    if problems.len() == 1 {
+        problems.remove(0).error
+    } else {
+        TokenizerError::ConfigurationProblems(problems.iter().map(Problem::diagnostic).collect())
+    }
+}
+
+/// Checks the option names given alongside `strategy`: each must exist and
+/// be used by that strategy. Values aren't looked at; see [`problems`].
+pub fn option_problems<'a>(strategy: &str, options: impl IntoIterator<Item = &'a str>) -> Vec<Problem> {
+    let mut found = Vec::new();
+    let known_strategy = STRATEGIES.contains(&strategy);
+    if !known_strategy {
+        found.push(Problem::new("strategy", TokenizerError::UnknownStrategy(strategy.to_string())));
+    }
+
+    let all_options = || {
+        GENERAL_OPTIONS
+            .iter()
+            .chain(STRATEGIES.iter().flat_map(|strategy| strategy_options(strategy)))
+            .copied()
+    };
+
+    for option in options {
+        if GENERAL_OPTIONS.contains(&option) || strategy_options(strategy).contains(&option) {
+            continue;
+        }
+
+        if !all_options().any(|known| known == option) {
+            found.push(Problem::new(
+                option,
+                TokenizerError::UnknownOption {
+                    key: option.to_string(),
+                    suggestion: suggest(option, all_options()),
+                },
+            ));
+        } else if known_strategy {
+            found.push(Problem::new(
+                option,
+                TokenizerError::UnusedOption {
+                    key: option.to_string(),
+                    strategy: strategy.to_string(),
+                },
+            ));
+        }
+    }
+
+    found
+}
+
+/// Checks the type and option names of the filter at `path`, such as
+/// `filters[0]`, like [`option_problems`] does for a strategy.
+pub fn filter_problems<'a>(path: &str, filter: &str, options: impl IntoIterator<Item = &'a str>) -> Vec<Problem> {
+    typed_problems(path, filter, options, ("filters", "filter", FILTERS), filter_options(filter))
+}
+
+/// Checks the type and option names of the char filter at `path`, such as
+/// `char_filters[0]`. No char filter has options yet.
+pub fn char_filter_problems<'a>(path: &str, filter: &str, options: impl IntoIterator<Item = &'a str>) -> Vec<Problem> {
+    typed_problems(path, filter, options, ("char_filters", "char filter", CHAR_FILTERS), &[])
+}
+
+// The problems of a list entry with a `type` among `types` and the options
+// `known`, where `key` is the list and `noun` what its entries are
+fn typed_problems<'a>(
+    path: &str,
+    kind: &str,
+    options: impl IntoIterator<Item = &'a str>,
+    (key, noun, types): (&str, &str, &[&str]),
+    known: &[&str],
+) -> Vec<Problem> {
+    if !types.contains(&kind) {
+        let suggestion = suggest(kind, types.iter().copied())
+            .map(|suggestion| format!(" (did you mean '{}'?)", suggestion))
+            .unwrap_or_default();
+        return vec![Problem::new(
+            format!("{}.type", path),
+            TokenizerError::InvalidConfiguration {
+                key: key.to_string(),
+                message: format!("unknown {} '{}'{}", noun, kind, suggestion),
+            },
+        )];
+    }
+
+    options
+        .into_iter()
+        .filter(|option| *option != "type" && !known.contains(option))
+        .map(|option| {
+            Problem::new(
+                format!("{}.{}", path, option),
+                TokenizerError::UnknownOption {
+                    key: option.to_string(),
+                    suggestion: suggest(option, known.iter().copied()),
+                },
+            )
+        })
+        .collect()
+}
+
+/// Every problem with the values in `config`, in option order.
+pub fn problems(config: &TokenizerConfig) -> Vec<Problem> {
+    use TokenizerStrategy::*;
+
+    let mut found = Vec::new();
+
+    match &config.strategy {
+        EdgeNgram { min_gram, max_gram } | Ngram { min_gram, max_gram }
+            if *min_gram == 0 || min_gram > max_gram =>
+        {
+            // Blame max_gram when min_gram is fine on its own
+            let path = if *min_gram == 0 { "min_gram" } else { "max_gram" };
+            found.push(Problem::new(
+                path,
+                TokenizerError::InvalidNgramConfig {
+                    min: *min_gram,
+                    max: *max_gram,
+                },
+            ));
+        }
+        PathHierarchy { delimiter } if delimiter.is_empty() => {
+            found.push(Problem::new(
+                "delimiter",
+                TokenizerError::EmptyDelimiter {
+                    tokenizer: "PathHierarchy".to_string(),
+                },
+            ));
+        }
+        Pattern { regex } => {
+            if let Err(e) = config.regex_limits.compile(regex) {
+                found.push(Problem::new("regex", e));
+            }
+        }
+        // The file's own normalizer decides casing, so `lowercase` is ignored;
+        // tokens can't be dropped or regrouped without breaking the ids they
+        // stand for
+        HuggingFace { path, .. } => {
+            if path.is_empty() {
+                found.push(Problem::new(
+                    "path",
+                    TokenizerError::InvalidConfiguration {
+                        key: "path".to_string(),
+                        message: "huggingface strategy requires a tokenizer.json path".to_string(),
+                    },
+                ));
+            }
+            if !config.preserve_patterns.is_empty() {
+                found.push(unsupported_preserve_patterns("huggingface"));
+            }
+            if config.remove_punctuation {
+                found.push(Problem::new(
+                    "remove_punctuation",
+                    TokenizerError::InvalidConfiguration {
+                        key: "remove_punctuation".to_string(),
+                        message: "remove_punctuation is not supported by the huggingface strategy".to_string(),
+                    },
+                ));
+            }
+            if config.locale.is_some() {
+                found.push(unsupported_locale("huggingface"));
+            }
+        }
+        Bytes => {
+            // Preserved tokens go through the Unicode filters, which would
+            // change the non-ASCII bytes this strategy promises to keep
+            if !config.preserve_patterns.is_empty() {
+                found.push(unsupported_preserve_patterns("bytes"));
+            }
+            // Only ASCII letters are lowercased, and Turkish `I` has no
+            // single-byte lowercase
+            if config.locale.is_some() {
+                found.push(unsupported_locale("bytes"));
+            }
+        }
+        Japanese { dictionary, .. } if dictionary.is_empty() => {
+            found.push(Problem::new(
+                "dictionary",
+                TokenizerError::InvalidConfiguration {
+                    key: "dictionary".to_string(),
+                    message: "japanese strategy requires a dictionary directory".to_string(),
+                },
+            ));
+        }
+        Cjk {
+            dictionary: Some(path),
+            ..
+        } if path.is_empty() => {
+            found.push(Problem::new(
+                "dictionary",
+                TokenizerError::InvalidConfiguration {
+                    key: "dictionary".to_string(),
+                    message: "dictionary path must not be empty; leave it out to use the bundled dictionary"
+                        .to_string(),
+                },
+            ));
+        }
+        Router { routes, fallback } => {
+            if routes.is_empty() {
+                found.push(invalid_route("routes", "router strategy requires at least one route"));
+            }
+            for (index, route) in routes.iter().enumerate() {
+                let path = format!("routes[{}]", index);
+                if route.scripts.is_empty() && route.languages.is_empty() {
+                    found.push(invalid_route(
+                        format!("{}.scripts", path),
+                        "route needs at least one script or language",
+                    ));
+                }
+                for name in route.scripts.iter().filter(|name| parse_script(name).is_none()) {
+                    found.push(invalid_route(format!("{}.scripts", path), format!("unknown script '{}'", name)));
+                }
+                for name in route.languages.iter().filter(|name| parse_language(name).is_none()) {
+                    found.push(invalid_route(
+                        format!("{}.languages", path),
+                        format!("unknown language '{}'", name),
+                    ));
+                }
+                found.extend(nested_problems(&format!("{}.tokenizer", path), &route.tokenizer));
+            }
+            if let Some(fallback) = fallback {
+                found.extend(nested_problems("fallback", fallback));
+            }
+            // Each route preserves its own patterns
+            if !config.preserve_patterns.is_empty() {
+                found.push(unsupported_preserve_patterns("router"));
+            }
+        }
+        _ => {}
+    }
+
+    if !config.word_break_dictionaries.is_empty() && !matches!(config.strategy, Unicode) {
+        found.push(Problem::new(
+            "word_break_dictionaries",
+            TokenizerError::InvalidConfiguration {
+                key: "word_break_dictionaries".to_string(),
+                message: "word_break_dictionaries is only used by the unicode strategy".to_string(),
+            },
+        ));
+    }
+
+    if let Some(locale) = config.locale.as_deref().filter(|locale| !is_valid_locale(locale)) {
+        found.push(Problem::new(
+            "locale",
+            TokenizerError::InvalidConfiguration {
+                key: "locale".to_string(),
+                message: format!("'{}' is not a BCP 47 language tag such as 'tr' or 'az-Latn'", locale),
+            },
+        ));
+    }
+
+    for (name, limit) in [
+        ("max_input_bytes", config.limits.max_input_bytes),
+        ("max_tokens", config.limits.max_tokens),
+        ("max_token_length", config.limits.max_token_length),
+        ("regex_size_limit", config.regex_limits.size_limit),
+        ("regex_dfa_size_limit", config.regex_limits.dfa_size_limit),
+    ] {
+        if limit == Some(0) {
+            found.push(Problem::new(
+                name,
+                TokenizerError::InvalidConfiguration {
+                    key: name.to_string(),
+                    message: format!("{} must be at least 1", name),
+                },
+            ));
+        }
+    }
+
+    let max_patterns = config.regex_limits.max_preserve_patterns;
+    for (index, pattern) in config.preserve_patterns.iter().enumerate() {
+        let path = format!("preserve_patterns[{}]", index);
+        if let Some(max) = max_patterns.filter(|max| index == *max) {
+            found.push(Problem::new(
+                &path,
+                TokenizerError::RegexLimitExceeded {
+                    pattern: pattern.clone(),
+                    limit: "max_preserve_patterns".to_string(),
+                    max,
+                },
+            ));
+        }
+        if let Err(e) = config.regex_limits.compile(pattern) {
+            found.push(Problem::new(path, e));
+        }
+    }
+
+    found
+}
+
+fn invalid_route(path: impl Into<String>, message: impl Into<String>) -> Problem {
+    Problem::new(
+        path,
+        TokenizerError::InvalidConfiguration {
+            key: "routes".to_string(),
+            message: message.into(),
+        },
+    )
+}
+
+// Problems of a config inside another one, at paths under `prefix`
+fn nested_problems(prefix: &str, config: &TokenizerConfig) -> Vec<Problem> {
+    problems(config)
+        .into_iter()
+        .map(|problem| Problem::new(format!("{}.{}", prefix, problem.path), problem.error))
+        .collect()
+}
+
+fn unsupported_locale(strategy: &str) -> Problem {
+    Problem::new(
+        "locale",
+        TokenizerError::InvalidConfiguration {
+            key: "locale".to_string(),
+            message: format!("locale is not supported by the {} strategy", strategy),
+        },
+    )
+}
+
+fn unsupported_preserve_patterns(strategy: &str) -> Problem {
+    Problem::new(
+        "preserve_patterns",
+        TokenizerError::InvalidConfiguration {
+            key: "preserve_patterns".to_string(),
+            message: format!("preserve_patterns are not supported by the {} strategy", strategy),
+        },
+    )
+}
+
+/// The candidate closest to `word`, if it is close enough to be a typo:
+/// within a third of its length in edits, as `did_you_mean` does.
+pub fn suggest<'a>(word: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let threshold = (word.chars().count() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+// Levenshtein distance, counting a transposition as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>(); a.len() + 1];
+
+    for i in 1..=a.len() {
+        rows[i][0] = i;
+        for j in 1..=b.len() {
+            let substitution = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + substitution);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
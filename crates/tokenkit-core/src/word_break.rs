@@ -0,0 +1,133 @@
+//! Dictionary word breaking for Thai, Lao, Khmer and Myanmar, which are
+//! written without spaces between words.
+//!
+//! The bundled word lists are the ICU dictionaries compiled into
+//! `icu_segmenter`. User dictionaries add words on top: one word per line,
+//! anything after the word on the same line is ignored.
+
+use crate::error::{Result, TokenizerError};
+use aho_corasick::{AhoCorasick, Anchored, Input, MatchKind, StartKind};
+use icu_segmenter::options::WordBreakInvariantOptions;
+use icu_segmenter::{WordSegmenter, WordSegmenterBorrowed};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use unicode_segmentation::GraphemeCursor;
+
+/// Breaks runs of [`DICTIONARY_SCRIPTS`] into words.
+///
+/// [`DICTIONARY_SCRIPTS`]: crate::script::DICTIONARY_SCRIPTS
+pub struct WordBreaker {
+    segmenter: WordSegmenterBorrowed<'static>,
+    user_words: Option<AhoCorasick>,
+}
+
+impl WordBreaker {
+    /// A breaker using the bundled word lists, plus the words of
+    /// `user_dictionaries`.
+    pub fn new(user_dictionaries: &[String]) -> Result<Self> {
+        let mut words = Vec::new();
+        for path in user_dictionaries {
+            load_user_dictionary(&mut words, path)?;
+        }
+
+        let user_words = if words.is_empty() {
+            None
+        } else {
+            let automaton = AhoCorasick::builder()
+                .match_kind(MatchKind::LeftmostLongest)
+                .start_kind(StartKind::Anchored)
+                .build(&words)
+                .map_err(|e| invalid(&user_dictionaries.join(", "), e))?;
+            Some(automaton)
+        };
+
+        Ok(Self {
+            user_words,
+            ..Self::default()
+        })
+    }
+
+    /// Splits `text` into words, leaving out the spaces and punctuation
+    /// between them.
+    ///
+    /// A user word is matched where a word starts, and kept whole when it is
+    /// at least as long as the bundled dictionary's word there. Words never
+    /// end inside a grapheme cluster, such as before a Myanmar medial.
+    pub fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let mut boundaries = self.boundaries(text, 0);
+        let Some(user_words) = &self.user_words else {
+            return segments(text, &boundaries);
+        };
+
+        let mut cuts = vec![0];
+        let mut pos = 0;
+        while pos < text.len() {
+            let next = next_boundary(&boundaries, pos).unwrap_or(text.len());
+            let input = Input::new(&text[pos..]).anchored(Anchored::Yes);
+            pos = match user_words.find(input) {
+                Some(word) if pos + word.end() >= next && is_grapheme_boundary(text, pos + word.end()) => {
+                    let end = pos + word.end();
+                    if boundaries.binary_search(&end).is_err() {
+                        boundaries = self.boundaries(text, end);
+                    }
+                    end
+                }
+                _ => next,
+            };
+            cuts.push(pos);
+        }
+        segments(text, &cuts)
+    }
+
+    // Bundled dictionary boundaries of `text[from..]`, as offsets into `text`
+    fn boundaries(&self, text: &str, from: usize) -> Vec<usize> {
+        self.segmenter
+            .segment_str(&text[from..])
+            .map(|boundary| from + boundary)
+            .filter(|&boundary| is_grapheme_boundary(text, boundary))
+            .collect()
+    }
+}
+
+impl Default for WordBreaker {
+    fn default() -> Self {
+        Self {
+            segmenter: WordSegmenter::new_dictionary(WordBreakInvariantOptions::default()),
+            user_words: None,
+        }
+    }
+}
+
+fn is_grapheme_boundary(text: &str, offset: usize) -> bool {
+    matches!(GraphemeCursor::new(offset, text.len(), true).is_boundary(text, 0), Ok(true))
+}
+
+fn next_boundary(boundaries: &[usize], pos: usize) -> Option<usize> {
+    let index = boundaries.partition_point(|&boundary| boundary <= pos);
+    boundaries.get(index).copied()
+}
+
+fn segments<'a>(text: &'a str, cuts: &[usize]) -> Vec<&'a str> {
+    cuts.windows(2)
+        .map(|pair| &text[pair[0]..pair[1]])
+        .filter(|segment| segment.chars().any(char::is_alphanumeric))
+        .collect()
+}
+
+fn invalid(path: &str, error: impl std::fmt::Display) -> TokenizerError {
+    TokenizerError::InvalidDictionary {
+        path: path.to_string(),
+        message: error.to_string(),
+    }
+}
+
+fn load_user_dictionary(words: &mut Vec<String>, path: &str) -> Result<()> {
+    let file = File::open(path).map_err(|e| invalid(path, e))?;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|e| invalid(path, e))?;
+        if let Some(word) = line.split_whitespace().next() {
+            words.push(word.to_string());
+        }
+    }
+    Ok(())
+}
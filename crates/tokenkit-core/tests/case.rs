@@ -0,0 +1,114 @@
+use tokenkit_core::case::{is_valid_locale, CaseLocale};
+use tokenkit_core::validation::diagnose;
+use tokenkit_core::{from_config, TokenizerConfig, TokenizerStrategy};
+
+fn tokenize_in(locale: &str, strategy: TokenizerStrategy, text: &str) -> Vec<String> {
+    let config = TokenizerConfig {
+        strategy,
+        locale: Some(locale.to_string()),
+        ..TokenizerConfig::default()
+    };
+    from_config(config)
+        .unwrap()
+        .tokenize(text)
+        .into_iter()
+        .map(|t| t.into_owned())
+        .collect()
+}
+
+#[test]
+fn turkish_has_dotted_and_dotless_i() {
+    let turkish = CaseLocale::new(Some("tr"));
+    assert_eq!(turkish.lowercase("İSTANBUL"), "istanbul");
+    assert_eq!(turkish.lowercase("ISPARTA"), "ısparta");
+    // A combining dot above makes `I` dotted, and is absorbed
+    assert_eq!(turkish.lowercase("I\u{307}STANBUL"), "istanbul");
+    assert_eq!(CaseLocale::new(Some("az-Latn-AZ")), CaseLocale::Turkic);
+
+    // Without a locale, `İ` keeps its dot as a combining mark
+    assert_eq!(CaseLocale::Root.lowercase("İSTANBUL"), "i\u{307}stanbul");
+}
+
+#[test]
+fn lithuanian_keeps_the_dot_under_accents() {
+    let lithuanian = CaseLocale::new(Some("lt"));
+    assert_eq!(lithuanian.lowercase("Ì"), "i\u{307}\u{300}");
+    assert_eq!(lithuanian.lowercase("I\u{301}"), "i\u{307}\u{301}");
+    assert_eq!(lithuanian.lowercase("ĮLANKA"), "įlanka");
+}
+
+#[test]
+fn greek_sigma_is_final_at_the_end_of_a_word() {
+    assert_eq!(CaseLocale::Root.lowercase("ΟΔΟΣ ΣΟΦΙΑΣ"), "οδο\u{3c2} σοφια\u{3c2}");
+    assert_eq!(CaseLocale::new(Some("tr")).lowercase("ΟΔΟΣ"), "οδο\u{3c2}");
+}
+
+#[test]
+fn every_strategy_follows_the_locale() {
+    let text = "İSTANBUL IRMAK";
+    let expected = ["istanbul", "ırmak"];
+
+    assert_eq!(tokenize_in("tr", TokenizerStrategy::Unicode, text), expected);
+    assert_eq!(tokenize_in("tr", TokenizerStrategy::Whitespace, text), expected);
+    assert_eq!(tokenize_in("tr", TokenizerStrategy::Letter, text), expected);
+    assert_eq!(tokenize_in("tr", TokenizerStrategy::Lowercase, text), expected);
+    assert_eq!(tokenize_in("tr", TokenizerStrategy::Sentence, "İSTANBUL IRMAK."), ["istanbul ırmak."]);
+    assert_eq!(
+        tokenize_in("tr", TokenizerStrategy::PathHierarchy { delimiter: "/".to_string() }, "/İL/IRMAK"),
+        ["/il", "/il/ırmak"]
+    );
+    assert_eq!(
+        tokenize_in("tr", TokenizerStrategy::EdgeNgram { min_gram: 1, max_gram: 2 }, "IŞIK"),
+        ["ı", "ış"]
+    );
+}
+
+#[test]
+fn locale_applies_around_preserved_text() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Sentence,
+        locale: Some("tr".to_string()),
+        preserve_patterns: vec![r"\bISO\d+".to_string()],
+        ..TokenizerConfig::default()
+    };
+    let tokens = from_config(config).unwrap().tokenize("ISO9001 İÇIN.");
+    assert_eq!(tokens, ["ISO9001 içın."]);
+
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::PathHierarchy { delimiter: "/".to_string() },
+        locale: Some("tr".to_string()),
+        preserve_patterns: vec!["ISO".to_string()],
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(from_config(config).unwrap().tokenize("ISO/İL"), ["ISO", "ISO/il"]);
+}
+
+#[test]
+fn final_sigma_in_the_lowercase_strategy() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Lowercase,
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(from_config(config).unwrap().tokenize("ΟΔΟΣ ΣΟΦΙΑΣ"), ["οδο\u{3c2}", "σοφια\u{3c2}"]);
+}
+
+#[test]
+fn validates_locales() {
+    assert!(is_valid_locale("tr"));
+    assert!(is_valid_locale("az_Latn"));
+    assert!(!is_valid_locale("turkish"));
+    assert!(!is_valid_locale(""));
+
+    let config = TokenizerConfig {
+        locale: Some("turkish".to_string()),
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(diagnose(&config)[0].path, "locale");
+
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Bytes,
+        locale: Some("tr".to_string()),
+        ..TokenizerConfig::default()
+    };
+    assert!(diagnose(&config)[0].message.contains("not supported by the bytes strategy"));
+}
@@ -0,0 +1,133 @@
+use std::path::PathBuf;
+use tokenkit_core::{from_config, TokenStream, Tokenizer, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn fixture(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/cjk")
+        .join(name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn cjk(dictionary: Option<&str>, user_dictionaries: &[&str], search_mode: bool) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Cjk {
+            dictionary: dictionary.map(fixture),
+            user_dictionaries: user_dictionaries.iter().map(|name| fixture(name)).collect(),
+            search_mode,
+            hmm: true,
+        },
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn segments_with_the_bundled_dictionary() {
+    let tokenizer = cjk(None, &[], false);
+
+    assert_eq!(tokenizer.tokenize("我来到北京清华大学"), vec!["我", "来到", "北京", "清华大学"]);
+}
+
+#[test]
+fn drops_whitespace_and_punctuation_and_keeps_other_words() {
+    let tokenizer = cjk(None, &[], false);
+
+    assert_eq!(
+        tokenizer.tokenize("我爱 Rust，也爱北京。Version 2"),
+        vec!["我", "爱", "rust", "也", "爱", "北京", "version", "2"]
+    );
+}
+
+#[test]
+fn offsets_point_into_the_input() {
+    let text = "他来到了网易杭研大厦";
+    for token in cjk(None, &[], true).tokenize_with_offsets(text) {
+        assert_eq!(&text[token.start..token.end], token.text);
+    }
+}
+
+#[test]
+fn search_mode_adds_words_inside_long_words() {
+    let text = "小明硕士毕业于中国科学院计算所";
+    let plain = cjk(None, &[], false).tokenize(text);
+    let search = cjk(None, &[], true).tokenize(text);
+
+    assert!(plain.contains(&"中国科学院".into()));
+    assert!(!plain.contains(&"科学".into()));
+    for word in ["中国", "科学", "学院", "科学院", "中国科学院"] {
+        assert!(search.contains(&word.into()), "{} missing from {:?}", word, search);
+    }
+}
+
+#[test]
+fn user_dictionaries_keep_their_words_whole() {
+    let text = "李小福是创新办主任也是云计算方面的专家";
+    assert!(!cjk(None, &[], false).tokenize(text).contains(&"创新办".into()));
+
+    let tokens = cjk(None, &["user.txt"], false).tokenize(text);
+    assert!(tokens.contains(&"创新办".into()), "{:?}", tokens);
+    assert!(tokens.contains(&"云计算".into()), "{:?}", tokens);
+}
+
+#[test]
+fn a_dictionary_replaces_the_bundled_one() {
+    let tokens = cjk(Some("small.txt"), &[], false).tokenize("小明硕士毕业于中国科学院");
+
+    assert_eq!(tokens, vec!["小明", "硕士", "毕业", "于", "中国科学院"]);
+}
+
+#[test]
+fn unreadable_or_malformed_dictionaries_are_errors() {
+    for (dictionary, message) in [("missing.txt", "No such file"), ("broken.txt", "12x")] {
+        let config = TokenizerConfig {
+            strategy: TokenizerStrategy::Cjk {
+                dictionary: None,
+                user_dictionaries: vec![fixture(dictionary)],
+                search_mode: false,
+                hmm: true,
+            },
+            ..TokenizerConfig::default()
+        };
+
+        match from_config(config) {
+            Err(TokenizerError::InvalidDictionary { path, message: error }) => {
+                assert!(path.ends_with(dictionary));
+                assert!(error.contains(message), "{}", error);
+            }
+            other => panic!("expected InvalidDictionary, got {:?}", other.map(|_| ())),
+        }
+    }
+}
+
+#[test]
+fn preserve_patterns_are_kept_whole() {
+    let tokenizer = from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Cjk {
+            dictionary: None,
+            user_dictionaries: Vec::new(),
+            search_mode: false,
+            hmm: true,
+        },
+        preserve_patterns: vec![r"\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    })
+    .unwrap();
+
+    assert_eq!(tokenizer.tokenize("每天服用100mg阿司匹林"), vec!["每天", "服用", "100mg", "阿司匹林"]);
+}
+
+#[test]
+fn streams_split_at_sentence_ends() {
+    let tokenizer = cjk(None, &[], false);
+    let text = "我来到北京清华大学。他来到了网易杭研大厦";
+
+    let mut stream = TokenStream::new(cjk(None, &[], false));
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(5) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, tokenizer.tokenize(text).into_iter().map(|t| t.into_owned()).collect::<Vec<_>>());
+}
@@ -0,0 +1,68 @@
+use tokenkit_core::{from_config, validate_config, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+#[test]
+fn default_config_is_lowercasing_unicode() {
+    let config = TokenizerConfig::default();
+    assert_eq!(config.strategy, TokenizerStrategy::Unicode);
+    assert!(config.lowercase);
+    assert!(!config.remove_punctuation);
+    assert!(config.preserve_patterns.is_empty());
+}
+
+#[test]
+fn rejects_zero_min_gram() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Ngram { min_gram: 0, max_gram: 3 },
+        ..TokenizerConfig::default()
+    };
+    assert!(matches!(
+        validate_config(&config),
+        Err(TokenizerError::InvalidNgramConfig { min: 0, max: 3 })
+    ));
+}
+
+#[test]
+fn rejects_min_gram_above_max_gram() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::EdgeNgram { min_gram: 5, max_gram: 2 },
+        ..TokenizerConfig::default()
+    };
+    assert!(validate_config(&config).is_err());
+}
+
+#[test]
+fn rejects_empty_delimiter() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::PathHierarchy { delimiter: String::new() },
+        ..TokenizerConfig::default()
+    };
+    assert!(matches!(
+        validate_config(&config),
+        Err(TokenizerError::EmptyDelimiter { .. })
+    ));
+}
+
+#[test]
+fn from_config_rejects_invalid_regexes() {
+    let config = TokenizerConfig {
+        preserve_patterns: vec!["[invalid(".to_string()],
+        ..TokenizerConfig::default()
+    };
+    match from_config(config) {
+        Err(TokenizerError::InvalidRegex { pattern, .. }) => assert_eq!(pattern, "[invalid("),
+        other => panic!("expected InvalidRegex, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn config_round_trips_through_json() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::EdgeNgram { min_gram: 2, max_gram: 5 },
+        preserve_patterns: vec![r"\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    let parsed: TokenizerConfig = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.strategy, config.strategy);
+    assert_eq!(parsed.preserve_patterns, config.preserve_patterns);
+}
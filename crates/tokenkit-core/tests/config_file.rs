@@ -0,0 +1,139 @@
+use tokenkit_core::{ConfigFormat, TokenizerConfig, TokenizerError, TokenizerStrategy, CONFIG_VERSION};
+
+fn sample() -> TokenizerConfig {
+    TokenizerConfig {
+        strategy: TokenizerStrategy::EdgeNgram { min_gram: 2, max_gram: 5 },
+        lowercase: false,
+        remove_punctuation: true,
+        preserve_patterns: vec![r"(?i)\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    }
+}
+
+fn file_error(result: tokenkit_core::Result<TokenizerConfig>) -> String {
+    match result {
+        Err(TokenizerError::InvalidConfigFile(message)) => message,
+        other => panic!("expected InvalidConfigFile, got {:?}", other),
+    }
+}
+
+#[test]
+fn round_trips_through_json_and_yaml() {
+    let config = sample();
+    assert_eq!(TokenizerConfig::from_json(&config.to_json().unwrap()).unwrap(), config);
+    assert_eq!(TokenizerConfig::from_yaml(&config.to_yaml().unwrap()).unwrap(), config);
+}
+
+#[test]
+fn every_strategy_round_trips() {
+    for strategy in [
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Pattern { regex: r"\w+".to_string() },
+        TokenizerStrategy::Sentence,
+        TokenizerStrategy::Grapheme { extended: false },
+        TokenizerStrategy::Keyword,
+        TokenizerStrategy::Ngram { min_gram: 1, max_gram: 3 },
+        TokenizerStrategy::PathHierarchy { delimiter: "\\".to_string() },
+        TokenizerStrategy::UrlEmail,
+        TokenizerStrategy::CharGroup { split_on_chars: ",;".to_string() },
+        TokenizerStrategy::Letter,
+        TokenizerStrategy::Lowercase,
+        TokenizerStrategy::Bytes,
+        TokenizerStrategy::Cjk {
+            dictionary: Some("dict.txt".to_string()),
+            user_dictionaries: vec!["user.txt".to_string()],
+            search_mode: true,
+            hmm: false,
+        },
+        TokenizerStrategy::Japanese {
+            dictionary: "ipadic".to_string(),
+            user_dictionaries: vec!["user.csv".to_string()],
+        },
+    ] {
+        let config = TokenizerConfig { strategy, ..TokenizerConfig::default() };
+        let yaml = config.dump(ConfigFormat::Yaml).unwrap();
+        assert_eq!(TokenizerConfig::from_yaml(&yaml).unwrap(), config, "{}", yaml);
+    }
+}
+
+#[test]
+fn writes_the_documented_schema() {
+    let json = sample().to_json().unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "version": CONFIG_VERSION,
+            "tokenizer": {
+                "strategy": { "type": "edge_ngram", "min_gram": 2, "max_gram": 5 },
+                "lowercase": false,
+                "remove_punctuation": true,
+                "preserve_patterns": ["(?i)\\d+mg"]
+            }
+        })
+    );
+}
+
+#[test]
+fn omitted_fields_take_defaults() {
+    let config = TokenizerConfig::from_yaml("version: 1\ntokenizer:\n  strategy:\n    type: ngram\n").unwrap();
+    assert_eq!(config.strategy, TokenizerStrategy::Ngram { min_gram: 2, max_gram: 10 });
+    assert!(config.lowercase);
+
+    let config = TokenizerConfig::from_json(r#"{"version": 1, "tokenizer": {}}"#).unwrap();
+    assert_eq!(config, TokenizerConfig::default());
+}
+
+#[test]
+fn rejects_unknown_fields() {
+    let message = file_error(TokenizerConfig::from_json(r#"{"version": 1, "tokenizer": {"lowercse": false}}"#));
+    assert!(message.contains("unknown field `lowercse`"), "{}", message);
+
+    let message = file_error(TokenizerConfig::from_yaml(
+        "version: 1\ntokenizer:\n  strategy:\n    type: ngram\n    min_grams: 3\n",
+    ));
+    assert!(message.contains("unknown field `min_grams`"), "{}", message);
+
+    let message = file_error(TokenizerConfig::from_json(r#"{"version": 1, "tokenizers": {}}"#));
+    assert!(message.contains("unknown field `tokenizers`"), "{}", message);
+}
+
+#[test]
+fn rejects_unknown_strategies() {
+    let message = file_error(TokenizerConfig::from_json(
+        r#"{"version": 1, "tokenizer": {"strategy": {"type": "stemmer"}}}"#,
+    ));
+    assert!(message.contains("unknown variant `stemmer`"), "{}", message);
+}
+
+#[test]
+fn requires_a_supported_version() {
+    let message = file_error(TokenizerConfig::from_json(r#"{"tokenizer": {}}"#));
+    assert!(message.contains("missing `version`"), "{}", message);
+
+    let message = file_error(TokenizerConfig::from_json(r#"{"version": 2, "tokenizer": {"new_field": 1}}"#));
+    assert!(message.contains("version 2 is newer"), "{}", message);
+}
+
+#[test]
+fn validates_loaded_configs() {
+    let result = TokenizerConfig::from_json(
+        r#"{"version": 1, "tokenizer": {"strategy": {"type": "ngram", "min_gram": 4, "max_gram": 2}}}"#,
+    );
+    assert!(matches!(result, Err(TokenizerError::InvalidNgramConfig { min: 4, max: 2 })));
+}
+
+#[test]
+fn picks_the_format_from_the_extension() {
+    let dir = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    for name in ["tokenizer.json", "tokenizer.yaml", "tokenizer.YML"] {
+        let path = dir.join(name);
+        let format = ConfigFormat::from_path(&path).unwrap();
+        std::fs::write(&path, sample().dump(format).unwrap()).unwrap();
+        assert_eq!(TokenizerConfig::load_file(&path).unwrap(), sample());
+    }
+
+    let message = file_error(TokenizerConfig::load_file(dir.join("tokenizer.toml")));
+    assert!(message.contains("expected a .json, .yml or .yaml file"), "{}", message);
+}
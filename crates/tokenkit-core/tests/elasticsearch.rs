@@ -0,0 +1,255 @@
+use tokenkit_core::elasticsearch::import_analysis;
+use tokenkit_core::{from_config, TokenFilter, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn import_error(json: &str) -> String {
+    match import_analysis(json) {
+        Err(TokenizerError::InvalidAnalysisSettings(message)) => message,
+        other => panic!("expected InvalidAnalysisSettings, got {:?}", other),
+    }
+}
+
+fn tokenize(config: &TokenizerConfig, text: &str) -> Vec<String> {
+    from_config(config.clone())
+        .unwrap()
+        .tokenize(text)
+        .into_iter()
+        .map(|t| t.into_owned())
+        .collect()
+}
+
+const SETTINGS: &str = r#"{
+  "settings": {
+    "index": {
+      "number_of_shards": 1,
+      "analysis": {
+        "analyzer": {
+          "autocomplete": {
+            "type": "custom",
+            "tokenizer": "autocomplete",
+            "filter": ["lowercase"]
+          },
+          "sku": { "tokenizer": "sku" },
+          "paths": { "tokenizer": "path_hierarchy" },
+          "codes": { "tokenizer": "codes", "filter": "my_lowercase" }
+        },
+        "tokenizer": {
+          "autocomplete": {
+            "type": "edge_ngram",
+            "min_gram": 2,
+            "max_gram": "4",
+            "token_chars": ["letter", "digit", "punctuation", "symbol"]
+          },
+          "sku": { "type": "char_group", "tokenize_on_chars": ["whitespace", "-"] },
+          "codes": { "type": "pattern", "pattern": "[a-z]+\\d+", "group": 0, "flags": "CASE_INSENSITIVE" }
+        },
+        "filter": {
+          "my_lowercase": { "type": "lowercase" }
+        }
+      }
+    }
+  }
+}"#;
+
+#[test]
+fn imports_custom_analyzers() {
+    let configs = import_analysis(SETTINGS).unwrap();
+    assert_eq!(configs.keys().collect::<Vec<_>>(), ["autocomplete", "codes", "paths", "sku"]);
+
+    assert_eq!(
+        configs["autocomplete"],
+        TokenizerConfig {
+            strategy: TokenizerStrategy::EdgeNgram { min_gram: 2, max_gram: 4 },
+            lowercase: true,
+            ..TokenizerConfig::default()
+        }
+    );
+    assert_eq!(tokenize(&configs["autocomplete"], "Laptop"), ["la", "lap", "lapt"]);
+
+    // No lowercase filter, so case is kept
+    assert!(!configs["sku"].lowercase);
+    assert_eq!(tokenize(&configs["sku"], "AB-123 X"), ["AB", "123", "X"]);
+
+    assert_eq!(configs["paths"].strategy, TokenizerStrategy::PathHierarchy { delimiter: "/".into() });
+
+    assert_eq!(
+        configs["codes"].strategy,
+        TokenizerStrategy::Pattern {
+            regex: r"(?i)[a-z]+\d+".into()
+        }
+    );
+    assert_eq!(tokenize(&configs["codes"], "see ABC12 and x9"), ["abc12", "x9"]);
+}
+
+#[test]
+fn imports_built_in_analyzers() {
+    let configs = import_analysis(
+        r#"{"analysis": {"analyzer": {
+            "std": {"type": "standard", "stopwords": "_none_"},
+            "simple": {"type": "simple"},
+            "ws": {"type": "whitespace"},
+            "kw": {"type": "keyword"}
+        }}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(configs["std"], TokenizerConfig::default());
+    assert_eq!(tokenize(&configs["simple"], "Hello World2"), ["hello", "world"]);
+    assert_eq!(tokenize(&configs["ws"], "Hello, World"), ["Hello,", "World"]);
+    assert_eq!(tokenize(&configs["kw"], "Hello World"), ["Hello World"]);
+}
+
+#[test]
+fn letter_tokenizers_split_on_marks_that_are_not_letters() {
+    let configs = import_analysis(
+        r#"{"analysis": {"analyzer": {
+            "letters": {"tokenizer": "letter"},
+            "lower": {"tokenizer": "lowercase"},
+            "simple": {"type": "simple"}
+        }}}"#,
+    )
+    .unwrap();
+
+    // The virama is a combining mark but not alphabetic, so Elasticsearch splits on it
+    assert_eq!(tokenize(&configs["letters"], "Hindi हिन्दी"), ["Hindi", "हिन", "दी"]);
+    assert_eq!(tokenize(&configs["lower"], "Hindi हिन्दी"), ["hindi", "हिन", "दी"]);
+    assert_eq!(tokenize(&configs["simple"], "Hindi हिन्दी"), ["hindi", "हिन", "दी"]);
+}
+
+#[test]
+fn imports_the_thai_tokenizer() {
+    let configs = import_analysis(r#"{"analysis": {"analyzer": {"th": {"tokenizer": "thai"}}}}"#).unwrap();
+
+    assert_eq!(tokenize(&configs["th"], "ภาษาไทยง่ายนิดเดียว"), ["ภาษา", "ไทย", "ง่าย", "นิด", "เดียว"]);
+}
+
+#[test]
+fn imports_cjk_bigram_filters() {
+    let configs = import_analysis(
+        r#"{"analysis": {
+            "analyzer": {
+                "bigrams": {"tokenizer": "standard", "filter": ["cjk_bigram", "lowercase"]},
+                "both": {"tokenizer": "standard", "filter": ["grams"]}
+            },
+            "filter": {"grams": {"type": "cjk_bigram", "output_unigrams": "true"}}
+        }}"#,
+    )
+    .unwrap();
+
+    assert_eq!(configs["bigrams"].filters, [TokenFilter::CjkBigram { output_unigrams: false }]);
+    assert_eq!(tokenize(&configs["bigrams"], "東京都 Tower"), ["東京", "京都", "tower"]);
+    assert!(!configs["both"].lowercase);
+    assert_eq!(tokenize(&configs["both"], "東京"), ["東", "東京", "京"]);
+
+    let message = import_error(
+        r#"{"analysis": {"analyzer": {"a": {"tokenizer": "standard", "filter": ["han_only"]}},
+            "filter": {"han_only": {"type": "cjk_bigram", "ignored_scripts": ["hangul"]}}}}"#,
+    );
+    assert!(message.contains("filter.han_only.ignored_scripts: ignoring scripts is not supported"), "{}", message);
+}
+
+#[test]
+fn imports_arabic_and_persian_filters() {
+    let configs = import_analysis(
+        r#"{"analysis": {
+            "analyzer": {
+                "arabic": {"tokenizer": "standard", "filter": ["lowercase", "arabic_normalization", "arabic_stemmer"]},
+                "persian": {"tokenizer": "standard", "filter": ["arabic_normalization", "persian_normalization"]}
+            },
+            "filter": {"arabic_stemmer": {"type": "stemmer", "language": "arabic"}}
+        }}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        configs["arabic"].filters,
+        [TokenFilter::ArabicNormalization, TokenFilter::ArabicStem]
+    );
+    assert_eq!(tokenize(&configs["arabic"], "والمكتبة"), ["مكتب"]);
+    assert_eq!(tokenize(&configs["persian"], "کتاب"), ["كتاب"]);
+
+    let message = import_error(
+        r#"{"analysis": {"analyzer": {"a": {"tokenizer": "standard", "filter": ["english_stemmer"]}},
+            "filter": {"english_stemmer": {"type": "stemmer", "language": "english"}}}}"#,
+    );
+    assert!(message.contains("filter.english_stemmer.language: unsupported stemmer language 'english'"), "{}", message);
+}
+
+#[test]
+fn accepts_the_settings_api_response_and_a_bare_analysis_block() {
+    let analysis = r#"{"analyzer": {"words": {"tokenizer": "whitespace", "filter": ["lowercase"]}}}"#;
+    let wrapped = format!(r#"{{"products": {{"settings": {{"index": {{"analysis": {}}}}}}}}}"#, analysis);
+
+    let expected = TokenizerConfig {
+        strategy: TokenizerStrategy::Whitespace,
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(import_analysis(analysis).unwrap()["words"], expected);
+    assert_eq!(import_analysis(&wrapped).unwrap()["words"], expected);
+}
+
+#[test]
+fn reports_every_unsupported_setting_with_its_path() {
+    let message = import_error(
+        r#"{"settings": {"analysis": {
+            "analyzer": {
+                "english": {"type": "english"},
+                "folded": {"tokenizer": "standard", "filter": ["lowercase", "asciifolding"], "char_filter": ["html_strip"]},
+                "grams": {"tokenizer": "grams"}
+            },
+            "tokenizer": {
+                "grams": {"type": "ngram", "token_chars": ["letter", "digit"], "max_ngram_diff": 3}
+            }
+        }}}"#,
+    );
+
+    for expected in [
+        "settings.analysis.analyzer.english.type: unsupported analyzer type 'english'",
+        "settings.analysis.analyzer.folded.char_filter: character filters are not supported",
+        "settings.analysis.analyzer.folded.filter[1]: unsupported token filter 'asciifolding'",
+        "settings.analysis.tokenizer.grams.token_chars: token_chars [\"digit\", \"letter\"] is not supported",
+        "settings.analysis.tokenizer.grams.max_ngram_diff: unsupported setting",
+    ] {
+        assert!(message.contains(expected), "{:?} missing from {:?}", expected, message);
+    }
+}
+
+#[test]
+fn rejects_settings_without_an_exact_equivalent() {
+    // Split mode is the pattern tokenizer's default
+    let message = import_error(r#"{"analysis": {"analyzer": {"a": {"tokenizer": "pattern"}}}}"#);
+    assert!(message.contains("only group 0 is supported, got -1"), "{}", message);
+
+    let message = import_error(
+        r#"{"analysis": {"analyzer": {"a": {"tokenizer": "t"}},
+            "tokenizer": {"t": {"type": "path_hierarchy", "reverse": true, "skip": 1}}}}"#,
+    );
+    assert!(message.contains("tokenizer.t.reverse"), "{}", message);
+    assert!(message.contains("tokenizer.t.skip"), "{}", message);
+
+    let message = import_error(
+        r#"{"analysis": {"analyzer": {"a": {"tokenizer": "t", "filter": ["tr"]}},
+            "tokenizer": {"t": {"type": "standard", "max_token_length": 5}},
+            "filter": {"tr": {"type": "lowercase", "language": "turkish"}}}}"#,
+    );
+    assert!(message.contains("tokenizer.t.max_token_length"), "{}", message);
+    assert!(message.contains("filter.tr.language: unsupported setting"), "{}", message);
+}
+
+#[test]
+fn validates_imported_configs() {
+    let message = import_error(
+        r#"{"analysis": {"analyzer": {"a": {"tokenizer": "t"}},
+            "tokenizer": {"t": {"type": "pattern", "pattern": "(unclosed", "group": 0}}}}"#,
+    );
+    assert!(message.starts_with("analysis.analyzer.a: Invalid regex pattern"), "{}", message);
+}
+
+#[test]
+fn rejects_malformed_input() {
+    assert!(import_error("{not json").starts_with("not valid JSON"));
+    assert_eq!(import_error(r#"{"settings": {}}"#), "no analysis block found");
+    assert_eq!(
+        import_error(r#"{"analysis": {"tokenizer": {}}}"#),
+        "analysis.analyzer: no analyzers defined"
+    );
+}
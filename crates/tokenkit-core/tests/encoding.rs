@@ -0,0 +1,129 @@
+use tokenkit_core::encoding::{decode, decode_bytes, encode_bytes};
+use tokenkit_core::{
+    from_config, AnalyzerRegistry, InvalidUtf8Policy, TokenStream, TokenizerConfig, TokenizerError, TokenizerStrategy,
+};
+
+const BROKEN: &[u8] = b"caf\xe9 na\xc3\xafve \xff\xfeok";
+
+fn config(strategy: TokenizerStrategy) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy,
+        ..TokenizerConfig::default()
+    }
+}
+
+#[test]
+fn valid_utf8_is_borrowed() {
+    for policy in [InvalidUtf8Policy::Raise, InvalidUtf8Policy::Replace, InvalidUtf8Policy::Skip] {
+        let text = decode("naïve".as_bytes(), policy).unwrap();
+        assert!(matches!(text, std::borrow::Cow::Borrowed("naïve")));
+    }
+}
+
+#[test]
+fn invalid_utf8_policies() {
+    match decode(BROKEN, InvalidUtf8Policy::Raise) {
+        Err(TokenizerError::InvalidUtf8 { position }) => assert_eq!(position, 3),
+        other => panic!("expected InvalidUtf8, got {:?}", other),
+    }
+    assert_eq!(decode(BROKEN, InvalidUtf8Policy::Replace).unwrap(), "caf\u{FFFD} naïve \u{FFFD}\u{FFFD}ok");
+    assert_eq!(decode(BROKEN, InvalidUtf8Policy::Skip).unwrap(), "caf naïve ok");
+}
+
+#[test]
+fn bytes_round_trip_through_text() {
+    let bytes: Vec<u8> = (0..=255).collect();
+    let text = decode_bytes(&bytes);
+
+    assert_eq!(text.chars().count(), 256);
+    assert_eq!(encode_bytes(&text), bytes);
+}
+
+#[test]
+fn bytes_strategy_only_touches_ascii() {
+    let tokenizer = from_config(config(TokenizerStrategy::Bytes)).unwrap();
+    let text = decode_bytes(b"GIF89a \xC0\xFF\x85DATA\xA0X\r\nEnd.");
+
+    let tokens: Vec<Vec<u8>> = tokenizer.tokenize(&text).iter().map(|t| encode_bytes(t)).collect();
+    // U+0085 and U+00A0 are Unicode whitespace but not ASCII, so they stay
+    assert_eq!(tokens, [b"gif89a".to_vec(), b"\xC0\xFF\x85data\xA0x".to_vec(), b"end.".to_vec()]);
+}
+
+#[test]
+fn bytes_strategy_rejects_preserve_patterns() {
+    let config = TokenizerConfig {
+        preserve_patterns: vec![r"\d+".to_string()],
+        ..config(TokenizerStrategy::Bytes)
+    };
+
+    assert!(matches!(
+        from_config(config),
+        Err(TokenizerError::InvalidConfiguration { key, .. }) if key == "preserve_patterns"
+    ));
+}
+
+#[test]
+fn stream_applies_the_policy_across_chunks() {
+    let tokens = |policy| {
+        let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap())
+            .with_invalid_utf8(policy);
+        let mut tokens = Vec::new();
+        for chunk in BROKEN.chunks(2) {
+            tokens.extend(stream.try_push(chunk)?);
+        }
+        tokens.extend(stream.try_finish()?);
+        Ok::<_, TokenizerError>(tokens)
+    };
+
+    assert_eq!(tokens(InvalidUtf8Policy::Skip).unwrap(), ["caf", "naïve", "ok"]);
+    assert_eq!(tokens(InvalidUtf8Policy::Replace).unwrap(), ["caf\u{FFFD}", "naïve", "\u{FFFD}\u{FFFD}ok"]);
+    assert!(matches!(
+        tokens(InvalidUtf8Policy::Raise),
+        Err(TokenizerError::InvalidUtf8 { position: 3 })
+    ));
+}
+
+#[test]
+fn stream_reports_a_truncated_last_character() {
+    let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap());
+
+    assert_eq!(stream.try_push(b"one two \xc3").unwrap(), ["one", "two"]);
+    assert!(matches!(stream.try_finish(), Err(TokenizerError::InvalidUtf8 { position: 8 })));
+}
+
+#[test]
+fn infallible_stream_methods_replace() {
+    let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap());
+    let mut tokens = stream.push(BROKEN);
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, ["caf\u{FFFD}", "naïve", "\u{FFFD}\u{FFFD}ok"]);
+}
+
+#[test]
+fn registry_keeps_each_analyzers_config() {
+    let registry = AnalyzerRegistry::new();
+    let config = TokenizerConfig {
+        invalid_utf8: InvalidUtf8Policy::Skip,
+        ..config(TokenizerStrategy::Bytes)
+    };
+    registry.register("raw", config.clone()).unwrap();
+
+    let analyzer = registry.analyzer("raw").unwrap();
+    assert_eq!(*analyzer.config, config);
+    assert_eq!(analyzer.tokenizer.tokenize("A b"), ["a", "b"]);
+}
+
+#[test]
+fn policy_is_only_serialized_when_set() {
+    let default = TokenizerConfig::default();
+    assert!(!default.to_json().unwrap().contains("invalid_utf8"));
+
+    let config = TokenizerConfig {
+        invalid_utf8: InvalidUtf8Policy::Replace,
+        ..default
+    };
+    let json = config.to_json().unwrap();
+    assert!(json.contains(r#""invalid_utf8": "replace""#), "{}", json);
+    assert_eq!(TokenizerConfig::from_json(&json).unwrap(), config);
+}
@@ -0,0 +1,327 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokenkit_core::filter;
+use tokenkit_core::script::{is_cjk, token_script, Script};
+use tokenkit_core::tokenizer::FilteredTokenizer;
+use tokenkit_core::validation::diagnose;
+use tokenkit_core::{
+    from_config, CharFilter, ConfigFormat, Token, TokenFilter, TokenStream, Tokenizer, TokenizerConfig,
+    TokenizerStrategy,
+};
+use unicode_normalization_alignments::UnicodeNormalization;
+
+fn bigrams(strategy: TokenizerStrategy, output_unigrams: bool) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        strategy,
+        filters: vec![TokenFilter::CjkBigram { output_unigrams }],
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn detects_the_script_of_a_token() {
+    assert_eq!(token_script("hello"), Some(Script::Latin));
+    assert_eq!(token_script("タワー"), Some(Script::Katakana));
+    assert_eq!(token_script("東京"), Some(Script::Han));
+    assert_eq!(token_script("한국어"), Some(Script::Hangul));
+    assert_eq!(token_script("東京タワー"), None);
+    assert_eq!(token_script("2024"), None);
+
+    assert!(is_cjk('ー') && is_cjk('ひ') && is_cjk('한'));
+    assert!(!is_cjk('1') && !is_cjk('、') && !is_cjk('a'));
+}
+
+#[test]
+fn cjk_runs_become_overlapping_bigrams() {
+    let tokenizer = bigrams(TokenizerStrategy::Unicode, false);
+
+    assert_eq!(tokenizer.tokenize("東京都庁"), vec!["東京", "京都", "都庁"]);
+    assert_eq!(tokenizer.tokenize("한국어 사전"), vec!["한국", "국어", "사전"]);
+}
+
+#[test]
+fn runs_cross_scripts_but_not_gaps() {
+    let tokenizer = bigrams(TokenizerStrategy::Unicode, false);
+
+    assert_eq!(tokenizer.tokenize("東京タワー"), vec!["東京", "京タ", "タワ", "ワー"]);
+    assert_eq!(tokenizer.tokenize("東京、大阪"), vec!["東京", "大阪"]);
+}
+
+#[test]
+fn other_tokens_pass_through() {
+    let tokenizer = bigrams(TokenizerStrategy::Unicode, false);
+
+    assert_eq!(
+        tokenizer.tokenize("Rust 2024 東京 café 日"),
+        vec!["rust", "2024", "東京", "café", "日"]
+    );
+}
+
+#[test]
+fn unigrams_come_before_the_bigram_they_start() {
+    let tokenizer = bigrams(TokenizerStrategy::Unicode, true);
+
+    assert_eq!(tokenizer.tokenize("一二三"), vec!["一", "一二", "二", "二三", "三"]);
+    assert_eq!(tokenizer.tokenize("日"), vec!["日"]);
+}
+
+#[test]
+fn bigrams_span_their_characters() {
+    let tokenizer = bigrams(TokenizerStrategy::Unicode, false);
+    let tokens = tokenizer.tokenize_with_offsets("in 東京都");
+
+    let found: Vec<_> = tokens.iter().map(|t| (t.text.as_ref(), t.start, t.end)).collect();
+    assert_eq!(found, vec![("in", 0, 2), ("東京", 3, 9), ("京都", 6, 12)]);
+}
+
+#[test]
+fn whole_cjk_tokens_of_any_strategy_are_split() {
+    let tokenizer = bigrams(TokenizerStrategy::Whitespace, false);
+
+    assert_eq!(tokenizer.tokenize("東京タワー tower"), vec!["東京", "京タ", "タワ", "ワー", "tower"]);
+}
+
+#[test]
+fn filters_round_trip_through_config_files() {
+    let config = TokenizerConfig {
+        filters: vec![TokenFilter::CjkBigram { output_unigrams: true }],
+        ..TokenizerConfig::default()
+    };
+    let json = config.dump(ConfigFormat::Json).unwrap();
+
+    assert_eq!(
+        serde_json::to_string(&config.filters).unwrap(),
+        r#"[{"type":"cjk_bigram","output_unigrams":true}]"#
+    );
+    assert_eq!(TokenizerConfig::parse(&json, ConfigFormat::Json).unwrap(), config);
+}
+
+#[test]
+fn streams_match_whole_input() {
+    let text = "東京都庁 に 行きました。 한국어 사전";
+
+    let mut stream = TokenStream::new(bigrams(TokenizerStrategy::Unicode, true));
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(5) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, bigrams(TokenizerStrategy::Unicode, true).tokenize(text));
+}
+
+// Splits on whitespace, counting the words callers have pulled
+struct CountingTokenizer {
+    pulled: Arc<AtomicUsize>,
+}
+
+impl CountingTokenizer {
+    fn words<'a>(&self, text: &'a str) -> impl Iterator<Item = Token<'a>> {
+        let pulled = Arc::clone(&self.pulled);
+        text.split_whitespace().map(move |word| {
+            pulled.fetch_add(1, Ordering::Relaxed);
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            Token::new(word, start, start + word.len())
+        })
+    }
+}
+
+impl Tokenizer for CountingTokenizer {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.words(text).collect()
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        Box::new(self.words(text))
+    }
+}
+
+#[test]
+fn filters_pull_tokens_only_as_needed() {
+    let filters = [TokenFilter::CjkBigram { output_unigrams: false }, TokenFilter::ArabicNormalization];
+    let pulled = Arc::new(AtomicUsize::new(0));
+    let tokenizer = FilteredTokenizer::new(
+        Box::new(CountingTokenizer { pulled: Arc::clone(&pulled) }),
+        filters.iter().map(filter::from_config).collect(),
+    );
+
+    let first: Vec<_> = tokenizer.tokens("one 東京都 two three four five").take(3).map(|token| token.text).collect();
+
+    assert_eq!(first, ["one", "東京", "京都"]);
+    // The bigram filter reads one word past the run to see where it ends
+    assert_eq!(pulled.load(Ordering::Relaxed), 3);
+}
+
+fn with_filters(filters: Vec<TokenFilter>) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        filters,
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn arabic_normalization_folds_variants_and_removes_diacritics() {
+    let tokenizer = with_filters(vec![TokenFilter::ArabicNormalization]);
+
+    assert_eq!(tokenizer.tokenize("أحمد إسلام آمن"), ["احمد", "اسلام", "امن"]);
+    assert_eq!(tokenizer.tokenize("مكتبة مستشفى"), ["مكتبه", "مستشفي"]);
+    assert_eq!(tokenizer.tokenize("كتـــاب مُحَمَّد"), ["كتاب", "محمد"]);
+    // A token of tatweel alone is left empty, and dropped
+    assert_eq!(tokenizer.tokenize("ـــ كتاب"), ["كتاب"]);
+}
+
+#[test]
+fn persian_normalization_folds_letter_forms() {
+    let tokenizer = with_filters(vec![TokenFilter::ArabicNormalization, TokenFilter::PersianNormalization]);
+
+    assert_eq!(tokenizer.tokenize("کتاب فارسی خانۀ"), ["كتاب", "فارسي", "خانه"]);
+}
+
+#[test]
+fn hebrew_normalization_strips_niqqud() {
+    let tokenizer = with_filters(vec![TokenFilter::HebrewNormalization]);
+
+    assert_eq!(tokenizer.tokenize("שָׁלוֹם עוֹלָם"), ["שלום", "עולם"]);
+    assert_eq!(tokenizer.tokenize("שלום"), ["שלום"]);
+}
+
+#[test]
+fn arabic_stem_strips_light_affixes() {
+    let tokenizer = with_filters(vec![TokenFilter::ArabicStem]);
+
+    assert_eq!(
+        tokenizer.tokenize("الحسن والحسن بالحسن كالحسن فالحسن للاخر وحسن"),
+        ["حسن", "حسن", "حسن", "حسن", "حسن", "اخر", "حسن"]
+    );
+    assert_eq!(tokenizer.tokenize("ساهدها ساهدان ساهدات ساهدون ساهدين"), ["ساهد"; 5]);
+    // Too short to lose an affix
+    assert_eq!(tokenizer.tokenize("الم وحد"), ["الم", "وحد"]);
+}
+
+#[test]
+fn indic_normalization_composes_and_removes_joiners() {
+    let tokenizer = with_filters(vec![TokenFilter::IndicNormalization]);
+
+    // Letters with a nukta are decomposed, as NFC requires
+    assert_eq!(tokenizer.tokenize("\u{958}ुरान"), ["\u{915}\u{93C}ुरान"]);
+    // Bengali and Tamil split vowel signs are composed
+    assert_eq!(tokenizer.tokenize("ক\u{9C7}\u{9BE}ন"), ["ক\u{9CB}ন"]);
+    assert_eq!(tokenizer.tokenize("க\u{BC6}\u{BBE}டு"), ["க\u{BCA}டு"]);
+    // Joiners and doubled viramas go, and split independent vowels are joined
+    assert_eq!(tokenizer.tokenize("क्\u{200D}ष"), ["क्ष"]);
+    assert_eq!(tokenizer.tokenize("क्\u{94D}ष"), ["क्ष"]);
+    assert_eq!(tokenizer.tokenize("अ\u{93E}म"), ["आम"]);
+    // Other scripts are left alone, joiners included
+    assert_eq!(tokenizer.tokenize("می\u{200C}خواهم"), ["می\u{200C}خواهم"]);
+}
+
+#[test]
+fn arabic_filters_run_in_order_and_keep_offsets() {
+    let tokenizer = with_filters(vec![TokenFilter::ArabicNormalization, TokenFilter::ArabicStem]);
+    let text = "قرأ والمكتبة";
+
+    let tokens = tokenizer.tokenize_with_offsets(text);
+    let stems: Vec<_> = tokens.iter().map(|token| token.text.as_ref()).collect();
+    assert_eq!(stems, ["قرا", "مكتب"]);
+    assert_eq!(&text[tokens[1].start..tokens[1].end], "والمكتبة");
+}
+
+fn width_folded(char_filters: Vec<CharFilter>, filters: Vec<TokenFilter>) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        char_filters,
+        filters,
+        preserve_patterns: vec![r"\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn cjk_width_folds_only_width_variants() {
+    let tokenizer = with_filters(vec![TokenFilter::CjkWidth]);
+
+    assert_eq!(tokenizer.tokenize("ｉＰｈｏｎｅ１５ ｶﾀｶﾅ"), ["iphone15", "カタカナ"]);
+    // Voiced sound marks join the kana before them
+    assert_eq!(tokenizer.tokenize("ｶﾞｲﾄﾞﾌﾞｯｸ ﾊﾟﾝ"), ["ガイドブック", "パン"]);
+    assert_eq!(tokenizer.tokenize("ﾊﾝｸﾞﾙ"), ["ハングル"]);
+    // Other compatibility characters keep their meaning
+    assert_eq!(tokenizer.tokenize("½ ① ﬁ"), ["½", "①", "ﬁ"]);
+
+    // Every width variant folds to a character NFKC treats the same
+    let keyword = from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Keyword,
+        lowercase: false,
+        filters: vec![TokenFilter::CjkWidth],
+        ..TokenizerConfig::default()
+    })
+    .unwrap();
+    let nfkc = |text: &str| text.nfkc().map(|(c, _)| c).collect::<String>();
+    for c in '\u{FF01}'..='\u{FFEE}' {
+        for folded in keyword.tokenize(&c.to_string()) {
+            assert_eq!(folded.chars().count(), 1, "{:?}", c);
+            assert_eq!(nfkc(&folded), nfkc(&c.to_string()), "{:?}", c);
+        }
+    }
+}
+
+#[test]
+fn cjk_width_as_a_char_filter_changes_what_the_strategy_sees() {
+    let text = "ﾊﾞｯｸﾞ１００ｍｇ　ＵＳＢ－Ｃ";
+
+    // Folded first, the dose matches the preserve pattern and the hyphen joins
+    let tokenizer = width_folded(vec![CharFilter::CjkWidth], Vec::new());
+    let tokens = tokenizer.tokenize_with_offsets(text);
+    let words: Vec<_> = tokens.iter().map(|token| token.text.as_ref()).collect();
+    assert_eq!(words, ["バッグ", "100mg", "usb-c"]);
+    // Offsets point into the original input
+    assert_eq!(&text[tokens[0].start..tokens[0].end], "ﾊﾞｯｸﾞ");
+    assert_eq!(&text[tokens[1].start..tokens[1].end], "１００ｍｇ");
+    assert_eq!(&text[tokens[2].start..tokens[2].end], "ＵＳＢ－Ｃ");
+
+    // As a token filter it only folds the tokens the strategy made
+    let tokenizer = width_folded(Vec::new(), vec![TokenFilter::CjkWidth]);
+    assert_eq!(tokenizer.tokenize("１００ｍｇ"), ["100mg"]);
+    assert_eq!(tokenizer.tokenize_with_offsets("１００ｍｇ")[0].end, "１００ｍｇ".len());
+
+    // Input without width variants is tokenized as it is
+    let tokenizer = width_folded(vec![CharFilter::CjkWidth], Vec::new());
+    assert_eq!(tokenizer.tokenize("plain 20mg"), ["plain", "20mg"]);
+}
+
+#[test]
+fn char_filters_stream_and_round_trip() {
+    let config = TokenizerConfig {
+        char_filters: vec![CharFilter::CjkWidth],
+        filters: vec![TokenFilter::CjkBigram { output_unigrams: false }],
+        ..TokenizerConfig::default()
+    };
+    let text = "ｶﾀｶﾅ ＡＢＣ ﾃｽﾄ";
+
+    let mut stream = TokenStream::new(from_config(config.clone()).unwrap());
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(4) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+    assert_eq!(tokens, ["カタ", "タカ", "カナ", "abc", "テス", "スト"]);
+
+    // Each bigram points at its own two characters of the input
+    let tokens = from_config(config.clone()).unwrap().tokenize_with_offsets(text);
+    let sources: Vec<_> = tokens.iter().map(|token| &text[token.start..token.end]).collect();
+    assert_eq!(sources, ["ｶﾀ", "ﾀｶ", "ｶﾅ", "ＡＢＣ", "ﾃｽ", "ｽﾄ"]);
+    let tokens = from_config(config.clone()).unwrap().tokenize_with_offsets("ｶﾞｲﾄﾞ");
+    let sources: Vec<_> = tokens.iter().map(|token| &"ｶﾞｲﾄﾞ"[token.start..token.end]).collect();
+    assert_eq!(sources, ["ｶﾞｲ", "ｲﾄﾞ"]);
+
+    let yaml = config.dump(ConfigFormat::Yaml).unwrap();
+    assert!(yaml.contains("char_filters"), "{}", yaml);
+    assert_eq!(TokenizerConfig::parse(&yaml, ConfigFormat::Yaml).unwrap(), config);
+
+    let config = TokenizerConfig {
+        char_filters: vec![CharFilter::CjkWidth],
+        ..TokenizerConfig::default()
+    };
+    assert!(diagnose(&config).is_empty());
+}
@@ -0,0 +1,206 @@
+use serde_json::Value;
+use std::path::PathBuf;
+use tokenkit_core::huggingface::HuggingFaceTokenizer;
+use tokenkit_core::{from_config, Tokenizer, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/huggingface").join(name)
+}
+
+fn load_error(json: &str) -> String {
+    match HuggingFaceTokenizer::from_json(json) {
+        Err(TokenizerError::InvalidHuggingFaceTokenizer(message)) => message,
+        Err(other) => panic!("expected InvalidHuggingFaceTokenizer, got {:?}", other),
+        Ok(_) => panic!("expected an error"),
+    }
+}
+
+// Compares every input of a fixture against the reference implementation's output
+fn assert_matches_reference(name: &str) {
+    let tokenizer = HuggingFaceTokenizer::from_file(fixture(name).join("tokenizer.json")).unwrap();
+    let expected: Vec<Value> =
+        serde_json::from_str(&std::fs::read_to_string(fixture(name).join("expected.json")).unwrap()).unwrap();
+
+    for case in expected {
+        let text = case["text"].as_str().unwrap();
+        let encoding = tokenizer.encode(text, true);
+        let offsets: Vec<(usize, usize)> = serde_json::from_value(case["offsets"].clone()).unwrap();
+
+        assert_eq!(encoding.tokens, serde_json::from_value::<Vec<String>>(case["tokens"].clone()).unwrap(), "{}: tokens of {:?}", name, text);
+        assert_eq!(encoding.ids, serde_json::from_value::<Vec<u32>>(case["ids"].clone()).unwrap(), "{}: ids of {:?}", name, text);
+        assert_eq!(encoding.offsets, offsets, "{}: offsets of {:?}", name, text);
+        assert_eq!(tokenizer.decode(&encoding.ids, true), case["decoded"].as_str().unwrap(), "{}: decoding {:?}", name, text);
+    }
+}
+
+#[test]
+fn wordpiece_matches_reference() {
+    assert_matches_reference("bert");
+}
+
+#[test]
+fn byte_level_bpe_matches_reference() {
+    assert_matches_reference("roberta");
+}
+
+#[test]
+fn unigram_matches_reference() {
+    assert_matches_reference("unigram");
+}
+
+#[test]
+fn byte_fallback_bpe_matches_reference() {
+    assert_matches_reference("llama");
+}
+
+#[test]
+fn split_regex_bpe_matches_reference() {
+    assert_matches_reference("llama3");
+}
+
+#[test]
+fn suffix_bpe_matches_reference() {
+    assert_matches_reference("suffix_bpe");
+}
+
+#[test]
+fn special_tokens_are_optional() {
+    let tokenizer = HuggingFaceTokenizer::from_file(fixture("bert/tokenizer.json")).unwrap();
+
+    let encoding = tokenizer.encode("Hello, world!", false);
+    assert_eq!(encoding.tokens, ["hello", ",", "world", "!"]);
+    assert_eq!(tokenizer.decode(&tokenizer.encode("Hello", true).ids, false), "[CLS] hello [SEP]");
+}
+
+#[test]
+fn vocabulary_lookup() {
+    let tokenizer = HuggingFaceTokenizer::from_file(fixture("bert/tokenizer.json")).unwrap();
+
+    assert_eq!(tokenizer.token_to_id("[CLS]"), Some(2));
+    assert_eq!(tokenizer.id_to_token(2), Some("[CLS]"));
+    assert_eq!(tokenizer.token_to_id("hello").and_then(|id| tokenizer.id_to_token(id)), Some("hello"));
+    assert_eq!(tokenizer.token_to_id("not-a-token"), None);
+}
+
+#[test]
+fn tokenizer_trait_uses_model_tokens_and_offsets() {
+    let tokenizer = HuggingFaceTokenizer::from_file(fixture("roberta/tokenizer.json"))
+        .unwrap()
+        .with_special_tokens(false);
+
+    let tokens = tokenizer.tokenize_with_offsets("Hello world");
+    let pieces: Vec<(&str, usize, usize)> = tokens.iter().map(|t| (t.text.as_ref(), t.start, t.end)).collect();
+    assert_eq!(pieces.first().map(|p| p.1), Some(0));
+    assert!(pieces.iter().any(|p| p.0.starts_with('Ġ')));
+    assert_eq!(pieces.last().map(|p| p.2), Some(11));
+}
+
+#[test]
+fn strategy_loads_the_file() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::HuggingFace {
+            path: fixture("bert/tokenizer.json").display().to_string(),
+            add_special_tokens: false,
+        },
+        ..TokenizerConfig::default()
+    };
+
+    assert_eq!(from_config(config).unwrap().tokenize("Hello, world!"), ["hello", ",", "world", "!"]);
+}
+
+#[test]
+fn strategy_rejects_preserve_patterns() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::HuggingFace {
+            path: fixture("bert/tokenizer.json").display().to_string(),
+            add_special_tokens: true,
+        },
+        preserve_patterns: vec![r"\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    };
+
+    assert!(matches!(from_config(config), Err(TokenizerError::InvalidConfiguration { .. })));
+}
+
+#[test]
+fn strategy_rejects_remove_punctuation() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::HuggingFace {
+            path: fixture("bert/tokenizer.json").display().to_string(),
+            add_special_tokens: true,
+        },
+        remove_punctuation: true,
+        ..TokenizerConfig::default()
+    };
+
+    match from_config(config) {
+        Err(TokenizerError::InvalidConfiguration { key, .. }) => assert_eq!(key, "remove_punctuation"),
+        other => panic!("expected InvalidConfiguration, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn strategy_ignores_lowercase() {
+    let path = fixture("roberta/tokenizer.json");
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::HuggingFace {
+            path: path.display().to_string(),
+            add_special_tokens: false,
+        },
+        lowercase: true,
+        ..TokenizerConfig::default()
+    };
+
+    let encoding = HuggingFaceTokenizer::from_file(path).unwrap().encode("Hello", false);
+    assert_eq!(from_config(config).unwrap().tokenize("Hello"), encoding.tokens);
+}
+
+#[test]
+fn missing_file_is_reported() {
+    let error = HuggingFaceTokenizer::from_file(fixture("missing/tokenizer.json")).err().unwrap();
+    assert!(error.to_string().contains("cannot read"));
+}
+
+#[test]
+fn unsupported_components_are_reported_by_name() {
+    let message = load_error(
+        r#"{
+          "truncation": {"max_length": 512},
+          "padding": null,
+          "added_tokens": [],
+          "normalizer": {"type": "Precompiled", "precompiled_charsmap": ""},
+          "pre_tokenizer": {"type": "Sequence", "pretokenizers": [
+            {"type": "WhitespaceSplit"},
+            {"type": "UnicodeScripts"}
+          ]},
+          "model": {"type": "WordLevel", "vocab": {}, "unk_token": "[UNK]"},
+          "post_processor": null,
+          "decoder": {"type": "CTC"}
+        }"#,
+    );
+
+    assert_eq!(
+        message,
+        "truncation: truncation is not supported; \
+         normalizer: unsupported normalizer 'Precompiled'; \
+         pre_tokenizer.pretokenizers[1]: unsupported pre-tokenizer 'UnicodeScripts'; \
+         model: unsupported model 'WordLevel'; \
+         decoder: unsupported decoder 'CTC'"
+    );
+}
+
+#[test]
+fn invalid_model_is_reported() {
+    let message = load_error(
+        r#"{
+          "model": {"type": "BPE", "vocab": {"a": 0, "b": 1}, "merges": ["a b"], "dropout": 0.1, "unk_token": "<unk>"}
+        }"#,
+    );
+    assert_eq!(message, "model.dropout: BPE dropout is not supported, it makes tokenization random");
+
+    let message = load_error(r#"{"model": {"type": "BPE", "vocab": {"a": 0, "b": 1}, "merges": ["a b"]}}"#);
+    assert_eq!(message, "model.merges[0]: 'ab' is not in the vocabulary");
+
+    assert_eq!(load_error("[]"), "expected a JSON object");
+    assert_eq!(load_error("{}"), "model: missing");
+}
@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+use tokenkit_core::japanese::{Dictionary, JapaneseTokenizer};
+use tokenkit_core::{from_config, TokenAttributes, TokenStream, Tokenizer, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn fixture(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/japanese")
+        .join(name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn japanese(user_dictionaries: &[&str]) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Japanese {
+            dictionary: fixture("ipadic"),
+            user_dictionaries: user_dictionaries.iter().map(|name| fixture(name)).collect(),
+        },
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+fn attributes(part_of_speech: &str, base_form: Option<&str>, reading: Option<&str>) -> TokenAttributes {
+    TokenAttributes {
+        part_of_speech: part_of_speech.to_string(),
+        base_form: base_form.map(str::to_string),
+        reading: reading.map(str::to_string),
+    }
+}
+
+#[test]
+fn takes_the_cheapest_path_through_the_lattice() {
+    assert_eq!(
+        japanese(&[]).tokenize("すもももももももものうち"),
+        vec!["すもも", "も", "もも", "も", "もも", "の", "うち"]
+    );
+}
+
+#[test]
+fn tokens_carry_part_of_speech_base_form_and_reading() {
+    let tokens = japanese(&[]).tokenize_with_offsets("東京で食べた");
+
+    let found: Vec<_> = tokens.iter().map(|t| (t.text.as_ref(), t.start, t.end)).collect();
+    assert_eq!(found, vec![("東京", 0, 6), ("で", 6, 9), ("食べ", 9, 15), ("た", 15, 18)]);
+    assert_eq!(
+        tokens[0].attributes.as_deref(),
+        Some(&attributes("名詞-固有名詞-地域-一般", Some("東京"), Some("トウキョウ")))
+    );
+    assert_eq!(
+        tokens[2].attributes.as_deref(),
+        Some(&attributes("動詞-自立", Some("食べる"), Some("タベ")))
+    );
+}
+
+#[test]
+fn unknown_words_are_grouped_by_character_category() {
+    let tokens = japanese(&[]).tokenize_with_offsets("東京タワーでRust2024");
+
+    let texts: Vec<_> = tokens.iter().map(|t| t.text.as_ref()).collect();
+    assert_eq!(texts, vec!["東京", "タワー", "で", "rust", "2024"]);
+    assert_eq!(tokens[1].attributes.as_deref(), Some(&attributes("名詞-一般", None, None)));
+    assert_eq!(tokens[4].attributes.as_deref(), Some(&attributes("名詞-数", None, None)));
+}
+
+#[test]
+fn punctuation_and_whitespace_are_left_out() {
+    assert_eq!(japanese(&[]).tokenize("東京、 食べた。"), vec!["東京", "食べ", "た"]);
+}
+
+#[test]
+fn user_dictionary_words_are_segmented_as_given() {
+    let tokens = japanese(&["user.csv"]).tokenize_with_offsets("関西国際空港に行った");
+
+    let texts: Vec<_> = tokens.iter().map(|t| t.text.as_ref()).collect();
+    assert_eq!(texts, vec!["関西", "国際", "空港", "に", "行っ", "た"]);
+    assert_eq!((tokens[1].start, tokens[1].end), (6, 12));
+    assert_eq!(
+        tokens[1].attributes.as_deref(),
+        Some(&attributes("カスタム名詞", Some("国際"), Some("コクサイ")))
+    );
+    assert_eq!(tokens[4].attributes.as_ref().unwrap().base_form.as_deref(), Some("行く"));
+}
+
+#[test]
+fn a_loaded_dictionary_can_be_shared() {
+    let dictionary = std::sync::Arc::new(Dictionary::load(fixture("ipadic")).unwrap());
+    let first = JapaneseTokenizer::with_dictionary(TokenizerConfig::default(), dictionary.clone(), &[]).unwrap();
+    let second = JapaneseTokenizer::with_dictionary(TokenizerConfig::default(), dictionary, &[]).unwrap();
+
+    assert_eq!(first.tokenize("食べた"), second.tokenize("食べた"));
+}
+
+#[test]
+fn broken_dictionaries_name_the_file() {
+    let missing = from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Japanese {
+            dictionary: fixture("missing"),
+            user_dictionaries: Vec::new(),
+        },
+        ..TokenizerConfig::default()
+    });
+    match missing {
+        Err(TokenizerError::InvalidDictionary { path, .. }) => assert!(path.ends_with("matrix.def"), "{}", path),
+        other => panic!("expected InvalidDictionary, got {:?}", other.map(|_| ())),
+    }
+
+    let broken = from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Japanese {
+            dictionary: fixture("ipadic"),
+            user_dictionaries: vec![fixture("broken_user.csv")],
+        },
+        ..TokenizerConfig::default()
+    });
+    match broken {
+        Err(TokenizerError::InvalidDictionary { path, message }) => {
+            assert!(path.ends_with("broken_user.csv"));
+            assert_eq!(message, "line 1: segments don't add up to the surface");
+        }
+        other => panic!("expected InvalidDictionary, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn empty_dictionary_path_is_a_configuration_error() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Japanese {
+            dictionary: String::new(),
+            user_dictionaries: Vec::new(),
+        },
+        ..TokenizerConfig::default()
+    };
+
+    assert!(matches!(from_config(config), Err(TokenizerError::InvalidConfiguration { key, .. }) if key == "dictionary"));
+}
+
+#[test]
+fn streams_split_at_sentence_ends() {
+    let text = "東京で食べた。すもももももももものうち";
+
+    let mut stream = TokenStream::new(japanese(&[]));
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(4) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, japanese(&[]).tokenize(text));
+}
+
+#[test]
+fn attributes_are_serialized_with_the_token() {
+    let tokens = japanese(&[]).tokenize_with_offsets("食べ");
+    let json = serde_json::to_string(&tokens[0]).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"text":"食べ","start":0,"end":6,"part_of_speech":"動詞-自立","base_form":"食べる","reading":"タベ"}"#
+    );
+}
@@ -0,0 +1,32 @@
+use tokenkit_core::language::{detect_language, parse_language, supported_languages};
+
+#[test]
+fn detects_languages_with_confidence() {
+    for (text, code) in [
+        ("The quick brown fox jumps over the lazy dog", "eng"),
+        ("Le renard brun rapide saute par-dessus le chien paresseux", "fra"),
+        ("El rápido zorro marrón salta sobre el perro perezoso", "spa"),
+        ("Быстрая коричневая лиса прыгает через ленивую собаку", "rus"),
+        ("素早い茶色の狐がのろまな犬を飛び越える", "jpn"),
+    ] {
+        let language = detect_language(text).unwrap();
+        assert_eq!(language.code, code, "{}", text);
+        assert!(language.confidence > 0.0 && language.confidence <= 1.0);
+    }
+}
+
+#[test]
+fn short_text_is_not_reliable() {
+    let language = detect_language("ok").unwrap();
+    assert!(!language.reliable, "{:?}", language);
+    assert_eq!(detect_language("123 !?"), None);
+}
+
+#[test]
+fn parses_language_names_and_codes() {
+    assert_eq!(parse_language("deu"), Some("deu"));
+    assert_eq!(parse_language("German"), Some("deu"));
+    assert_eq!(parse_language("MANDARIN"), Some("cmn"));
+    assert_eq!(parse_language("klingon"), None);
+    assert!(supported_languages().count() >= 50);
+}
@@ -0,0 +1,350 @@
+use tokenkit_core::{
+    from_config, Limits, LongTokenPolicy, OverflowPolicy, RegexLimits, TokenStream, Tokenizer, TokenizerConfig,
+    TokenizerError, TokenizerStrategy,
+};
+
+fn limited(strategy: TokenizerStrategy, limits: Limits) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        strategy,
+        limits,
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+fn limit_error(result: tokenkit_core::Result<Vec<std::borrow::Cow<'_, str>>>) -> (String, usize) {
+    match result {
+        Err(TokenizerError::LimitExceeded { limit, max }) => (limit, max),
+        other => panic!("expected LimitExceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn input_over_max_bytes() {
+    let limits = Limits {
+        max_input_bytes: Some(10),
+        ..Limits::default()
+    };
+    let tokenizer = limited(TokenizerStrategy::Whitespace, limits.clone());
+
+    assert_eq!(tokenizer.try_tokenize("short text").unwrap(), ["short", "text"]);
+    assert_eq!(limit_error(tokenizer.try_tokenize("a little longer")), ("max_input_bytes".to_string(), 10));
+
+    // Truncation cuts at a token boundary rather than mid-token
+    let tokenizer = limited(
+        TokenizerStrategy::Whitespace,
+        Limits {
+            overflow: OverflowPolicy::Truncate,
+            ..limits
+        },
+    );
+    assert_eq!(tokenizer.try_tokenize("a little longer").unwrap(), ["a", "little"]);
+}
+
+#[test]
+fn input_truncation_respects_char_boundaries() {
+    let tokenizer = limited(
+        TokenizerStrategy::Keyword,
+        Limits {
+            max_input_bytes: Some(4),
+            overflow: OverflowPolicy::Truncate,
+            ..Limits::default()
+        },
+    );
+
+    assert_eq!(tokenizer.try_tokenize("añé").unwrap(), ["añ"]);
+}
+
+#[test]
+fn token_count_over_max_tokens() {
+    let limits = Limits {
+        max_tokens: Some(3),
+        ..Limits::default()
+    };
+    let tokenizer = limited(TokenizerStrategy::Whitespace, limits.clone());
+
+    assert_eq!(tokenizer.try_tokenize("one two three").unwrap(), ["one", "two", "three"]);
+    assert_eq!(limit_error(tokenizer.try_tokenize("one two three four")), ("max_tokens".to_string(), 3));
+
+    let tokenizer = limited(
+        TokenizerStrategy::Whitespace,
+        Limits {
+            overflow: OverflowPolicy::Truncate,
+            ..limits
+        },
+    );
+    assert_eq!(tokenizer.try_tokenize("one two three four").unwrap(), ["one", "two", "three"]);
+}
+
+#[test]
+fn ngrams_stop_at_max_tokens() {
+    let tokenizer = limited(
+        TokenizerStrategy::Ngram { min_gram: 1, max_gram: 10 },
+        Limits {
+            max_tokens: Some(5),
+            ..Limits::default()
+        },
+    );
+
+    // A megabyte of text would produce tens of millions of n-grams; the
+    // limit is hit after the sixth instead
+    let text = "abcdefghij".repeat(100_000);
+    let mut tokens = tokenizer.try_tokens(&text);
+    assert_eq!(tokens.by_ref().take(5).filter(|token| token.is_ok()).count(), 5);
+    assert!(matches!(tokens.next(), Some(Err(TokenizerError::LimitExceeded { .. }))));
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn tokens_over_max_token_length() {
+    let limits = Limits {
+        max_token_length: Some(4),
+        ..Limits::default()
+    };
+    let tokenizer = limited(TokenizerStrategy::Whitespace, limits.clone());
+
+    let tokens = tokenizer.try_tokenize_with_offsets("tiny enormous größer").unwrap();
+    let pieces: Vec<(&str, usize, usize)> = tokens.iter().map(|t| (t.text.as_ref(), t.start, t.end)).collect();
+    assert_eq!(pieces, [("tiny", 0, 4), ("enor", 5, 13), ("größ", 14, 22)]);
+
+    let tokenizer = limited(
+        TokenizerStrategy::Whitespace,
+        Limits {
+            long_tokens: LongTokenPolicy::Drop,
+            ..limits
+        },
+    );
+    assert_eq!(tokenizer.try_tokenize("tiny enormous größer").unwrap(), ["tiny"]);
+}
+
+#[test]
+fn dropped_tokens_do_not_count_towards_max_tokens() {
+    let tokenizer = limited(
+        TokenizerStrategy::Whitespace,
+        Limits {
+            max_tokens: Some(2),
+            max_token_length: Some(3),
+            long_tokens: LongTokenPolicy::Drop,
+            ..Limits::default()
+        },
+    );
+
+    assert_eq!(tokenizer.try_tokenize("a enormous b").unwrap(), ["a", "b"]);
+}
+
+#[test]
+fn infallible_methods_truncate() {
+    let tokenizer = limited(
+        TokenizerStrategy::Whitespace,
+        Limits {
+            max_tokens: Some(2),
+            ..Limits::default()
+        },
+    );
+
+    assert_eq!(tokenizer.tokenize("one two three"), ["one", "two"]);
+    assert_eq!(tokenizer.tokens("one two three").count(), 2);
+}
+
+fn limited_stream(limits: Limits) -> TokenStream {
+    TokenStream::new(limited(TokenizerStrategy::Whitespace, limits.clone())).with_limits(limits)
+}
+
+fn stream_limit_error(result: tokenkit_core::Result<Vec<String>>) -> (String, usize) {
+    match result {
+        Err(TokenizerError::LimitExceeded { limit, max }) => (limit, max),
+        other => panic!("expected LimitExceeded, got {:?}", other),
+    }
+}
+
+#[test]
+fn streams_count_tokens_across_chunks() {
+    let limits = Limits {
+        max_tokens: Some(3),
+        ..Limits::default()
+    };
+
+    let mut stream = limited_stream(limits.clone());
+    let mut result = Ok(Vec::new());
+    for _ in 0..5 {
+        result = stream.try_push(b"a b c d e ");
+        if result.is_err() {
+            break;
+        }
+    }
+    assert_eq!(stream_limit_error(result), ("max_tokens".to_string(), 3));
+
+    let mut stream = limited_stream(limits.clone());
+    assert_eq!(stream.try_push(b"a b ").unwrap(), ["a", "b"]);
+    assert_eq!(stream_limit_error(stream.try_push(b"c d ")), ("max_tokens".to_string(), 3));
+    // The rest of the input is ignored
+    assert!(stream.try_push(b"e ").unwrap().is_empty());
+    assert!(stream.try_finish().unwrap().is_empty());
+
+    // Exactly max_tokens is fine, and finishing starts the count again
+    assert_eq!(stream.try_push(b"a b ").unwrap(), ["a", "b"]);
+    assert_eq!(stream.try_push(b"c").unwrap(), Vec::<String>::new());
+    assert_eq!(stream.try_finish().unwrap(), ["c"]);
+
+    let mut stream = limited_stream(Limits {
+        overflow: OverflowPolicy::Truncate,
+        ..limits.clone()
+    });
+    let mut tokens = stream.try_push(b"a b ").unwrap();
+    tokens.extend(stream.try_push(b"c d ").unwrap());
+    tokens.extend(stream.try_finish().unwrap());
+    assert_eq!(tokens, ["a", "b", "c"]);
+
+    // The infallible methods always truncate
+    let mut stream = limited_stream(limits);
+    let mut tokens = stream.push(b"a b ");
+    tokens.extend(stream.push(b"c d "));
+    tokens.extend(stream.finish());
+    assert_eq!(tokens, ["a", "b", "c"]);
+}
+
+#[test]
+fn streams_count_input_bytes_across_chunks() {
+    let limits = Limits {
+        max_input_bytes: Some(10),
+        ..Limits::default()
+    };
+
+    let mut stream = limited_stream(limits.clone());
+    assert_eq!(stream.try_push(b"short ").unwrap(), ["short"]);
+    assert_eq!(stream_limit_error(stream.try_push(b"text more")), ("max_input_bytes".to_string(), 10));
+    assert!(stream.try_finish().unwrap().is_empty());
+
+    // Truncation cuts where the whole input would be cut
+    let limits = Limits {
+        overflow: OverflowPolicy::Truncate,
+        ..limits
+    };
+    for text in ["short text more", "a little longer", "tiny bits of it"] {
+        let mut tokens = Vec::new();
+        let mut stream = limited_stream(limits.clone());
+        for chunk in text.as_bytes().chunks(4) {
+            tokens.extend(stream.try_push(chunk).unwrap());
+        }
+        tokens.extend(stream.try_finish().unwrap());
+
+        let whole = limited(TokenizerStrategy::Whitespace, limits.clone()).try_tokenize(text).unwrap();
+        assert_eq!(tokens, whole, "{:?}", text);
+    }
+}
+
+#[test]
+fn zero_limits_are_rejected() {
+    let config = TokenizerConfig {
+        limits: Limits {
+            max_tokens: Some(0),
+            ..Limits::default()
+        },
+        ..TokenizerConfig::default()
+    };
+
+    assert!(matches!(from_config(config), Err(TokenizerError::InvalidConfiguration { .. })));
+}
+
+#[test]
+fn limits_in_config_files() {
+    let config = TokenizerConfig::from_yaml(
+        "version: 1\ntokenizer:\n  limits:\n    max_tokens: 100\n    overflow: truncate\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.limits,
+        Limits {
+            max_tokens: Some(100),
+            overflow: OverflowPolicy::Truncate,
+            ..Limits::default()
+        }
+    );
+    assert_eq!(TokenizerConfig::from_json(&config.to_json().unwrap()).unwrap(), config);
+
+    // Unlimited configs are written without a limits section
+    assert!(!TokenizerConfig::default().to_json().unwrap().contains("limits"));
+}
+
+#[test]
+fn regex_over_size_limit() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Pattern {
+            regex: r"\w{50}".to_string(),
+        },
+        regex_limits: RegexLimits {
+            size_limit: Some(10_000),
+            ..RegexLimits::default()
+        },
+        ..TokenizerConfig::default()
+    };
+
+    match from_config(config) {
+        Err(TokenizerError::RegexLimitExceeded { pattern, limit, max }) => {
+            assert_eq!((pattern.as_str(), limit.as_str(), max), (r"\w{50}", "size_limit", 10_000));
+        }
+        other => panic!("expected RegexLimitExceeded, got {:?}", other.err()),
+    }
+
+    // The same pattern compiles with the default limit
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Pattern {
+            regex: r"\w{50}".to_string(),
+        },
+        ..TokenizerConfig::default()
+    };
+    assert!(from_config(config).is_ok());
+}
+
+#[test]
+fn preserve_patterns_use_regex_limits() {
+    let config = TokenizerConfig {
+        preserve_patterns: vec![r"\d+mg".to_string(), r"\w{50}".to_string()],
+        regex_limits: RegexLimits {
+            size_limit: Some(10_000),
+            dfa_size_limit: Some(100_000),
+            ..RegexLimits::default()
+        },
+        ..TokenizerConfig::default()
+    };
+
+    let error = from_config(config).err().unwrap();
+    assert_eq!(error.to_string(), r"Regex pattern '\w{50}' exceeds size_limit (10000)");
+}
+
+#[test]
+fn too_many_preserve_patterns() {
+    let config = TokenizerConfig {
+        preserve_patterns: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        regex_limits: RegexLimits {
+            max_preserve_patterns: Some(2),
+            ..RegexLimits::default()
+        },
+        ..TokenizerConfig::default()
+    };
+
+    match from_config(config) {
+        Err(TokenizerError::RegexLimitExceeded { pattern, limit, max }) => {
+            assert_eq!((pattern.as_str(), limit.as_str(), max), ("c", "max_preserve_patterns", 2));
+        }
+        other => panic!("expected RegexLimitExceeded, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn regex_limits_in_config_files() {
+    let config = TokenizerConfig::from_yaml(
+        "version: 1\ntokenizer:\n  regex_limits:\n    size_limit: 100000\n    max_preserve_patterns: 20\n",
+    )
+    .unwrap();
+    assert_eq!(
+        config.regex_limits,
+        RegexLimits {
+            size_limit: Some(100_000),
+            dfa_size_limit: None,
+            max_preserve_patterns: Some(20),
+        }
+    );
+    assert_eq!(TokenizerConfig::from_json(&config.to_json().unwrap()).unwrap(), config);
+    assert!(!TokenizerConfig::default().to_json().unwrap().contains("regex_limits"));
+}
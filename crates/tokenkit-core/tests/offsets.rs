@@ -0,0 +1,93 @@
+use tokenkit_core::{from_config, Token, TokenizerConfig, TokenizerStrategy};
+
+fn offsets(config: TokenizerConfig, text: &str) -> Vec<(String, usize, usize)> {
+    from_config(config)
+        .unwrap()
+        .tokenize_with_offsets(text)
+        .into_iter()
+        .map(|Token { text, start, end, .. }| (text.into_owned(), start, end))
+        .collect()
+}
+
+fn with_strategy(strategy: TokenizerStrategy) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy,
+        ..TokenizerConfig::default()
+    }
+}
+
+#[test]
+fn borrowed_tokens_point_at_their_source() {
+    let text = "  Héllo, wörld! 世界 ";
+    for strategy in [
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Letter,
+        TokenizerStrategy::Lowercase,
+        TokenizerStrategy::Keyword,
+        TokenizerStrategy::Sentence,
+        TokenizerStrategy::Grapheme { extended: true },
+        TokenizerStrategy::Pattern { regex: r"\w+".to_string() },
+        TokenizerStrategy::CharGroup { split_on_chars: ",!".to_string() },
+        TokenizerStrategy::UrlEmail,
+    ] {
+        let config = TokenizerConfig {
+            lowercase: false,
+            ..with_strategy(strategy.clone())
+        };
+        for (token, start, end) in offsets(config, text) {
+            assert_eq!(token.to_lowercase(), text[start..end].to_lowercase(), "{:?}", strategy);
+        }
+    }
+}
+
+#[test]
+fn filtered_tokens_keep_original_offsets() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Whitespace,
+        remove_punctuation: true,
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(
+        offsets(config, "Hello, World!"),
+        [("hello".to_string(), 0, 6), ("world".to_string(), 7, 13)]
+    );
+}
+
+#[test]
+fn preserved_spans_have_offsets() {
+    let config = TokenizerConfig {
+        preserve_patterns: vec![r"\d+mg".to_string()],
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(
+        offsets(config, "Took 100mg daily"),
+        [
+            ("took".to_string(), 0, 4),
+            ("100mg".to_string(), 5, 10),
+            ("daily".to_string(), 11, 16),
+        ]
+    );
+}
+
+#[test]
+fn ngram_offsets_cover_removed_punctuation() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Ngram { min_gram: 2, max_gram: 2 },
+        remove_punctuation: true,
+        ..TokenizerConfig::default()
+    };
+    assert_eq!(
+        offsets(config, "x a-bc"),
+        [("ab".to_string(), 2, 5), ("bc".to_string(), 4, 6)]
+    );
+}
+
+#[test]
+fn path_hierarchy_offsets_are_prefixes() {
+    let config = with_strategy(TokenizerStrategy::PathHierarchy { delimiter: "/".to_string() });
+    assert_eq!(
+        offsets(config, " /usr/local "),
+        [("/usr".to_string(), 1, 5), ("/usr/local".to_string(), 1, 11)]
+    );
+}
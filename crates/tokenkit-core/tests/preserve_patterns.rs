@@ -0,0 +1,48 @@
+use tokenkit_core::{from_config, TokenizerConfig, TokenizerStrategy};
+
+fn preserving(strategy: TokenizerStrategy, patterns: &[&str]) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy,
+        preserve_patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        ..TokenizerConfig::default()
+    }
+}
+
+#[test]
+fn preserved_matches_keep_their_case() {
+    let config = preserving(TokenizerStrategy::Unicode, &[r"\d+ug", "BRCA1"]);
+    let tokens = from_config(config).unwrap().tokenize("Patient received 100ug for BRCA1 study");
+    assert_eq!(tokens, ["patient", "received", "100ug", "for", "BRCA1", "study"]);
+}
+
+#[test]
+fn preserved_matches_may_span_separators() {
+    let config = preserving(TokenizerStrategy::Whitespace, &["New York"]);
+    let tokens = from_config(config).unwrap().tokenize("Visit New York today");
+    assert_eq!(tokens, ["visit", "New York", "today"]);
+}
+
+#[test]
+fn overlapping_matches_are_merged() {
+    let config = preserving(TokenizerStrategy::Unicode, &["Anti-CD3", "CD3 antibody"]);
+    let tokens = from_config(config).unwrap().tokenize("the Anti-CD3 antibody works");
+    assert_eq!(tokens, ["the", "Anti-CD3 antibody", "works"]);
+}
+
+#[test]
+fn char_group_splits_around_preserved_text() {
+    let strategy = TokenizerStrategy::CharGroup { split_on_chars: ",".to_string() };
+    let config = preserving(strategy, &["ID-[0-9]+"]);
+    let tokens = from_config(config).unwrap().tokenize("Alpha,ID-42,Beta");
+    assert_eq!(tokens, ["alpha", "ID-42", "beta"]);
+}
+
+#[test]
+fn filters_apply_when_nothing_matches() {
+    let config = TokenizerConfig {
+        remove_punctuation: true,
+        ..preserving(TokenizerStrategy::Whitespace, &[r"\d+mg"])
+    };
+    let tokens = from_config(config).unwrap().tokenize("Hello, World!");
+    assert_eq!(tokens, ["hello", "world"]);
+}
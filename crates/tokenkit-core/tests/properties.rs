@@ -0,0 +1,195 @@
+//! Runs every strategy on arbitrary UTF-8, checking that nothing panics and
+//! that offsets always point into the input. Raise the case count with
+//! `PROPTEST_CASES=100000 cargo test --test properties`.
+
+use proptest::prelude::*;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokenkit_core::{
+    from_config, Limits, OverflowPolicy, Result, Route, Token, TokenFilter, TokenStream, Tokenizer, TokenizerConfig,
+    TokenizerStrategy,
+};
+
+fn strategies() -> Vec<TokenizerStrategy> {
+    let fixtures = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let bert = fixtures.join("huggingface/bert/tokenizer.json");
+
+    vec![
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Pattern {
+            regex: r"\w+|[^\w\s]".to_string(),
+        },
+        TokenizerStrategy::Sentence,
+        TokenizerStrategy::Grapheme { extended: true },
+        TokenizerStrategy::Grapheme { extended: false },
+        TokenizerStrategy::Keyword,
+        TokenizerStrategy::EdgeNgram { min_gram: 1, max_gram: 3 },
+        TokenizerStrategy::Ngram { min_gram: 1, max_gram: 3 },
+        TokenizerStrategy::PathHierarchy {
+            delimiter: "/".to_string(),
+        },
+        TokenizerStrategy::PathHierarchy {
+            delimiter: "é".to_string(),
+        },
+        TokenizerStrategy::UrlEmail,
+        TokenizerStrategy::CharGroup {
+            split_on_chars: " -ß".to_string(),
+        },
+        TokenizerStrategy::Letter,
+        TokenizerStrategy::Lowercase,
+        TokenizerStrategy::Bytes,
+        cjk(),
+        TokenizerStrategy::Japanese {
+            dictionary: fixtures.join("japanese/ipadic").to_string_lossy().into_owned(),
+            user_dictionaries: vec![fixtures.join("japanese/user.csv").to_string_lossy().into_owned()],
+        },
+        TokenizerStrategy::HuggingFace {
+            path: bert.to_string_lossy().into_owned(),
+            add_special_tokens: true,
+        },
+        TokenizerStrategy::Router {
+            routes: vec![route("han", cjk()), route("thai", TokenizerStrategy::Unicode)],
+            fallback: Some(Box::default()),
+        },
+    ]
+}
+
+fn cjk() -> TokenizerStrategy {
+    TokenizerStrategy::Cjk {
+        dictionary: None,
+        user_dictionaries: Vec::new(),
+        search_mode: true,
+        hmm: true,
+    }
+}
+
+fn route(script: &str, strategy: TokenizerStrategy) -> Route {
+    Route {
+        scripts: vec![script.to_string()],
+        languages: Vec::new(),
+        tokenizer: TokenizerConfig {
+            strategy,
+            ..TokenizerConfig::default()
+        },
+    }
+}
+
+// Each strategy plain, with preserve patterns and with every filter and limit
+fn configs() -> Vec<TokenizerConfig> {
+    let mut configs = Vec::new();
+
+    for strategy in strategies() {
+        let preserves = !matches!(
+            strategy,
+            TokenizerStrategy::HuggingFace { .. } | TokenizerStrategy::Bytes | TokenizerStrategy::Router { .. }
+        );
+        let huggingface = matches!(strategy, TokenizerStrategy::HuggingFace { .. });
+
+        configs.push(TokenizerConfig {
+            strategy: strategy.clone(),
+            ..TokenizerConfig::default()
+        });
+        if preserves {
+            configs.push(TokenizerConfig {
+                strategy: strategy.clone(),
+                preserve_patterns: vec![r"\d+mg".to_string(), r"(?i)[a-zé]{2}\d".to_string(), r"İ\w*".to_string()],
+                ..TokenizerConfig::default()
+            });
+        }
+        configs.push(TokenizerConfig {
+            strategy,
+            lowercase: false,
+            remove_punctuation: !huggingface,
+            filters: vec![TokenFilter::CjkBigram { output_unigrams: true }],
+            limits: Limits {
+                max_input_bytes: Some(64),
+                max_tokens: Some(8),
+                max_token_length: Some(3),
+                overflow: OverflowPolicy::Truncate,
+                ..Limits::default()
+            },
+            ..TokenizerConfig::default()
+        });
+    }
+
+    configs
+}
+
+fn tokenizers() -> &'static [Box<dyn Tokenizer>] {
+    static TOKENIZERS: OnceLock<Vec<Box<dyn Tokenizer>>> = OnceLock::new();
+    TOKENIZERS.get_or_init(|| configs().into_iter().map(|config| from_config(config).unwrap()).collect())
+}
+
+// Lends a tokenizer built once to a stream, which owns its tokenizer
+struct Shared(&'static dyn Tokenizer);
+
+impl Tokenizer for Shared {
+    fn tokenize_with_offsets<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        self.0.tokenize_with_offsets(text)
+    }
+
+    fn tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Token<'a>> + 'a> {
+        self.0.tokens(text)
+    }
+
+    fn try_tokenize_with_offsets<'a>(&self, text: &'a str) -> Result<Vec<Token<'a>>> {
+        self.0.try_tokenize_with_offsets(text)
+    }
+
+    fn try_tokens<'a>(&'a self, text: &'a str) -> Box<dyn Iterator<Item = Result<Token<'a>>> + 'a> {
+        self.0.try_tokens(text)
+    }
+
+    fn stream_boundary(&self, text: &str) -> Option<usize> {
+        self.0.stream_boundary(text)
+    }
+}
+
+// Mostly characters that are awkward for tokenizers: multi-byte letters whose
+// case mapping changes length, combining marks, emoji sequences, punctuation
+fn text() -> impl Strategy<Value = String> {
+    let awkward = prop::sample::select(vec![
+        'a', 'Z', '1', ' ', '\t', '\n', '.', '-', '/', '@', ':', 'é', 'ß', 'İ', 'ﬁ', '\u{301}', '\u{200d}', '👨',
+        '👩', '🇺', '🇸', '世', 'タ', 'ー', '한', 'ก', '\u{0}', '\u{feff}',
+    ]);
+    prop_oneof![
+        prop::collection::vec(awkward, 0..64).prop_map(String::from_iter),
+        any::<String>(),
+        "(https?://[a-z.]+/[a-z]*|[a-z]+@[a-z]+\\.com|[0-9]+mg| )*",
+    ]
+}
+
+fn assert_offsets(text: &str, tokenizer: &dyn Tokenizer) {
+    for token in tokenizer.tokenize_with_offsets(text) {
+        assert!(token.start <= token.end && token.end <= text.len(), "{:?} out of {:?}", token, text);
+        assert!(text.is_char_boundary(token.start) && text.is_char_boundary(token.end), "{:?} in {:?}", token, text);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn every_strategy_handles_arbitrary_text(text in text()) {
+        for tokenizer in tokenizers() {
+            assert_offsets(&text, tokenizer.as_ref());
+            let _ = tokenizer.tokens(&text).count();
+            let _ = tokenizer.try_tokenize(&text);
+        }
+    }
+
+    #[test]
+    fn every_strategy_streams_arbitrary_chunks(text in text(), cut in any::<prop::sample::Index>()) {
+        // Splitting the bytes anywhere, even inside a character
+        let bytes = text.as_bytes();
+        let cut = if bytes.is_empty() { 0 } else { cut.index(bytes.len()) };
+
+        for (config, tokenizer) in configs().into_iter().zip(tokenizers()) {
+            let mut stream = TokenStream::new(Box::new(Shared(tokenizer.as_ref()))).with_limits(config.limits);
+            stream.push(&bytes[..cut]);
+            stream.push(&bytes[cut..]);
+            stream.finish();
+        }
+    }
+}
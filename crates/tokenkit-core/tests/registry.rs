@@ -0,0 +1,74 @@
+use std::sync::Arc;
+use std::thread;
+use tokenkit_core::{AnalyzerRegistry, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn config(strategy: TokenizerStrategy) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy,
+        ..TokenizerConfig::default()
+    }
+}
+
+#[test]
+fn tokenizes_with_named_analyzers() {
+    let registry = AnalyzerRegistry::new();
+    registry.register("title", config(TokenizerStrategy::Unicode)).unwrap();
+    registry
+        .register("sku", config(TokenizerStrategy::CharGroup { split_on_chars: "-".to_string() }))
+        .unwrap();
+
+    assert_eq!(registry.tokenize("title", "Red-Blue Shirt").unwrap(), ["red", "blue", "shirt"]);
+    assert_eq!(registry.tokenize("sku", "AB-12 X").unwrap(), ["ab", "12 x"]);
+    assert_eq!(registry.names(), ["sku", "title"]);
+}
+
+#[test]
+fn re_registering_replaces_the_analyzer() {
+    let registry = AnalyzerRegistry::new();
+    registry.register("body", config(TokenizerStrategy::Unicode)).unwrap();
+    registry.register("body", config(TokenizerStrategy::Whitespace)).unwrap();
+    assert_eq!(registry.tokenize("body", "a, b").unwrap(), ["a,", "b"]);
+}
+
+#[test]
+fn unknown_analyzers_are_errors() {
+    let registry = AnalyzerRegistry::new();
+    assert!(matches!(
+        registry.tokenize("missing", "text"),
+        Err(TokenizerError::UnknownAnalyzer(name)) if name == "missing"
+    ));
+
+    registry.register("gone", config(TokenizerStrategy::Unicode)).unwrap();
+    assert!(registry.unregister("gone"));
+    assert!(!registry.unregister("gone"));
+    assert!(!registry.contains("gone"));
+}
+
+#[test]
+fn invalid_configs_are_not_registered() {
+    let registry = AnalyzerRegistry::new();
+    let invalid = config(TokenizerStrategy::Ngram { min_gram: 3, max_gram: 1 });
+    assert!(registry.register("bad", invalid).is_err());
+    assert!(registry.names().is_empty());
+}
+
+#[test]
+fn shared_across_threads() {
+    let registry = Arc::new(AnalyzerRegistry::new());
+    registry.register("words", config(TokenizerStrategy::Unicode)).unwrap();
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let registry = Arc::clone(&registry);
+            thread::spawn(move || {
+                let text = format!("Thread {} text", i);
+                let tokens = registry.tokenize("words", &text).unwrap();
+                assert_eq!(tokens, ["thread".to_string(), i.to_string(), "text".to_string()]);
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
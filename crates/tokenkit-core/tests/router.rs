@@ -0,0 +1,238 @@
+use tokenkit_core::script::{parse_script, Script};
+use tokenkit_core::validation::diagnose;
+use tokenkit_core::{
+    from_config, CharFilter, Limits, Route, TokenFilter, TokenStream, Tokenizer, TokenizerConfig, TokenizerError,
+    TokenizerStrategy,
+};
+
+fn route(scripts: &[&str], strategy: TokenizerStrategy) -> Route {
+    Route {
+        scripts: scripts.iter().map(|s| s.to_string()).collect(),
+        languages: Vec::new(),
+        tokenizer: TokenizerConfig {
+            strategy,
+            ..TokenizerConfig::default()
+        },
+    }
+}
+
+fn router(routes: Vec<Route>) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy: TokenizerStrategy::Router { routes, fallback: None },
+        ..TokenizerConfig::default()
+    }
+}
+
+fn mixed() -> Box<dyn Tokenizer> {
+    let cjk = TokenizerStrategy::Cjk {
+        dictionary: None,
+        user_dictionaries: Vec::new(),
+        search_mode: false,
+        hmm: true,
+    };
+    let mut latin = route(&["latin"], TokenizerStrategy::Whitespace);
+    latin.tokenizer.lowercase = false;
+
+    from_config(router(vec![route(&["han"], cjk), latin])).unwrap()
+}
+
+#[test]
+fn parses_script_names() {
+    assert_eq!(parse_script("latin"), Some(Script::Latin));
+    assert_eq!(parse_script("HAN"), Some(Script::Han));
+    assert_eq!(parse_script("old_italic"), Some(Script::Old_Italic));
+    assert_eq!(parse_script("Thai"), Some(Script::Thai));
+    assert_eq!(parse_script("cyrl"), Some(Script::Cyrillic));
+    assert_eq!(parse_script("klingon"), None);
+}
+
+#[test]
+fn routes_each_script_to_its_tokenizer() {
+    // Thai has no route and goes to the fallback, the default Unicode strategy
+    assert_eq!(
+        mixed().tokenize("Hello World 我来到北京清华大学 ภาษาไทย"),
+        vec!["Hello", "World", "我", "来到", "北京", "清华大学", "ภาษา", "ไทย"]
+    );
+}
+
+#[test]
+fn offsets_point_into_the_whole_input() {
+    let text = "Hello 我来到北京 again";
+    let tokens = mixed().tokenize_with_offsets(text);
+
+    assert_eq!(tokens.len(), 5);
+    for token in tokens {
+        assert_eq!(&text[token.start..token.end], token.text);
+    }
+}
+
+#[test]
+fn neighbouring_scripts_of_one_route_are_tokenized_together() {
+    let tokenizer = from_config(router(vec![route(&["han", "hiragana"], TokenizerStrategy::Keyword)])).unwrap();
+
+    assert_eq!(tokenizer.tokenize("東京に行く"), vec!["東京に行く"]);
+}
+
+#[test]
+fn characters_of_any_script_stay_with_their_run() {
+    let tokenizer = from_config(router(vec![route(&["han"], TokenizerStrategy::Keyword)])).unwrap();
+
+    // Leading digits join the first run, trailing ones the run before them
+    assert_eq!(tokenizer.tokenize("2024年 Tokyo 99"), vec!["2024年", "tokyo", "99"]);
+}
+
+#[test]
+fn route_limits_are_enforced() {
+    let mut han = route(&["han"], TokenizerStrategy::Keyword);
+    han.tokenizer.limits = Limits {
+        max_input_bytes: Some(3),
+        ..Limits::default()
+    };
+    let tokenizer = from_config(router(vec![han])).unwrap();
+
+    assert!(tokenizer.try_tokenize("abc 東").is_ok());
+    assert!(matches!(
+        tokenizer.try_tokenize("abc 東京"),
+        Err(TokenizerError::LimitExceeded { .. })
+    ));
+}
+
+#[test]
+fn route_limits_are_enforced_through_filters() {
+    let mut latin = route(&["latin"], TokenizerStrategy::Whitespace);
+    latin.tokenizer.limits = Limits {
+        max_tokens: Some(2),
+        ..Limits::default()
+    };
+    let tokenizer = from_config(TokenizerConfig {
+        filters: vec![TokenFilter::CjkBigram { output_unigrams: false }],
+        ..router(vec![latin])
+    })
+    .unwrap();
+
+    assert_eq!(tokenizer.try_tokenize("a b").unwrap(), ["a", "b"]);
+    assert!(matches!(tokenizer.try_tokenize("a b c"), Err(TokenizerError::LimitExceeded { .. })));
+    assert!(matches!(
+        tokenizer.try_tokens("a b c").last(),
+        Some(Err(TokenizerError::LimitExceeded { .. }))
+    ));
+}
+
+#[test]
+fn route_limits_are_enforced_through_char_filters() {
+    let mut latin = route(&["latin"], TokenizerStrategy::Whitespace);
+    latin.tokenizer.limits = Limits {
+        max_tokens: Some(2),
+        ..Limits::default()
+    };
+    let tokenizer = from_config(TokenizerConfig {
+        char_filters: vec![CharFilter::CjkWidth],
+        filters: vec![TokenFilter::CjkBigram { output_unigrams: false }],
+        ..router(vec![latin])
+    })
+    .unwrap();
+
+    // Full-width letters, so the char filter changes the input
+    assert_eq!(tokenizer.try_tokenize("ａ ｂ").unwrap(), ["a", "b"]);
+    for text in ["ａ ｂ ｃ", "a b c"] {
+        assert!(matches!(tokenizer.try_tokenize(text), Err(TokenizerError::LimitExceeded { .. })));
+        assert!(matches!(
+            tokenizer.try_tokens(text).last(),
+            Some(Err(TokenizerError::LimitExceeded { .. }))
+        ));
+    }
+}
+
+#[test]
+fn streaming_matches_whole_input() {
+    let text = "Hello World 我来到北京清华大学。 ภาษาไทยง่าย 2024 and more text";
+    let mut stream = TokenStream::new(mixed());
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(5) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, mixed().tokenize(text));
+}
+
+#[test]
+fn round_trips_through_yaml() {
+    let mut config = router(vec![route(&["han"], TokenizerStrategy::Keyword)]);
+    if let TokenizerStrategy::Router { fallback, .. } = &mut config.strategy {
+        *fallback = Some(Box::new(TokenizerConfig {
+            strategy: TokenizerStrategy::Whitespace,
+            ..TokenizerConfig::default()
+        }));
+    }
+
+    let yaml = config.to_yaml().unwrap();
+    assert_eq!(TokenizerConfig::from_yaml(&yaml).unwrap(), config, "{}", yaml);
+}
+
+#[test]
+fn routes_by_the_detected_language() {
+    let mut english = route(&["latin"], TokenizerStrategy::Whitespace);
+    english.languages = vec!["english".to_string()];
+    let mut config = router(vec![english]);
+    if let TokenizerStrategy::Router { fallback, .. } = &mut config.strategy {
+        *fallback = Some(Box::new(TokenizerConfig {
+            strategy: TokenizerStrategy::Keyword,
+            ..TokenizerConfig::default()
+        }));
+    }
+    let tokenizer = from_config(config).unwrap();
+
+    assert_eq!(
+        tokenizer.tokenize("The quick brown fox jumps over the lazy dog"),
+        ["the", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog"]
+    );
+    assert_eq!(
+        tokenizer.tokenize("Der schnelle braune Fuchs springt über den faulen Hund"),
+        ["der schnelle braune fuchs springt über den faulen hund"]
+    );
+}
+
+#[test]
+fn detects_the_language_of_each_run() {
+    let mut english = route(&["latin"], TokenizerStrategy::Whitespace);
+    english.languages = vec!["english".to_string()];
+    let tokenizer = from_config(router(vec![english, route(&["latin"], TokenizerStrategy::Keyword)])).unwrap();
+
+    let text = "The quick brown fox jumps over the lazy dog.\nDer schnelle braune Fuchs springt über den faulen Hund.";
+    assert_eq!(
+        tokenizer.tokenize(text),
+        [
+            "the", "quick", "brown", "fox", "jumps", "over", "the", "lazy", "dog.",
+            "der schnelle braune fuchs springt über den faulen hund."
+        ]
+    );
+}
+
+#[test]
+fn reports_problems_inside_routes() {
+    let mut config = router(vec![
+        route(&["latin", "klingon"], TokenizerStrategy::Ngram { min_gram: 3, max_gram: 1 }),
+        route(&[], TokenizerStrategy::Keyword),
+    ]);
+    config.preserve_patterns = vec![r"\d+".to_string()];
+
+    let mut by_language = route(&[], TokenizerStrategy::Keyword);
+    by_language.languages = vec!["klingon".to_string()];
+    if let TokenizerStrategy::Router { routes, .. } = &mut config.strategy {
+        routes.push(by_language);
+    }
+
+    let paths: Vec<String> = diagnose(&config).into_iter().map(|d| d.path).collect();
+    assert_eq!(
+        paths,
+        [
+            "routes[0].scripts",
+            "routes[0].tokenizer.max_gram",
+            "routes[1].scripts",
+            "routes[2].languages",
+            "preserve_patterns"
+        ]
+    );
+    assert!(diagnose(&router(Vec::new()))[0].message.contains("at least one route"));
+}
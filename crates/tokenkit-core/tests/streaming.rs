@@ -0,0 +1,130 @@
+use tokenkit_core::{from_config, TokenStream, TokenizerConfig, TokenizerStrategy};
+
+fn config(strategy: TokenizerStrategy) -> TokenizerConfig {
+    TokenizerConfig {
+        strategy,
+        ..TokenizerConfig::default()
+    }
+}
+
+fn stream_in_chunks(strategy: TokenizerStrategy, text: &str, chunk_size: usize) -> Vec<String> {
+    let mut stream = TokenStream::new(from_config(config(strategy)).unwrap());
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(chunk_size) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+    tokens
+}
+
+#[test]
+fn lazy_tokens_match_tokenize() {
+    let text = "Hello, wörld! Streaming 世界 tokens lazily.";
+    for strategy in [
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Letter,
+        TokenizerStrategy::Ngram { min_gram: 1, max_gram: 3 },
+        TokenizerStrategy::EdgeNgram { min_gram: 1, max_gram: 3 },
+        TokenizerStrategy::Grapheme { extended: true },
+    ] {
+        let tokenizer = from_config(config(strategy.clone())).unwrap();
+        let lazy: Vec<_> = tokenizer.tokens(text).collect();
+        assert_eq!(lazy, tokenizer.tokenize_with_offsets(text), "{:?}", strategy);
+    }
+}
+
+#[test]
+fn chunked_stream_matches_whole_input() {
+    let text = "Héllo wörld, this is a tést. Of 世界 streaming! Second sentence here.";
+    for strategy in [
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Sentence,
+        TokenizerStrategy::Keyword,
+        TokenizerStrategy::Grapheme { extended: true },
+        TokenizerStrategy::CharGroup { split_on_chars: ",".to_string() },
+        TokenizerStrategy::PathHierarchy { delimiter: " ".to_string() },
+    ] {
+        let whole = from_config(config(strategy.clone())).unwrap().tokenize(text);
+        for chunk_size in 1..8 {
+            assert_eq!(
+                stream_in_chunks(strategy.clone(), text, chunk_size),
+                whole,
+                "{:?} in chunks of {}",
+                strategy,
+                chunk_size
+            );
+        }
+    }
+}
+
+#[test]
+fn truncated_character_at_end_is_replaced() {
+    let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap());
+    let bytes = "ok é".as_bytes();
+    let mut tokens = stream.push(&bytes[..bytes.len() - 1]);
+    tokens.extend(stream.finish());
+    assert_eq!(tokens, ["ok", "\u{FFFD}"]);
+}
+
+#[test]
+fn stream_offsets_span_the_whole_input() {
+    let text = "alpha béta gamma delta";
+    let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap());
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(3) {
+        tokens.extend(stream.push_tokens(chunk));
+    }
+    tokens.extend(stream.finish_tokens());
+
+    let whole = from_config(config(TokenizerStrategy::Whitespace))
+        .unwrap()
+        .tokenize_with_offsets(text);
+    assert_eq!(tokens, whole);
+}
+
+#[test]
+fn finished_stream_starts_the_next_input_at_zero() {
+    let mut stream = TokenStream::new(from_config(config(TokenizerStrategy::Whitespace)).unwrap());
+    stream.push_tokens(b"first input ");
+    stream.finish_tokens();
+
+    let mut tokens = stream.push_tokens(b"second ");
+    tokens.extend(stream.finish_tokens());
+    let offsets: Vec<_> = tokens.iter().map(|token| (token.start, token.end)).collect();
+    assert_eq!(offsets, [(0, 6)]);
+}
+
+#[test]
+fn preserved_matches_spanning_a_chunk_are_kept_whole() {
+    let text = "I love New York City";
+    for strategy in [
+        TokenizerStrategy::Unicode,
+        TokenizerStrategy::Whitespace,
+        TokenizerStrategy::Letter,
+        TokenizerStrategy::Lowercase,
+        TokenizerStrategy::Sentence,
+        TokenizerStrategy::CharGroup { split_on_chars: " ".to_string() },
+        TokenizerStrategy::Pattern { regex: r"\w+".to_string() },
+    ] {
+        let config = TokenizerConfig {
+            strategy: strategy.clone(),
+            preserve_patterns: vec!["New York".to_string()],
+            ..TokenizerConfig::default()
+        };
+        let whole = from_config(config.clone()).unwrap().tokenize(text);
+        assert!(whole.iter().any(|token| token.contains("New York")), "{:?}", strategy);
+
+        let mut stream = TokenStream::new(from_config(config).unwrap());
+        let mut tokens = stream.push(b"I love New ");
+        tokens.extend(stream.push(b"York City"));
+        tokens.extend(stream.finish());
+        assert_eq!(tokens, whole, "{:?}", strategy);
+    }
+
+    // Pattern strategy tokens may span whitespace too
+    let strategy = TokenizerStrategy::Pattern { regex: r"[^,]+".to_string() };
+    let whole = from_config(config(strategy.clone())).unwrap().tokenize("a b, c d");
+    assert_eq!(stream_in_chunks(strategy, "a b, c d", 2), whole);
+}
@@ -0,0 +1,147 @@
+use tokenkit_core::{from_config, TokenizerConfig, TokenizerStrategy};
+
+fn tokenize_with(strategy: TokenizerStrategy, text: &str) -> Vec<String> {
+    let config = TokenizerConfig {
+        strategy,
+        ..TokenizerConfig::default()
+    };
+    from_config(config)
+        .unwrap()
+        .tokenize(text)
+        .into_iter()
+        .map(|t| t.into_owned())
+        .collect()
+}
+
+#[test]
+fn unicode_splits_on_word_boundaries() {
+    assert_eq!(
+        tokenize_with(TokenizerStrategy::Unicode, "Hello, World! Don't worry about café."),
+        ["hello", "world", "don't", "worry", "about", "café"]
+    );
+}
+
+#[test]
+fn whitespace_keeps_punctuation() {
+    assert_eq!(
+        tokenize_with(TokenizerStrategy::Whitespace, "Hello,  world!\tagain"),
+        ["hello,", "world!", "again"]
+    );
+}
+
+#[test]
+fn pattern_extracts_matches() {
+    let strategy = TokenizerStrategy::Pattern {
+        regex: r"[A-Za-z]+-\d+".to_string(),
+    };
+    assert_eq!(tokenize_with(strategy, "see ABC-123 and x-9"), ["abc-123", "x-9"]);
+}
+
+#[test]
+fn sentence_splits_sentences() {
+    assert_eq!(
+        tokenize_with(TokenizerStrategy::Sentence, "Hello world! How are you?"),
+        ["hello world! ", "how are you?"]
+    );
+}
+
+#[test]
+fn grapheme_keeps_clusters_together() {
+    assert_eq!(
+        tokenize_with(TokenizerStrategy::Grapheme { extended: true }, "👨‍👩‍👧‍👦e\u{301}"),
+        ["👨‍👩‍👧‍👦", "e\u{301}"]
+    );
+}
+
+#[test]
+fn keyword_returns_trimmed_input() {
+    assert_eq!(tokenize_with(TokenizerStrategy::Keyword, "  PROD-2024  "), ["prod-2024"]);
+    assert!(tokenize_with(TokenizerStrategy::Keyword, "   ").is_empty());
+}
+
+#[test]
+fn edge_ngram_emits_prefixes() {
+    let strategy = TokenizerStrategy::EdgeNgram { min_gram: 2, max_gram: 4 };
+    assert_eq!(tokenize_with(strategy, "Laptop"), ["la", "lap", "lapt"]);
+}
+
+#[test]
+fn ngram_emits_all_grams_by_size() {
+    let strategy = TokenizerStrategy::Ngram { min_gram: 2, max_gram: 3 };
+    assert_eq!(tokenize_with(strategy, "quick"), ["qu", "ui", "ic", "ck", "qui", "uic", "ick"]);
+}
+
+#[test]
+fn ngram_handles_multibyte_characters() {
+    let strategy = TokenizerStrategy::Ngram { min_gram: 2, max_gram: 2 };
+    assert_eq!(tokenize_with(strategy, "café"), ["ca", "af", "fé"]);
+}
+
+#[test]
+fn path_hierarchy_emits_each_level() {
+    let strategy = TokenizerStrategy::PathHierarchy { delimiter: "/".to_string() };
+    assert_eq!(
+        tokenize_with(strategy, "/usr//local/bin"),
+        ["/usr", "/usr/local", "/usr/local/bin"]
+    );
+}
+
+#[test]
+fn url_email_keeps_links_whole() {
+    assert_eq!(
+        tokenize_with(TokenizerStrategy::UrlEmail, "Mail support@example.com or https://example.com"),
+        ["mail", "support@example.com", "or", "https://example.com"]
+    );
+}
+
+#[test]
+fn char_group_splits_on_given_characters() {
+    let strategy = TokenizerStrategy::CharGroup { split_on_chars: ",;".to_string() };
+    assert_eq!(tokenize_with(strategy, "John Doe,30;Engineer"), ["john doe", "30", "engineer"]);
+}
+
+#[test]
+fn letter_splits_on_non_letters() {
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "hello-world123test"), ["hello", "world", "test"]);
+}
+
+#[test]
+fn letter_keeps_combining_marks_in_words() {
+    // Virama, nukta and Tamil pulli are marks, not letters
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "हिन्दी भाषा"), ["हिन्दी", "भाषा"]);
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "ज़िंदगी"), ["ज़िंदगी"]);
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "বাংলা ভাষা"), ["বাংলা", "ভাষা"]);
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "தமிழ் மொழி"), ["தமிழ்", "மொழி"]);
+    assert_eq!(tokenize_with(TokenizerStrategy::Lowercase, "CAFE\u{301} नमस्ते"), ["cafe\u{301}", "नमस्ते"]);
+    // A mark with no letter before it starts no word
+    assert_eq!(tokenize_with(TokenizerStrategy::Letter, "\u{94D}क 1\u{301}"), ["क"]);
+}
+
+#[test]
+fn lowercase_always_lowercases() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Lowercase,
+        lowercase: false,
+        ..TokenizerConfig::default()
+    };
+    let tokens = from_config(config).unwrap().tokenize("HELLO World");
+    assert_eq!(tokens, ["hello", "world"]);
+}
+
+#[test]
+fn remove_punctuation_drops_empty_tokens() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Whitespace,
+        remove_punctuation: true,
+        ..TokenizerConfig::default()
+    };
+    let tokens = from_config(config).unwrap().tokenize("wait ... what?!");
+    assert_eq!(tokens, ["wait", "what"]);
+}
+
+#[test]
+fn unchanged_tokens_borrow_from_input() {
+    let tokens = from_config(TokenizerConfig::default()).unwrap().tokenize("already lower Mixed");
+    assert!(matches!(tokens[0], std::borrow::Cow::Borrowed("already")));
+    assert!(matches!(tokens[2], std::borrow::Cow::Owned(_)));
+}
@@ -0,0 +1,124 @@
+use tokenkit_core::validation::{self, diagnose, filter_problems, option_problems, suggest};
+use tokenkit_core::{validate_config, Diagnostic, Limits, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn paths(diagnostics: &[Diagnostic]) -> Vec<&str> {
+    diagnostics.iter().map(|d| d.path.as_str()).collect()
+}
+
+#[test]
+fn valid_config_has_no_diagnostics() {
+    assert!(diagnose(&TokenizerConfig::default()).is_empty());
+}
+
+#[test]
+fn every_problem_is_reported() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::Ngram { min_gram: 5, max_gram: 2 },
+        preserve_patterns: vec![r"\d+mg".to_string(), "[unclosed".to_string()],
+        limits: Limits {
+            max_tokens: Some(0),
+            ..Limits::default()
+        },
+        ..TokenizerConfig::default()
+    };
+
+    let diagnostics = diagnose(&config);
+    assert_eq!(paths(&diagnostics), ["max_gram", "max_tokens", "preserve_patterns[1]"]);
+    assert_eq!(diagnostics[1].to_string(), "max_tokens: Invalid configuration: max_tokens must be at least 1");
+
+    match validate_config(&config) {
+        Err(TokenizerError::ConfigurationProblems(problems)) => assert_eq!(problems, diagnostics),
+        other => panic!("expected ConfigurationProblems, got {:?}", other),
+    }
+}
+
+#[test]
+fn single_problem_keeps_its_error() {
+    let config = TokenizerConfig {
+        strategy: TokenizerStrategy::PathHierarchy {
+            delimiter: String::new(),
+        },
+        ..TokenizerConfig::default()
+    };
+
+    assert!(matches!(validate_config(&config), Err(TokenizerError::EmptyDelimiter { .. })));
+    assert_eq!(paths(&diagnose(&config)), ["delimiter"]);
+}
+
+#[test]
+fn misspelled_options_get_suggestions() {
+    let problems = option_problems("path_hierarchy", ["delimeter", "lowercase"]);
+    let diagnostics: Vec<Diagnostic> = problems.iter().map(|p| p.diagnostic()).collect();
+
+    assert_eq!(paths(&diagnostics), ["delimeter"]);
+    assert_eq!(diagnostics[0].suggestion.as_deref(), Some("delimiter"));
+    assert_eq!(diagnostics[0].message, "Unknown option 'delimeter' (did you mean 'delimiter'?)");
+
+    let problems = option_problems("edge_ngram", ["max_grams", "frobnicate"]);
+    let diagnostics: Vec<Diagnostic> = problems.iter().map(|p| p.diagnostic()).collect();
+    assert_eq!(diagnostics[0].suggestion.as_deref(), Some("max_gram"));
+    assert_eq!(diagnostics[1].suggestion, None);
+}
+
+#[test]
+fn options_of_other_strategies_are_rejected() {
+    let problems = option_problems("unicode", ["min_gram", "lowercase", "max_tokens"]);
+
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].path, "min_gram");
+    assert_eq!(
+        problems[0].error.to_string(),
+        "Option 'min_gram' is not used by the unicode strategy"
+    );
+}
+
+#[test]
+fn unknown_strategy_gets_a_suggestion() {
+    let problems = option_problems("edge_ngrams", ["min_gram"]);
+
+    // Options can't be checked against a strategy that doesn't exist
+    assert_eq!(problems.len(), 1);
+    assert_eq!(problems[0].diagnostic().suggestion.as_deref(), Some("edge_ngram"));
+    assert_eq!(
+        problems[0].error.to_string(),
+        "Unknown tokenizer strategy: edge_ngrams (did you mean 'edge_ngram'?)"
+    );
+}
+
+#[test]
+fn filter_types_and_options_are_checked() {
+    assert!(filter_problems("filters[0]", "cjk_bigram", ["type", "output_unigrams"]).is_empty());
+
+    let problems = filter_problems("filters[1]", "cjk_bigrams", ["type"]);
+    assert_eq!(problems[0].path, "filters[1].type");
+    assert_eq!(
+        problems[0].error.to_string(),
+        "Invalid configuration: unknown filter 'cjk_bigrams' (did you mean 'cjk_bigram'?)"
+    );
+
+    let problems = filter_problems("filters[0]", "cjk_bigram", ["output_unigram"]);
+    assert_eq!(problems[0].path, "filters[0].output_unigram");
+    assert_eq!(problems[0].diagnostic().suggestion.as_deref(), Some("output_unigrams"));
+}
+
+#[test]
+fn into_result_lists_every_problem() {
+    assert!(validation::into_result(Vec::new()).is_ok());
+
+    let problems = option_problems("unicode", ["delimiter", "regex"]);
+    let error = validation::into_result(problems).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid configuration:\n  delimiter: Option 'delimiter' is not used by the unicode strategy\n  \
+         regex: Option 'regex' is not used by the unicode strategy"
+    );
+}
+
+#[test]
+fn suggest_finds_close_candidates() {
+    let candidates = ["min_gram", "max_gram", "delimiter"];
+
+    assert_eq!(suggest("max_grams", candidates).as_deref(), Some("max_gram"));
+    assert_eq!(suggest("delimtier", candidates).as_deref(), Some("delimiter"));
+    assert_eq!(suggest("regex", candidates), None);
+}
@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+use tokenkit_core::script::needs_dictionary;
+use tokenkit_core::{from_config, TokenStream, Tokenizer, TokenizerConfig, TokenizerError, TokenizerStrategy};
+
+fn fixture(name: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/word_break")
+        .join(name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn unicode(dictionaries: &[&str]) -> Box<dyn Tokenizer> {
+    from_config(TokenizerConfig {
+        word_break_dictionaries: dictionaries.iter().map(|name| fixture(name)).collect(),
+        ..TokenizerConfig::default()
+    })
+    .unwrap()
+}
+
+#[test]
+fn detects_scripts_written_without_spaces() {
+    assert!(needs_dictionary('ก') && needs_dictionary('ລ') && needs_dictionary('ខ') && needs_dictionary('မ'));
+    // Thai vowel signs are combining marks of the Thai script
+    assert!(needs_dictionary('\u{e34}'));
+    assert!(!needs_dictionary('a') && !needs_dictionary('1') && !needs_dictionary('東'));
+}
+
+#[test]
+fn never_breaks_inside_a_grapheme_cluster() {
+    // The bundled Myanmar dictionary alone would split the medial ြ from မ
+    for word in unicode(&[]).tokenize("ကျွန်တော်မြန်မာစကားပြောတတ်ပါတယ်") {
+        assert!(!word.starts_with('\u{103c}'), "{:?}", word);
+    }
+}
+
+#[test]
+fn breaks_thai_and_lao_words() {
+    let tokenizer = unicode(&[]);
+
+    assert_eq!(tokenizer.tokenize("ภาษาไทยง่ายนิดเดียว"), vec!["ภาษา", "ไทย", "ง่าย", "นิด", "เดียว"]);
+    assert_eq!(tokenizer.tokenize("ພາສາລາວ"), vec!["ພາສາ", "ລາວ"]);
+}
+
+#[test]
+fn breaks_khmer_and_myanmar_words() {
+    let tokenizer = unicode(&[]);
+
+    assert_eq!(tokenizer.tokenize("ខ្ញុំស្រលាញ់ភាសាខ្មែរ"), vec!["ខ្ញុំ", "ស្រលាញ់", "ភាសាខ្មែរ"]);
+    assert_eq!(
+        tokenizer.tokenize("သူသည်ကျောင်းသို့သွားသည်"),
+        vec!["သူ", "သည်", "ကျောင်း", "သို့", "သွား", "သည်"]
+    );
+}
+
+#[test]
+fn other_words_are_unchanged() {
+    let tokenizer = unicode(&[]);
+
+    assert_eq!(
+        tokenizer.tokenize("Hello, ฉันกินข้าว café 東京"),
+        vec!["hello", "ฉัน", "กิน", "ข้าว", "café", "東", "京"]
+    );
+}
+
+#[test]
+fn offsets_point_into_the_input() {
+    let text = "I said ไปกรุงเทพมหานคร today";
+    let tokens = unicode(&[]).tokenize_with_offsets(text);
+
+    assert_eq!(tokens.len(), 5);
+    for token in tokens {
+        assert_eq!(text[token.start..token.end].to_lowercase(), token.text);
+    }
+}
+
+#[test]
+fn user_words_join_bundled_words() {
+    assert_eq!(unicode(&["thai_user.txt"]).tokenize("ฉันกินข้าว"), vec!["ฉัน", "กินข้าว"]);
+}
+
+#[test]
+fn user_words_do_not_split_longer_bundled_words() {
+    assert_eq!(
+        unicode(&["thai_user.txt"]).tokenize("ไปกรุงเทพมหานคร"),
+        vec!["ไป", "กรุงเทพมหานคร"]
+    );
+}
+
+#[test]
+fn missing_user_dictionary_is_an_error() {
+    let error = from_config(TokenizerConfig {
+        word_break_dictionaries: vec![fixture("missing.txt")],
+        ..TokenizerConfig::default()
+    })
+    .err()
+    .unwrap();
+
+    assert!(matches!(error, TokenizerError::InvalidDictionary { .. }));
+}
+
+#[test]
+fn dictionaries_need_the_unicode_strategy() {
+    let error = from_config(TokenizerConfig {
+        strategy: TokenizerStrategy::Whitespace,
+        word_break_dictionaries: vec![fixture("thai_user.txt")],
+        ..TokenizerConfig::default()
+    })
+    .err()
+    .unwrap();
+
+    assert!(matches!(error, TokenizerError::InvalidConfiguration { ref key, .. } if key == "word_break_dictionaries"));
+}
+
+#[test]
+fn streaming_matches_whole_input() {
+    let text = "ภาษาไทยง่ายนิดเดียว ฉันกินข้าว";
+    let mut stream = TokenStream::new(unicode(&[]));
+    let mut tokens = Vec::new();
+    for chunk in text.as_bytes().chunks(7) {
+        tokens.extend(stream.push(chunk));
+    }
+    tokens.extend(stream.finish());
+
+    assert_eq!(tokens, unicode(&[]).tokenize(text));
+}
@@ -1,67 +1,122 @@
-use thiserror::Error;
+use magnus::{class, prelude::*, Exception, ExceptionClass, IntoValue, RArray, RClass, RModule, Value};
+use std::any::Any;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use tokenkit_core::{Diagnostic, TokenizerError};
 
-#[derive(Error, Debug)]
-pub enum TokenizerError {
-    #[error("Invalid configuration: {0}")]
-    InvalidConfiguration(String),
-
-    #[error("Invalid regex pattern '{pattern}': {error}")]
-    InvalidRegex {
-        pattern: String,
-        error: String,
-    },
-
-    #[error("Invalid n-gram configuration: min_gram ({min}) must be > 0 and <= max_gram ({max})")]
-    InvalidNgramConfig {
-        min: usize,
-        max: usize,
-    },
-
-    #[error("Empty delimiter is not allowed for {tokenizer} tokenizer")]
-    EmptyDelimiter {
-        tokenizer: String,
-    },
+/// Maps a core error onto the matching `TokenKit::Error` subclass, with the
+/// error's fields as attributes so callers don't have to parse the message.
+///
+/// A free function rather than `From`, since both types live in other crates.
+pub(crate) fn ruby_error(error: TokenizerError) -> magnus::Error {
+    let message = error.to_string();
+    match error {
+        TokenizerError::InvalidConfiguration { key, .. } => raise("ConfigurationError", message, |e| {
+            set(e, "@key", key)
+        }),
+        TokenizerError::InvalidNgramConfig { min, max } => raise("ConfigurationError", message, |e| {
+            // Blame max_gram when min_gram is fine on its own, as the builder does
+            set(e, "@key", if min == 0 { "min_gram" } else { "max_gram" })?;
+            set(e, "@min", min)?;
+            set(e, "@max", max)
+        }),
+        TokenizerError::EmptyDelimiter { .. } => raise("ConfigurationError", message, |e| {
+            set(e, "@key", "delimiter")
+        }),
+        TokenizerError::UnknownStrategy(strategy) => raise("UnknownStrategyError", message, |e| {
+            set(e, "@key", "strategy")?;
+            set(e, "@strategy", strategy)
+        }),
+        TokenizerError::UnknownOption { key, .. } | TokenizerError::UnusedOption { key, .. } => {
+            raise("ConfigurationError", message, |e| set(e, "@key", key))
+        }
+        TokenizerError::ConfigurationProblems(diagnostics) => raise("ConfigurationError", message, |e| {
+            let array = RArray::with_capacity(diagnostics.len());
+            for diagnostic in &diagnostics {
+                array.push(ruby_diagnostic(diagnostic)?)?;
+            }
+            set(e, "@diagnostics", array)
+        }),
+        TokenizerError::InvalidRegex { pattern, error } => raise("InvalidRegexError", message, |e| {
+            set(e, "@pattern", pattern)?;
+            set(e, "@reason", error)
+        }),
+        TokenizerError::RegexLimitExceeded { pattern, limit, max } => {
+            raise("RegexLimitExceededError", message, |e| {
+                set(e, "@pattern", pattern)?;
+                set(e, "@limit", limit)?;
+                set(e, "@max", max)
+            })
+        }
+        TokenizerError::InvalidConfigFile(_) => raise("ConfigFileError", message, |_| Ok(())),
+        TokenizerError::InvalidAnalysisSettings(_) => raise("AnalysisSettingsError", message, |_| Ok(())),
+        TokenizerError::InvalidDictionary { path, .. } => raise("DictionaryError", message, |e| {
+            set(e, "@path", path)
+        }),
+        TokenizerError::InvalidHuggingFaceTokenizer(_) => {
+            raise("HuggingFaceTokenizerError", message, |_| Ok(()))
+        }
+        TokenizerError::UnknownAnalyzer(name) => raise("UnknownAnalyzerError", message, |e| {
+            set(e, "@name", name)
+        }),
+        TokenizerError::InvalidUtf8 { position } => raise("InvalidUtf8Error", message, |e| {
+            set(e, "@position", position)
+        }),
+        TokenizerError::LimitExceeded { limit, max } => raise("LimitExceededError", message, |e| {
+            set(e, "@limit", limit)?;
+            set(e, "@max", max)
+        }),
+    }
+}
 
-    #[error("Unknown tokenizer strategy: {0}")]
-    UnknownStrategy(String),
+/// Runs an exported function's body, raising `TokenKit::InternalError` if it
+/// panics. Left alone, magnus turns a panic into `fatal`, which Ruby can't
+/// rescue and which ends the process.
+pub(crate) fn guard<T>(body: impl FnOnce() -> Result<T, magnus::Error>) -> Result<T, magnus::Error> {
+    catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| Err(internal_error(payload)))
+}
 
-    #[error("Mutex lock failed: {0}")]
-    MutexError(String),
+fn internal_error(payload: Box<dyn Any + Send>) -> magnus::Error {
+    let panic_message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or("panic", |message| message).to_string(),
+    };
+    let message = format!("Internal error in TokenKit: {}", panic_message);
+    raise("InternalError", message, |e| set(e, "@panic_message", panic_message))
+}
 
-    #[error("Ruby conversion error: {0}")]
-    RubyConversionError(String),
+/// A `TokenKit::Diagnostic` (defined in lib/tokenkit/errors.rb) for `diagnostic`.
+pub(crate) fn ruby_diagnostic(diagnostic: &Diagnostic) -> Result<Value, magnus::Error> {
+    let module: RModule = class::object().const_get("TokenKit")?;
+    let class: RClass = module.const_get("Diagnostic")?;
+    class.new_instance((
+        diagnostic.path.as_str(),
+        diagnostic.message.as_str(),
+        diagnostic.suggestion.as_deref(),
+    ))
 }
 
-impl From<TokenizerError> for magnus::Error {
-    fn from(error: TokenizerError) -> Self {
-        use magnus::exception;
+// Builds a TokenKit::<class> (defined in lib/tokenkit/errors.rb) and lets
+// `attributes` fill in its readers. A failure along the way is raised instead
+fn raise(
+    class: &str,
+    message: String,
+    attributes: impl FnOnce(Exception) -> Result<(), magnus::Error>,
+) -> magnus::Error {
+    let exception = (|| -> Result<Exception, magnus::Error> {
+        let module: RModule = class::object().const_get("TokenKit")?;
+        let class: ExceptionClass = module.const_get(class)?;
+        let exception = class.new_instance((message,))?;
+        attributes(exception)?;
+        Ok(exception)
+    })();
 
-        match error {
-            TokenizerError::InvalidConfiguration(_) |
-            TokenizerError::InvalidNgramConfig { .. } |
-            TokenizerError::EmptyDelimiter { .. } |
-            TokenizerError::UnknownStrategy(_) => {
-                magnus::Error::new(exception::arg_error(), error.to_string())
-            }
-            TokenizerError::InvalidRegex { .. } => {
-                magnus::Error::new(exception::regexp_error(), error.to_string())
-            }
-            TokenizerError::MutexError(_) => {
-                magnus::Error::new(exception::runtime_error(), error.to_string())
-            }
-            TokenizerError::RubyConversionError(_) => {
-                magnus::Error::new(exception::type_error(), error.to_string())
-            }
-        }
+    match exception {
+        Ok(exception) => exception.into(),
+        Err(error) => error,
     }
 }
 
-// For converting magnus conversion errors to our error type
-impl From<magnus::Error> for TokenizerError {
-    fn from(error: magnus::Error) -> Self {
-        TokenizerError::RubyConversionError(error.to_string())
-    }
+fn set(exception: Exception, name: &str, value: impl IntoValue) -> Result<(), magnus::Error> {
+    exception.funcall::<_, _, Value>("instance_variable_set", (name, value))?;
+    Ok(())
 }
-
-// Internal result type for Rust functions
-pub type Result<T> = std::result::Result<T, TokenizerError>;
\ No newline at end of file
@@ -1,79 +1,230 @@
-mod config;
 mod error;
-mod tokenizer;
 
-use config::{TokenizerConfig, TokenizerStrategy};
-use error::TokenizerError;
-use magnus::{define_module, function, Error, RArray, RHash, TryConvert};
-use std::sync::Mutex;
+use error::{guard, ruby_diagnostic, ruby_error};
+use magnus::{
+    class, define_module, encoding::EncodingCapable, function, kwargs, method, prelude::*, r_hash::ForEach, Error,
+    RArray, RHash, RString, Ruby, TryConvert, Value,
+};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::sync::Arc;
+use arc_swap::ArcSwap;
 use once_cell::sync::Lazy;
+use tokenkit_core::huggingface::HuggingFaceTokenizer;
+use tokenkit_core::tokenizer::UnicodeTokenizer;
+use tokenkit_core::validation::{self, Problem};
+use tokenkit_core::{elasticsearch, encoding, language};
+use tokenkit_core::{
+    from_config, AnalyzerRegistry, CharFilter, ConfigFormat, InvalidUtf8Policy, Limits, LongTokenPolicy, OverflowPolicy,
+    RegexLimits, Route, Token, TokenFilter, TokenStream, Tokenizer, TokenizerConfig, TokenizerError, TokenizerStrategy,
+};
 
-// Store the default configuration and a cached tokenizer
-struct TokenizerCache {
+// The default configuration and its compiled tokenizer, swapped as one unit
+// so a reader never pairs a new config with an old tokenizer
+struct DefaultTokenizer {
     config: TokenizerConfig,
-    tokenizer: Option<Box<dyn tokenizer::Tokenizer + Send + Sync>>,
+    tokenizer: Box<dyn Tokenizer>,
 }
 
-static DEFAULT_CACHE: Lazy<Mutex<TokenizerCache>> = Lazy::new(|| {
-    Mutex::new(TokenizerCache {
-        config: TokenizerConfig::default(),
-        tokenizer: None,
+// Readers load the current snapshot without locking; configure/reset
+// publish a new one. The default config is the Unicode strategy
+static DEFAULT: Lazy<ArcSwap<DefaultTokenizer>> = Lazy::new(|| {
+    let config = TokenizerConfig::default();
+    ArcSwap::from_pointee(DefaultTokenizer {
+        tokenizer: Box::new(UnicodeTokenizer::default()),
+        config,
     })
 });
 
-// Use cached tokenizer if config hasn't changed
-fn tokenize(text: String) -> std::result::Result<Vec<String>, Error> {
-    let mut cache = DEFAULT_CACHE
-        .lock()
-        .map_err(|e| TokenizerError::MutexError(e.to_string()))?;
+// Named analyzers, each compiled once when registered
+static ANALYZERS: Lazy<AnalyzerRegistry> = Lazy::new(AnalyzerRegistry::new);
 
-    // Check if we need to create a new tokenizer
-    if cache.tokenizer.is_none() {
-        let tokenizer = tokenizer::from_config(cache.config.clone())?;
-        cache.tokenizer = Some(tokenizer);
+// Build the Ruby array straight from borrowed tokens, so each token's
+// bytes are copied exactly once: into its Ruby string
+fn tokens_to_array(tokens: Vec<Cow<'_, str>>) -> std::result::Result<RArray, Error> {
+    let array = RArray::with_capacity(tokens.len());
+    for token in tokens {
+        array.push(token.as_ref())?;
+    }
+    Ok(array)
+}
+
+// Borrow the Ruby string's bytes when they are valid UTF-8. UTF-8 strings
+// with invalid bytes, and binary strings, are decoded as UTF-8 by `policy`;
+// other encodings are transcoded, replacing or skipping what can't be.
+// Safe: `f` copies whatever it needs out of the borrowed text before control
+// returns to Ruby
+fn with_text<T>(
+    text: RString,
+    policy: InvalidUtf8Policy,
+    f: impl FnOnce(&str) -> std::result::Result<T, Error>,
+) -> std::result::Result<T, Error> {
+    if let Ok(borrowed) = unsafe { text.as_str() } {
+        return f(borrowed);
     }
 
-    // Use the cached tokenizer
-    let result = cache
-        .tokenizer
-        .as_ref()
-        .unwrap()
-        .tokenize(&text);
+    let ruby = Ruby::get_with(text);
+    if text.is_utf8_compatible_encoding() || text.enc_get() == ruby.ascii8bit_encindex() {
+        let decoded = encoding::decode(unsafe { text.as_slice() }, policy).map_err(ruby_error)?;
+        return f(&decoded);
+    }
 
-    Ok(result)
+    let owned = match policy {
+        InvalidUtf8Policy::Raise => text.to_string()?,
+        InvalidUtf8Policy::Replace => transcode(text, "\u{FFFD}")?,
+        InvalidUtf8Policy::Skip => transcode(text, "")?,
+    };
+    f(&owned)
 }
 
-// Configure sets the default configuration and invalidates cache
-fn configure(config_hash: RHash) -> std::result::Result<(), Error> {
-    let config = parse_config_from_hash(config_hash)?;
+// String#encode to UTF-8, putting `replacement` in place of bytes that are
+// invalid or have no UTF-8 equivalent
+fn transcode(text: RString, replacement: &str) -> std::result::Result<String, Error> {
+    let ruby = Ruby::get_with(text);
+    let options = kwargs!(
+        "invalid" => ruby.to_symbol("replace"),
+        "undef" => ruby.to_symbol("replace"),
+        "replace" => replacement
+    );
+    let utf8: RString = text.funcall("encode", ("UTF-8", options))?;
+    utf8.to_string()
+}
+
+// Whether `text` is binary input for the bytes strategy, which decodes it a
+// character per byte and returns binary tokens
+fn is_byte_input(text: RString, config: &TokenizerConfig) -> bool {
+    matches!(config.strategy, TokenizerStrategy::Bytes) && text.enc_get() == Ruby::get_with(text).ascii8bit_encindex()
+}
+
+// Tokenize a Ruby string as `config` says to decode it
+fn tokenize_text(
+    text: RString,
+    config: &TokenizerConfig,
+    tokenizer: &dyn Tokenizer,
+) -> std::result::Result<RArray, Error> {
+    if is_byte_input(text, config) {
+        // Safe: the bytes are copied into `decoded` straight away
+        let decoded = encoding::decode_bytes(unsafe { text.as_slice() });
+        let tokens = tokenizer.try_tokenize(&decoded).map_err(ruby_error)?;
+        let array = RArray::with_capacity(tokens.len());
+        for token in tokens {
+            array.push(RString::from_slice(&encoding::encode_bytes(&token)))?;
+        }
+        return Ok(array);
+    }
+
+    with_text(text, config.invalid_utf8, |text| {
+        tokens_to_array(tokenizer.try_tokenize(text).map_err(ruby_error)?)
+    })
+}
+
+// Tokens with their offsets and attributes, as arrays of
+// [text, start, end, part_of_speech, base_form, reading] for TokenKit::Token.
+// Offsets are byte offsets into the text as tokenized
+fn analyze_text(
+    text: RString,
+    config: &TokenizerConfig,
+    tokenizer: &dyn Tokenizer,
+) -> std::result::Result<RArray, Error> {
+    if is_byte_input(text, config) {
+        // Safe: the bytes are copied into `decoded` straight away
+        let decoded = encoding::decode_bytes(unsafe { text.as_slice() });
+        let tokens = tokenizer.try_tokenize_with_offsets(&decoded).map_err(ruby_error)?;
+        let byte_offset = input_offsets(&decoded, &tokens);
+        let array = RArray::with_capacity(tokens.len());
+        for token in &tokens {
+            let bytes = RString::from_slice(&encoding::encode_bytes(&token.text)).as_value();
+            array.push(token_fields(token, bytes, byte_offset(token.start), byte_offset(token.end))?)?;
+        }
+        return Ok(array);
+    }
+
+    with_text(text, config.invalid_utf8, |text| {
+        let tokens = tokenizer.try_tokenize_with_offsets(text).map_err(ruby_error)?;
+        let array = RArray::with_capacity(tokens.len());
+        for token in &tokens {
+            let text = RString::new(&token.text).as_value();
+            array.push(token_fields(token, text, token.start, token.end)?)?;
+        }
+        Ok(array)
+    })
+}
+
+// Every byte was decoded to one character, so a token's offset in the input
+// is the number of characters before it in `decoded`. Counts them for every
+// offset in one pass over `decoded`
+fn input_offsets(decoded: &str, tokens: &[Token<'_>]) -> impl Fn(usize) -> usize {
+    let mut offsets: Vec<usize> = tokens.iter().flat_map(|token| [token.start, token.end]).collect();
+    offsets.sort_unstable();
+    offsets.dedup();
 
-    // Update cache with new config and clear tokenizer
-    let mut cache = DEFAULT_CACHE
-        .lock()
-        .map_err(|e| TokenizerError::MutexError(e.to_string()))?;
-    cache.config = config;
-    cache.tokenizer = None; // Invalidate cached tokenizer
+    let mut boundaries = decoded.char_indices().map(|(index, _)| index).chain([decoded.len()]).enumerate();
+    let counts: Vec<usize> = offsets
+        .iter()
+        .map(|&offset| boundaries.find(|&(_, index)| index == offset).map_or(0, |(count, _)| count))
+        .collect();
+
+    move |offset| counts[offsets.binary_search(&offset).unwrap_or_default()]
+}
 
+fn token_fields(token: &Token, text: Value, start: usize, end: usize) -> std::result::Result<RArray, Error> {
+    let attributes = token.attributes.as_deref();
+    let fields = RArray::with_capacity(6);
+    fields.push(text)?;
+    fields.push(start)?;
+    fields.push(end)?;
+    fields.push(attributes.map(|a| a.part_of_speech.as_str()))?;
+    fields.push(attributes.and_then(|a| a.base_form.as_deref()))?;
+    fields.push(attributes.and_then(|a| a.reading.as_deref()))?;
+    Ok(fields)
+}
+
+// Tokenize with the default tokenizer. The snapshot keeps it alive even if
+// another thread swaps in a new configuration meanwhile
+fn tokenize(text: RString) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let current = DEFAULT.load_full();
+        tokenize_text(text, &current.config, current.tokenizer.as_ref())
+    })
+}
+
+fn analyze(text: RString) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let current = DEFAULT.load_full();
+        analyze_text(text, &current.config, current.tokenizer.as_ref())
+    })
+}
+
+// Compile `config` up front, then publish it; a failed build leaves the
+// previous default in place
+fn set_default(config: TokenizerConfig) -> std::result::Result<(), Error> {
+    let tokenizer = from_config(config.clone()).map_err(ruby_error)?;
+    DEFAULT.store(Arc::new(DefaultTokenizer { config, tokenizer }));
     Ok(())
 }
 
+// Configure sets the default configuration
+fn configure(config_hash: RHash) -> std::result::Result<(), Error> {
+    guard(|| {
+        let config = parse_config_from_hash(config_hash)?;
+        set_default(config)
+    })
+}
+
 // Reset to factory defaults
 fn reset() -> std::result::Result<(), Error> {
-    let mut cache = DEFAULT_CACHE
-        .lock()
-        .map_err(|e| TokenizerError::MutexError(e.to_string()))?;
-    cache.config = TokenizerConfig::default();
-    cache.tokenizer = None; // Clear cached tokenizer
-    Ok(())
+    guard(|| {
+        set_default(TokenizerConfig::default())?;
+        ANALYZERS.clear();
+        Ok(())
+    })
 }
 
 // Get current default configuration
 fn config_hash() -> std::result::Result<RHash, Error> {
-    let cache = DEFAULT_CACHE
-        .lock()
-        .map_err(|e| TokenizerError::MutexError(e.to_string()))?;
-
-    config_to_hash(&cache.config)
+    guard(|| {
+        config_to_hash(&DEFAULT.load().config)
+    })
 }
 
 // Helper function to convert config to RHash
@@ -94,6 +245,11 @@ fn config_to_hash(config: &TokenizerConfig) -> std::result::Result<RHash, Error>
         TokenizerStrategy::CharGroup { .. } => "char_group",
         TokenizerStrategy::Letter => "letter",
         TokenizerStrategy::Lowercase => "lowercase",
+        TokenizerStrategy::Bytes => "bytes",
+        TokenizerStrategy::Cjk { .. } => "cjk",
+        TokenizerStrategy::Japanese { .. } => "japanese",
+        TokenizerStrategy::HuggingFace { .. } => "huggingface",
+        TokenizerStrategy::Router { .. } => "router",
     };
     hash.aset("strategy", strategy_str)?;
 
@@ -123,6 +279,49 @@ fn config_to_hash(config: &TokenizerConfig) -> std::result::Result<RHash, Error>
         hash.aset("split_on_chars", split_on_chars.as_str())?;
     }
 
+    if let TokenizerStrategy::Cjk {
+        dictionary,
+        user_dictionaries,
+        search_mode,
+        hmm,
+    } = &config.strategy
+    {
+        hash.aset("dictionary", dictionary.as_deref())?;
+        hash.aset("user_dictionaries", RArray::from_iter(user_dictionaries.iter().map(String::as_str)))?;
+        hash.aset("search_mode", *search_mode)?;
+        hash.aset("hmm", *hmm)?;
+    }
+
+    if let TokenizerStrategy::Japanese {
+        dictionary,
+        user_dictionaries,
+    } = &config.strategy
+    {
+        hash.aset("dictionary", dictionary.as_str())?;
+        hash.aset("user_dictionaries", RArray::from_iter(user_dictionaries.iter().map(String::as_str)))?;
+    }
+
+    if let TokenizerStrategy::HuggingFace { path, add_special_tokens } = &config.strategy {
+        hash.aset("path", path.as_str())?;
+        hash.aset("add_special_tokens", *add_special_tokens)?;
+    }
+
+    if let TokenizerStrategy::Router { routes, fallback } = &config.strategy {
+        let entries = RArray::new();
+        for route in routes {
+            let entry = RHash::new();
+            entry.aset("scripts", RArray::from_iter(route.scripts.iter().map(String::as_str)))?;
+            entry.aset("languages", RArray::from_iter(route.languages.iter().map(String::as_str)))?;
+            entry.aset("tokenizer", config_to_hash(&route.tokenizer)?)?;
+            entries.push(entry)?;
+        }
+        hash.aset("routes", entries)?;
+        match fallback {
+            Some(fallback) => hash.aset("fallback", config_to_hash(fallback)?)?,
+            None => hash.aset("fallback", ())?,
+        }
+    }
+
     hash.aset("lowercase", config.lowercase)?;
     hash.aset("remove_punctuation", config.remove_punctuation)?;
 
@@ -132,200 +331,706 @@ fn config_to_hash(config: &TokenizerConfig) -> std::result::Result<RHash, Error>
     }
     hash.aset("preserve_patterns", patterns)?;
 
+    // Left out when empty, like in config files
+    if !config.word_break_dictionaries.is_empty() {
+        let dictionaries = RArray::from_iter(config.word_break_dictionaries.iter().map(String::as_str));
+        hash.aset("word_break_dictionaries", dictionaries)?;
+    }
+
+    let char_filters = RArray::new();
+    for filter in &config.char_filters {
+        let entry = RHash::new();
+        match filter {
+            CharFilter::CjkWidth => entry.aset("type", "cjk_width")?,
+        }
+        char_filters.push(entry)?;
+    }
+    hash.aset("char_filters", char_filters)?;
+
+    let filters = RArray::new();
+    for filter in &config.filters {
+        let entry = RHash::new();
+        match filter {
+            TokenFilter::CjkBigram { output_unigrams } => {
+                entry.aset("type", "cjk_bigram")?;
+                entry.aset("output_unigrams", *output_unigrams)?;
+            }
+            TokenFilter::ArabicNormalization => entry.aset("type", "arabic_normalization")?,
+            TokenFilter::PersianNormalization => entry.aset("type", "persian_normalization")?,
+            TokenFilter::HebrewNormalization => entry.aset("type", "hebrew_normalization")?,
+            TokenFilter::ArabicStem => entry.aset("type", "arabic_stem")?,
+            TokenFilter::IndicNormalization => entry.aset("type", "indic_normalization")?,
+            TokenFilter::CjkWidth => entry.aset("type", "cjk_width")?,
+        }
+        filters.push(entry)?;
+    }
+    hash.aset("filters", filters)?;
+
+    let limits = &config.limits;
+    if let Some(max) = limits.max_input_bytes {
+        hash.aset("max_input_bytes", max)?;
+    }
+    if let Some(max) = limits.max_tokens {
+        hash.aset("max_tokens", max)?;
+    }
+    if let Some(max) = limits.max_token_length {
+        hash.aset("max_token_length", max)?;
+    }
+    let overflow = match limits.overflow {
+        OverflowPolicy::Error => "error",
+        OverflowPolicy::Truncate => "truncate",
+    };
+    hash.aset("overflow", overflow)?;
+    let long_tokens = match limits.long_tokens {
+        LongTokenPolicy::Truncate => "truncate",
+        LongTokenPolicy::Drop => "drop",
+    };
+    hash.aset("long_tokens", long_tokens)?;
+
+    let regex_limits = &config.regex_limits;
+    if let Some(max) = regex_limits.size_limit {
+        hash.aset("regex_size_limit", max)?;
+    }
+    if let Some(max) = regex_limits.dfa_size_limit {
+        hash.aset("regex_dfa_size_limit", max)?;
+    }
+    if let Some(max) = regex_limits.max_preserve_patterns {
+        hash.aset("max_preserve_patterns", max)?;
+    }
+
+    let invalid_utf8 = match config.invalid_utf8 {
+        InvalidUtf8Policy::Raise => "raise",
+        InvalidUtf8Policy::Replace => "replace",
+        InvalidUtf8Policy::Skip => "skip",
+    };
+    hash.aset("invalid_utf8", invalid_utf8)?;
+    if let Some(locale) = &config.locale {
+        hash.aset("locale", locale.as_str())?;
+    }
+
     Ok(hash)
 }
 
-// Parse config from Ruby hash
+// Parse config from Ruby hash, reporting unknown and unused keys along with
+// every problem in the values
 fn parse_config_from_hash(config_hash: RHash) -> std::result::Result<TokenizerConfig, Error> {
-    let strategy_val = config_hash.get("strategy");
-    let strategy = if let Some(val) = strategy_val {
-        let strategy_str: String = TryConvert::try_convert(val)?;
-        match strategy_str.as_str() {
-            "whitespace" => TokenizerStrategy::Whitespace,
-            "unicode" => TokenizerStrategy::Unicode,
-            "pattern" => {
-                let regex_val = config_hash
-                    .get("regex")
-                    .ok_or_else(|| {
-                        TokenizerError::InvalidConfiguration(
-                            "pattern strategy requires regex parameter".to_string()
-                        )
-                    })?;
-                let regex: String = TryConvert::try_convert(regex_val)?;
-                TokenizerStrategy::Pattern { regex }
-            }
-            "sentence" => TokenizerStrategy::Sentence,
-            "grapheme" => {
-                let extended_val = config_hash.get("extended");
-                let extended = if let Some(val) = extended_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    true
-                };
-                TokenizerStrategy::Grapheme { extended }
-            }
-            "keyword" => TokenizerStrategy::Keyword,
-            "edge_ngram" => {
-                let min_gram_val = config_hash.get("min_gram");
-                let min_gram = if let Some(val) = min_gram_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    2
-                };
-                let max_gram_val = config_hash.get("max_gram");
-                let max_gram = if let Some(val) = max_gram_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    10
-                };
-                TokenizerStrategy::EdgeNgram { min_gram, max_gram }
-            }
-            "path_hierarchy" => {
-                let delimiter_val = config_hash.get("delimiter");
-                let delimiter = if let Some(val) = delimiter_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    "/".to_string()
-                };
-                TokenizerStrategy::PathHierarchy { delimiter }
+    let mut problems = option_problems(config_hash)?;
+
+    match parse_config(config_hash) {
+        Ok(config) => {
+            problems.extend(validation::problems(&config));
+            validation::into_result(problems).map_err(ruby_error)?;
+            Ok(config)
+        }
+        Err(problem) => {
+            add_problem(&mut problems, problem);
+            Err(ruby_error(validation::into_error(problems)))
+        }
+    }
+}
+
+// Check the hash's keys against the options its strategy takes
+fn option_problems(config_hash: RHash) -> std::result::Result<Vec<Problem>, Error> {
+    let mut keys = Vec::new();
+    config_hash.foreach(|key: Value, _: Value| {
+        keys.push(key.to_string());
+        Ok(ForEach::Continue)
+    })?;
+
+    let strategy = match config_hash.get("strategy") {
+        Some(val) => String::try_convert(val).unwrap_or_default(),
+        None => "unicode".to_string(),
+    };
+    let mut problems = validation::option_problems(&strategy, keys.iter().map(String::as_str));
+
+    // The type and option names of each filter; a malformed list is left to
+    // parse_char_filters and parse_filters
+    for list in ["char_filters", "filters"] {
+        let filters = config_hash
+            .get(list)
+            .and_then(|val| Vec::<RHash>::try_convert(val).ok())
+            .unwrap_or_default();
+        for (index, entry) in filters.into_iter().enumerate() {
+            let mut options = Vec::new();
+            entry.foreach(|key: Value, _: Value| {
+                options.push(key.to_string());
+                Ok(ForEach::Continue)
+            })?;
+            let kind = entry
+                .get("type")
+                .and_then(|val| String::try_convert(val).ok())
+                .unwrap_or_default();
+            let path = format!("{}[{}]", list, index);
+            let options = options.iter().map(String::as_str);
+            problems.extend(match list {
+                "char_filters" => validation::char_filter_problems(&path, &kind, options),
+                _ => validation::filter_problems(&path, &kind, options),
+            });
+        }
+    }
+
+    // Each route's tokenizer is a config hash of its own
+    let routes = config_hash
+        .get("routes")
+        .and_then(|val| Vec::<RHash>::try_convert(val).ok())
+        .unwrap_or_default();
+    for (index, entry) in routes.into_iter().enumerate() {
+        let path = format!("routes[{}]", index);
+        entry.foreach(|key: Value, _: Value| {
+            let key = key.to_string();
+            if !["scripts", "languages", "tokenizer"].contains(&key.as_str()) {
+                let suggestion = validation::suggest(&key, ["scripts", "languages", "tokenizer"]);
+                problems.push(Problem::new(
+                    format!("{}.{}", path, key),
+                    TokenizerError::UnknownOption { key, suggestion },
+                ));
             }
-            "url_email" => TokenizerStrategy::UrlEmail,
-            "ngram" => {
-                let min_gram_val = config_hash.get("min_gram");
-                let min_gram = if let Some(val) = min_gram_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    2
-                };
-                let max_gram_val = config_hash.get("max_gram");
-                let max_gram = if let Some(val) = max_gram_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    10
-                };
-                TokenizerStrategy::Ngram { min_gram, max_gram }
+            Ok(ForEach::Continue)
+        })?;
+        if let Some(tokenizer) = entry.get("tokenizer").and_then(|val| RHash::try_convert(val).ok()) {
+            problems.extend(nested(&format!("{}.tokenizer", path), option_problems(tokenizer)?));
+        }
+    }
+    if let Some(fallback) = config_hash.get("fallback").and_then(|val| RHash::try_convert(val).ok()) {
+        problems.extend(nested("fallback", option_problems(fallback)?));
+    }
+
+    Ok(problems)
+}
+
+// Problems of a config hash inside another one, at paths under `prefix`
+fn nested(prefix: &str, problems: Vec<Problem>) -> Vec<Problem> {
+    problems
+        .into_iter()
+        .map(|problem| Problem::new(format!("{}.{}", prefix, problem.path), problem.error))
+        .collect()
+}
+
+// An unknown strategy is already reported by option_problems
+fn add_problem(problems: &mut Vec<Problem>, problem: Problem) {
+    if !problems.iter().any(|reported| reported.path == problem.path) {
+        problems.push(problem);
+    }
+}
+
+// Read `key` from the hash, naming it when the value has the wrong type
+fn fetch<T: TryConvert>(config_hash: RHash, key: &str) -> std::result::Result<Option<T>, Problem> {
+    match config_hash.get(key) {
+        Some(val) => TryConvert::try_convert(val).map(Some).map_err(|e: Error| {
+            Problem::new(
+                key,
+                TokenizerError::InvalidConfiguration {
+                    key: key.to_string(),
+                    message: format!("{} is invalid: {}", key, e),
+                },
+            )
+        }),
+        None => Ok(None),
+    }
+}
+
+// Build the config without validating its values
+fn parse_config(config_hash: RHash) -> std::result::Result<TokenizerConfig, Problem> {
+    let required = |key: &str, strategy: &str| {
+        Problem::new(
+            key,
+            TokenizerError::InvalidConfiguration {
+                key: key.to_string(),
+                message: format!("{} strategy requires {} parameter", strategy, key),
+            },
+        )
+    };
+
+    let strategy_str: String = fetch(config_hash, "strategy")?.unwrap_or_else(|| "unicode".to_string());
+    let strategy = match strategy_str.as_str() {
+        "whitespace" => TokenizerStrategy::Whitespace,
+        "unicode" => TokenizerStrategy::Unicode,
+        "pattern" => TokenizerStrategy::Pattern {
+            regex: fetch(config_hash, "regex")?.ok_or_else(|| required("regex", "pattern"))?,
+        },
+        "sentence" => TokenizerStrategy::Sentence,
+        "grapheme" => TokenizerStrategy::Grapheme {
+            extended: fetch(config_hash, "extended")?.unwrap_or(true),
+        },
+        "keyword" => TokenizerStrategy::Keyword,
+        "edge_ngram" => TokenizerStrategy::EdgeNgram {
+            min_gram: fetch(config_hash, "min_gram")?.unwrap_or(2),
+            max_gram: fetch(config_hash, "max_gram")?.unwrap_or(10),
+        },
+        "path_hierarchy" => TokenizerStrategy::PathHierarchy {
+            delimiter: fetch(config_hash, "delimiter")?.unwrap_or_else(|| "/".to_string()),
+        },
+        "url_email" => TokenizerStrategy::UrlEmail,
+        "ngram" => TokenizerStrategy::Ngram {
+            min_gram: fetch(config_hash, "min_gram")?.unwrap_or(2),
+            max_gram: fetch(config_hash, "max_gram")?.unwrap_or(10),
+        },
+        "char_group" => TokenizerStrategy::CharGroup {
+            split_on_chars: fetch(config_hash, "split_on_chars")?.unwrap_or_else(|| " \t\n\r".to_string()),
+        },
+        "letter" => TokenizerStrategy::Letter,
+        "lowercase" => TokenizerStrategy::Lowercase,
+        "bytes" => TokenizerStrategy::Bytes,
+        "cjk" => TokenizerStrategy::Cjk {
+            dictionary: fetch::<Option<String>>(config_hash, "dictionary")?.flatten(),
+            user_dictionaries: fetch(config_hash, "user_dictionaries")?.unwrap_or_default(),
+            search_mode: fetch(config_hash, "search_mode")?.unwrap_or(false),
+            hmm: fetch(config_hash, "hmm")?.unwrap_or(true),
+        },
+        "japanese" => TokenizerStrategy::Japanese {
+            dictionary: fetch::<Option<String>>(config_hash, "dictionary")?
+                .flatten()
+                .ok_or_else(|| required("dictionary", "japanese"))?,
+            user_dictionaries: fetch(config_hash, "user_dictionaries")?.unwrap_or_default(),
+        },
+        "huggingface" => TokenizerStrategy::HuggingFace {
+            path: fetch(config_hash, "path")?.ok_or_else(|| required("path", "huggingface"))?,
+            add_special_tokens: fetch(config_hash, "add_special_tokens")?.unwrap_or(true),
+        },
+        "router" => TokenizerStrategy::Router {
+            routes: parse_routes(config_hash)?,
+            fallback: match fetch::<Option<RHash>>(config_hash, "fallback")?.flatten() {
+                Some(fallback) => Some(Box::new(parse_config(fallback).map_err(|problem| {
+                    Problem::new(format!("fallback.{}", problem.path), problem.error)
+                })?)),
+                None => None,
+            },
+        },
+        _ => return Err(Problem::new("strategy", TokenizerError::UnknownStrategy(strategy_str))),
+    };
+
+    Ok(TokenizerConfig {
+        strategy,
+        lowercase: fetch(config_hash, "lowercase")?.unwrap_or(true),
+        locale: fetch::<Option<String>>(config_hash, "locale")?.flatten(),
+        remove_punctuation: fetch(config_hash, "remove_punctuation")?.unwrap_or(false),
+        preserve_patterns: fetch(config_hash, "preserve_patterns")?.unwrap_or_default(),
+        word_break_dictionaries: fetch(config_hash, "word_break_dictionaries")?.unwrap_or_default(),
+        char_filters: parse_char_filters(config_hash)?,
+        filters: parse_filters(config_hash)?,
+        limits: parse_limits(config_hash)?,
+        regex_limits: parse_regex_limits(config_hash)?,
+        invalid_utf8: parse_invalid_utf8(config_hash)?,
+    })
+}
+
+// Each entry of `routes` is a hash with its "scripts", "languages" and a
+// "tokenizer" config hash
+fn parse_routes(config_hash: RHash) -> std::result::Result<Vec<Route>, Problem> {
+    let entries: Vec<RHash> = fetch(config_hash, "routes")?.unwrap_or_default();
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let path = format!("routes[{}]", index);
+            let at = |problem: Problem| Problem::new(format!("{}.{}", path, problem.path), problem.error);
+            let tokenizer = match fetch::<RHash>(entry, "tokenizer").map_err(at)? {
+                Some(tokenizer) => parse_config(tokenizer).map_err(|problem| {
+                    Problem::new(format!("{}.tokenizer.{}", path, problem.path), problem.error)
+                })?,
+                None => TokenizerConfig::default(),
+            };
+
+            Ok(Route {
+                scripts: fetch(entry, "scripts").map_err(at)?.unwrap_or_default(),
+                languages: fetch(entry, "languages").map_err(at)?.unwrap_or_default(),
+                tokenizer,
+            })
+        })
+        .collect()
+}
+
+// Each entry of `char_filters` is a hash with the filter's "type"
+fn parse_char_filters(config_hash: RHash) -> std::result::Result<Vec<CharFilter>, Problem> {
+    let entries: Vec<RHash> = fetch(config_hash, "char_filters")?.unwrap_or_default();
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let path = format!("char_filters[{}]", index);
+            let at = |problem: Problem| Problem::new(format!("{}.{}", path, problem.path), problem.error);
+            let kind: String = fetch(entry, "type").map_err(at)?.unwrap_or_default();
+
+            match kind.as_str() {
+                "cjk_width" => Ok(CharFilter::CjkWidth),
+                _ => Err(validation::char_filter_problems(&path, &kind, []).remove(0)),
             }
-            "char_group" => {
-                let split_on_chars_val = config_hash.get("split_on_chars");
-                let split_on_chars = if let Some(val) = split_on_chars_val {
-                    TryConvert::try_convert(val)?
-                } else {
-                    " \t\n\r".to_string()
-                };
-                TokenizerStrategy::CharGroup { split_on_chars }
+        })
+        .collect()
+}
+
+// Each entry of `filters` is a hash with the filter's "type" and options
+fn parse_filters(config_hash: RHash) -> std::result::Result<Vec<TokenFilter>, Problem> {
+    let entries: Vec<RHash> = fetch(config_hash, "filters")?.unwrap_or_default();
+
+    entries
+        .into_iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let path = format!("filters[{}]", index);
+            let at = |problem: Problem| Problem::new(format!("{}.{}", path, problem.path), problem.error);
+            let kind: String = fetch(entry, "type").map_err(at)?.unwrap_or_default();
+
+            match kind.as_str() {
+                "cjk_bigram" => Ok(TokenFilter::CjkBigram {
+                    output_unigrams: fetch(entry, "output_unigrams").map_err(at)?.unwrap_or(false),
+                }),
+                "arabic_normalization" => Ok(TokenFilter::ArabicNormalization),
+                "persian_normalization" => Ok(TokenFilter::PersianNormalization),
+                "hebrew_normalization" => Ok(TokenFilter::HebrewNormalization),
+                "arabic_stem" => Ok(TokenFilter::ArabicStem),
+                "indic_normalization" => Ok(TokenFilter::IndicNormalization),
+                "cjk_width" => Ok(TokenFilter::CjkWidth),
+                _ => Err(validation::filter_problems(&path, &kind, []).remove(0)),
             }
-            "letter" => TokenizerStrategy::Letter,
-            "lowercase" => TokenizerStrategy::Lowercase,
+        })
+        .collect()
+}
+
+fn parse_invalid_utf8(config_hash: RHash) -> std::result::Result<InvalidUtf8Policy, Problem> {
+    match fetch::<String>(config_hash, "invalid_utf8")? {
+        Some(policy) => match policy.as_str() {
+            "raise" => Ok(InvalidUtf8Policy::Raise),
+            "replace" => Ok(InvalidUtf8Policy::Replace),
+            "skip" => Ok(InvalidUtf8Policy::Skip),
+            _ => Err(Problem::new(
+                "invalid_utf8",
+                TokenizerError::InvalidConfiguration {
+                    key: "invalid_utf8".to_string(),
+                    message: format!("invalid_utf8 must be raise, replace or skip, got {}", policy),
+                },
+            )),
+        },
+        None => Ok(InvalidUtf8Policy::default()),
+    }
+}
+
+// Limits are flat keys in the hash; a missing or nil key means no limit
+fn parse_limits(config_hash: RHash) -> std::result::Result<Limits, Problem> {
+    let limit = |key: &str| -> std::result::Result<Option<usize>, Problem> {
+        Ok(fetch::<Option<usize>>(config_hash, key)?.flatten())
+    };
+    let invalid = |key: &str, message: String| {
+        Problem::new(
+            key,
+            TokenizerError::InvalidConfiguration {
+                key: key.to_string(),
+                message,
+            },
+        )
+    };
+
+    let overflow = match fetch::<String>(config_hash, "overflow")? {
+        Some(overflow) => match overflow.as_str() {
+            "error" => OverflowPolicy::Error,
+            "truncate" => OverflowPolicy::Truncate,
             _ => {
-                return Err(TokenizerError::UnknownStrategy(strategy_str).into())
+                return Err(invalid(
+                    "overflow",
+                    format!("overflow must be error or truncate, got {}", overflow),
+                ))
             }
-        }
-    } else {
-        TokenizerStrategy::Unicode
+        },
+        None => OverflowPolicy::default(),
     };
 
-    let lowercase_val = config_hash.get("lowercase");
-    let lowercase = if let Some(val) = lowercase_val {
-        TryConvert::try_convert(val)?
-    } else {
-        true
+    let long_tokens = match fetch::<String>(config_hash, "long_tokens")? {
+        Some(long_tokens) => match long_tokens.as_str() {
+            "truncate" => LongTokenPolicy::Truncate,
+            "drop" => LongTokenPolicy::Drop,
+            _ => {
+                return Err(invalid(
+                    "long_tokens",
+                    format!("long_tokens must be truncate or drop, got {}", long_tokens),
+                ))
+            }
+        },
+        None => LongTokenPolicy::default(),
     };
 
-    let remove_punctuation_val = config_hash.get("remove_punctuation");
-    let remove_punctuation = if let Some(val) = remove_punctuation_val {
-        TryConvert::try_convert(val)?
-    } else {
-        false
+    Ok(Limits {
+        max_input_bytes: limit("max_input_bytes")?,
+        max_tokens: limit("max_tokens")?,
+        max_token_length: limit("max_token_length")?,
+        overflow,
+        long_tokens,
+    })
+}
+
+// Regex limits are prefixed in the flat hash, except max_preserve_patterns
+fn parse_regex_limits(config_hash: RHash) -> std::result::Result<RegexLimits, Problem> {
+    let limit = |key: &str| -> std::result::Result<Option<usize>, Problem> {
+        Ok(fetch::<Option<usize>>(config_hash, key)?.flatten())
     };
 
-    let preserve_patterns_val = config_hash.get("preserve_patterns");
-    let preserve_patterns = if let Some(val) = preserve_patterns_val {
-        let array: RArray = TryConvert::try_convert(val)?;
-        let mut patterns = Vec::new();
-        for idx in 0..array.len() {
-            let item = array.entry(idx as isize)?;
-            let pattern_str: String = TryConvert::try_convert(item)?;
-            patterns.push(pattern_str);
+    Ok(RegexLimits {
+        size_limit: limit("regex_size_limit")?,
+        dfa_size_limit: limit("regex_dfa_size_limit")?,
+        max_preserve_patterns: limit("max_preserve_patterns")?,
+    })
+}
+
+// Raise for per-call option names that don't exist or that `strategy`
+// doesn't use; the values are checked once the config is built
+fn check_options(strategy: String, options: Vec<String>) -> std::result::Result<(), Error> {
+    guard(|| {
+        let problems = validation::option_problems(&strategy, options.iter().map(String::as_str));
+        validation::into_result(problems).map_err(ruby_error)
+    })
+}
+
+// Every problem with a config hash as TokenKit::Diagnostic structs, for
+// TokenKit.validate. A value that can't be read at all stops parsing, so it
+// is reported and left out to find the problems after it
+fn validate_config_hash(config_hash: RHash) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let config_hash: RHash = config_hash.funcall("dup", ())?;
+        let mut problems = option_problems(config_hash)?;
+
+        loop {
+            match parse_config(config_hash) {
+                Ok(config) => {
+                    problems.extend(validation::problems(&config));
+                    break;
+                }
+                Err(problem) => {
+                    let removed: Option<Value> = config_hash.delete(problem.path.as_str())?;
+                    add_problem(&mut problems, problem);
+                    if removed.is_none() {
+                        break;
+                    }
+                }
+            }
         }
-        patterns
-    } else {
-        Vec::new()
-    };
 
-    let config = TokenizerConfig {
-        strategy,
-        lowercase,
-        remove_punctuation,
-        preserve_patterns,
-    };
+        let diagnostics = RArray::with_capacity(problems.len());
+        for problem in &problems {
+            diagnostics.push(ruby_diagnostic(&problem.diagnostic())?)?;
+        }
+        Ok(diagnostics)
+    })
+}
 
-    // Validate the configuration
-    validate_config(&config)?;
+// Load config is just an alias for configure (for backward compat)
+fn load_config(config_hash: RHash) -> std::result::Result<(), Error> {
+    configure(config_hash)
+}
 
-    Ok(config)
+// Compile and store an analyzer under `name`, replacing any previous one
+fn register_analyzer(name: String, config_hash: RHash) -> std::result::Result<(), Error> {
+    guard(|| {
+        let config = parse_config_from_hash(config_hash)?;
+        ANALYZERS.register(name, config).map_err(ruby_error)
+    })
 }
 
-// Validate configuration parameters
-fn validate_config(config: &TokenizerConfig) -> std::result::Result<(), TokenizerError> {
-    use TokenizerStrategy::*;
+fn unregister_analyzer(name: String) -> std::result::Result<bool, Error> {
+    guard(|| Ok(ANALYZERS.unregister(&name)))
+}
 
-    match &config.strategy {
-        EdgeNgram { min_gram, max_gram } | Ngram { min_gram, max_gram } => {
-            if *min_gram == 0 {
-                return Err(TokenizerError::InvalidNgramConfig {
-                    min: *min_gram,
-                    max: *max_gram,
-                });
-            }
-            if min_gram > max_gram {
-                return Err(TokenizerError::InvalidNgramConfig {
-                    min: *min_gram,
-                    max: *max_gram,
-                });
-            }
+// Tokenize with a registered analyzer; the registry lock is only held for the lookup
+fn tokenize_with_analyzer(text: RString, name: String) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let analyzer = ANALYZERS
+            .analyzer(&name)
+            .ok_or_else(|| ruby_error(TokenizerError::UnknownAnalyzer(name)))?;
+        tokenize_text(text, &analyzer.config, analyzer.tokenizer.as_ref())
+    })
+}
+
+fn analyze_with_analyzer(text: RString, name: String) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let analyzer = ANALYZERS
+            .analyzer(&name)
+            .ok_or_else(|| ruby_error(TokenizerError::UnknownAnalyzer(name)))?;
+        analyze_text(text, &analyzer.config, analyzer.tokenizer.as_ref())
+    })
+}
+
+// Parse the contents of a JSON/YAML config file into a config hash
+fn parse_config_file(contents: String, format: String) -> std::result::Result<RHash, Error> {
+    guard(|| {
+        let format: ConfigFormat = format.parse().map_err(ruby_error)?;
+        let config = TokenizerConfig::parse(&contents, format).map_err(ruby_error)?;
+        config_to_hash(&config)
+    })
+}
+
+// Import Elasticsearch/OpenSearch analysis settings as name => config hash
+fn import_elasticsearch(json: String) -> std::result::Result<RHash, Error> {
+    guard(|| {
+        let configs = elasticsearch::import_analysis(&json).map_err(ruby_error)?;
+        let hash = RHash::new();
+        for (name, config) in &configs {
+            hash.aset(name.as_str(), config_to_hash(config)?)?;
         }
-        PathHierarchy { delimiter } => {
-            if delimiter.is_empty() {
-                return Err(TokenizerError::EmptyDelimiter {
-                    tokenizer: "PathHierarchy".to_string(),
-                });
+        Ok(hash)
+    })
+}
+
+// The most likely language of `text` as [code, name, confidence, reliable],
+// or nil. Invalid UTF-8 is replaced, since detection only needs the letters
+fn detect_language(text: RString) -> std::result::Result<Option<RArray>, Error> {
+    guard(|| {
+        with_text(text, InvalidUtf8Policy::Replace, |text| {
+            let Some(detected) = language::detect_language(text) else {
+                return Ok(None);
+            };
+            let fields = RArray::with_capacity(4);
+            fields.push(detected.code)?;
+            fields.push(detected.name)?;
+            fields.push(detected.confidence)?;
+            fields.push(detected.reliable)?;
+            Ok(Some(fields))
+        })
+    })
+}
+
+// Serialize a config hash as a JSON/YAML config file
+fn dump_config(config_hash: RHash, format: String) -> std::result::Result<String, Error> {
+    guard(|| {
+        let format: ConfigFormat = format.parse().map_err(ruby_error)?;
+        let config = parse_config_from_hash(config_hash)?;
+        config.dump(format).map_err(ruby_error)
+    })
+}
+
+// Tokenize with a specific config (creates fresh tokenizer)
+fn tokenize_with_config(text: RString, config_hash: RHash) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let config = parse_config_from_hash(config_hash)?;
+
+        // Create fresh tokenizer from config
+        let tokenizer = from_config(config.clone()).map_err(ruby_error)?;
+
+        // Tokenize and return
+        tokenize_text(text, &config, tokenizer.as_ref())
+    })
+}
+
+fn analyze_with_config(text: RString, config_hash: RHash) -> std::result::Result<RArray, Error> {
+    guard(|| {
+        let config = parse_config_from_hash(config_hash)?;
+        let tokenizer = from_config(config.clone()).map_err(ruby_error)?;
+        analyze_text(text, &config, tokenizer.as_ref())
+    })
+}
+
+// Yield tokens to the block one at a time instead of building an array.
+// Takes an owned copy of the text because the block may mutate the Ruby string
+fn each_token(text: RString, config_hash: RHash) -> std::result::Result<(), Error> {
+    guard(|| {
+        let config = parse_config_from_hash(config_hash)?;
+        let tokenizer = from_config(config.clone()).map_err(ruby_error)?;
+
+        if is_byte_input(text, &config) {
+            // Safe: the bytes are copied into `decoded` straight away
+            let decoded = encoding::decode_bytes(unsafe { text.as_slice() });
+            for token in tokenizer.try_tokens(&decoded) {
+                let token = token.map_err(ruby_error)?;
+                let token = RString::from_slice(&encoding::encode_bytes(&token.text));
+                magnus::block::yield_value::<RString, magnus::Value>(token)?;
             }
+            return Ok(());
         }
-        Pattern { regex } => {
-            // Validate regex pattern
-            regex::Regex::new(regex).map_err(|e| TokenizerError::InvalidRegex {
-                pattern: regex.clone(),
-                error: e.to_string(),
-            })?;
+
+        let text = with_text(text, config.invalid_utf8, |text| Ok(text.to_string()))?;
+        for token in tokenizer.try_tokens(&text) {
+            let token = token.map_err(ruby_error)?;
+            magnus::block::yield_value::<&str, magnus::Value>(&token.text)?;
         }
-        _ => {}
+
+        Ok(())
+    })
+}
+
+// Chunked tokenization state for IO input, exposed as TokenKit::TokenStream.
+// IO#read hands out binary chunks, so the bytes strategy reads the whole
+// stream a character per byte and returns binary tokens
+#[magnus::wrap(class = "TokenKit::TokenStream", free_immediately, size)]
+struct RbTokenStream {
+    stream: RefCell<TokenStream>,
+    bytes: bool,
+}
+
+impl RbTokenStream {
+    fn new(config_hash: RHash) -> std::result::Result<Self, Error> {
+        guard(|| {
+            let config = parse_config_from_hash(config_hash)?;
+            let bytes = matches!(config.strategy, TokenizerStrategy::Bytes);
+            let policy = config.invalid_utf8;
+            let limits = config.limits.clone();
+            let tokenizer = from_config(config).map_err(ruby_error)?;
+            let mut stream = TokenStream::new(tokenizer).with_invalid_utf8(policy).with_limits(limits);
+            if bytes {
+                stream = stream.with_byte_input();
+            }
+            Ok(Self { stream: RefCell::new(stream), bytes })
+        })
     }
 
-    // Validate preserve patterns
-    for pattern in &config.preserve_patterns {
-        regex::Regex::new(pattern).map_err(|e| TokenizerError::InvalidRegex {
-            pattern: pattern.clone(),
-            error: e.to_string(),
-        })?;
+    fn push(&self, chunk: RString) -> std::result::Result<RArray, Error> {
+        guard(|| {
+            // Safe: the slice is consumed before control returns to Ruby
+            let tokens = self.stream.borrow_mut().try_push(unsafe { chunk.as_slice() }).map_err(ruby_error)?;
+            self.to_array(tokens)
+        })
     }
 
-    Ok(())
-}
+    fn finish(&self) -> std::result::Result<RArray, Error> {
+        guard(|| {
+            let tokens = self.stream.borrow_mut().try_finish().map_err(ruby_error)?;
+            self.to_array(tokens)
+        })
+    }
 
-// Load config is just an alias for configure (for backward compat)
-fn load_config(config_hash: RHash) -> std::result::Result<(), Error> {
-    configure(config_hash)
+    fn to_array(&self, tokens: Vec<String>) -> std::result::Result<RArray, Error> {
+        let array = RArray::with_capacity(tokens.len());
+        for token in tokens {
+            if self.bytes {
+                array.push(RString::from_slice(&encoding::encode_bytes(&token)))?;
+            } else {
+                array.push(token)?;
+            }
+        }
+        Ok(array)
+    }
 }
 
-// Tokenize with a specific config (creates fresh tokenizer)
-fn tokenize_with_config(text: String, config_hash: RHash) -> std::result::Result<Vec<String>, Error> {
-    let config = parse_config_from_hash(config_hash)?;
+// A Hugging Face tokenizer.json with its ids and decoder, exposed as
+// TokenKit::HuggingFaceTokenizer. lib/tokenkit/huggingface_tokenizer.rb
+// wraps the results in keyword-argument methods
+#[magnus::wrap(class = "TokenKit::HuggingFaceTokenizer", free_immediately, size)]
+struct RbHuggingFaceTokenizer(HuggingFaceTokenizer);
 
-    // Create fresh tokenizer from config
-    let tokenizer = tokenizer::from_config(config)?;
+impl RbHuggingFaceTokenizer {
+    fn new(path: String) -> std::result::Result<Self, Error> {
+        guard(|| Ok(Self(HuggingFaceTokenizer::from_file(path).map_err(ruby_error)?)))
+    }
 
-    // Tokenize and return
-    Ok(tokenizer.tokenize(&text))
+    // [ids, tokens, offsets], each offset a [start, end] pair of byte offsets
+    fn encode(&self, text: RString, add_special_tokens: bool) -> std::result::Result<RArray, Error> {
+        guard(|| {
+            with_text(text, InvalidUtf8Policy::Raise, |text| {
+                let encoding = self.0.encode(text, add_special_tokens);
+                let offsets = RArray::with_capacity(encoding.offsets.len());
+                for offset in encoding.offsets {
+                    offsets.push(offset)?;
+                }
+                let fields = RArray::with_capacity(3);
+                fields.push(RArray::from_vec(encoding.ids))?;
+                fields.push(RArray::from_vec(encoding.tokens))?;
+                fields.push(offsets)?;
+                Ok(fields)
+            })
+        })
+    }
+
+    fn decode(&self, ids: Vec<u32>, skip_special_tokens: bool) -> std::result::Result<String, Error> {
+        guard(|| Ok(self.0.decode(&ids, skip_special_tokens)))
+    }
 }
 
 #[magnus::init]
@@ -339,8 +1044,43 @@ fn init(_ruby: &magnus::Ruby) -> std::result::Result<(), Error> {
     module.define_module_function("_config_hash", function!(config_hash, 0))?;
     module.define_module_function("_load_config", function!(load_config, 1))?;
 
+    // Named analyzers
+    module.define_module_function("_register_analyzer", function!(register_analyzer, 2))?;
+    module.define_module_function("_unregister_analyzer", function!(unregister_analyzer, 1))?;
+    module.define_module_function("_tokenize_with_analyzer", function!(tokenize_with_analyzer, 2))?;
+
+    // Config files
+    module.define_module_function("_parse_config_file", function!(parse_config_file, 2))?;
+    module.define_module_function("_dump_config", function!(dump_config, 2))?;
+    module.define_module_function("_import_elasticsearch", function!(import_elasticsearch, 1))?;
+
+    // Validation
+    module.define_module_function("_validate_config", function!(validate_config_hash, 1))?;
+    module.define_module_function("_check_options", function!(check_options, 2))?;
+
     // New instance-based function
     module.define_module_function("_tokenize_with_config", function!(tokenize_with_config, 2))?;
 
+    // Tokens with offsets and attributes
+    module.define_module_function("_analyze", function!(analyze, 1))?;
+    module.define_module_function("_analyze_with_analyzer", function!(analyze_with_analyzer, 2))?;
+    module.define_module_function("_analyze_with_config", function!(analyze_with_config, 2))?;
+
+    // Language identification
+    module.define_module_function("_detect_language", function!(detect_language, 1))?;
+
+    // Streaming functions
+    module.define_module_function("_each_token", function!(each_token, 2))?;
+    let stream_class = module.define_class("TokenStream", class::object())?;
+    stream_class.define_singleton_method("new", function!(RbTokenStream::new, 1))?;
+    stream_class.define_method("push", method!(RbTokenStream::push, 1))?;
+    stream_class.define_method("finish", method!(RbTokenStream::finish, 0))?;
+
+    // Hugging Face ids and decoding
+    let huggingface_class = module.define_class("HuggingFaceTokenizer", class::object())?;
+    huggingface_class.define_singleton_method("new", function!(RbHuggingFaceTokenizer::new, 1))?;
+    huggingface_class.define_method("_encode", method!(RbHuggingFaceTokenizer::encode, 2))?;
+    huggingface_class.define_method("_decode", method!(RbHuggingFaceTokenizer::decode, 2))?;
+
     Ok(())
 }
\ No newline at end of file
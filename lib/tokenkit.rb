@@ -1,6 +1,11 @@
 # frozen_string_literal: true
 
+require "json"
 require_relative "tokenkit/version"
+require_relative "tokenkit/errors"
+require_relative "tokenkit/token"
+require_relative "tokenkit/language"
+require_relative "tokenkit/huggingface_tokenizer"
 require_relative "tokenkit/regex_converter"
 require_relative "tokenkit/config_builder"
 require_relative "tokenkit/config_compat"
@@ -36,9 +41,6 @@ end
 #   tokenizer.tokenize("test text")
 #
 module TokenKit
-  # Base error class for TokenKit exceptions
-  class Error < StandardError; end
-
   # Instance-based tokenizer for thread-safe tokenization with specific configuration.
   #
   # @example Create a tokenizer with custom config
@@ -59,9 +61,12 @@ module TokenKit
     # @param config [Hash, Configuration, ConfigBuilder] The configuration for this tokenizer
     # @option config [Symbol] :strategy (:unicode) The tokenization strategy
     # @option config [Boolean] :lowercase (true) Whether to lowercase tokens
+    # @option config [String, Symbol] :locale Language tag whose case rules lowercasing follows, such as +"tr"+
     # @option config [Boolean] :remove_punctuation (false) Whether to remove punctuation
     # @option config [Array<Regexp>] :preserve_patterns ([]) Patterns to preserve
     #
+    # @raise [ConfigurationError] If an option is unknown or not used by the strategy
+    #
     # @example With hash configuration
     #   tokenizer = TokenKit::Tokenizer.new(strategy: :whitespace)
     #
@@ -76,10 +81,9 @@ module TokenKit
         config.build
       elsif config.is_a?(Hash)
         builder = TokenKit.config_hash.to_builder
-        config.each do |key, value|
-          builder.send("#{key}=", value) if builder.respond_to?("#{key}=")
-        end
-        builder.build
+        options = config.transform_keys(&:to_sym)
+        TokenKit.send(:apply_options, builder, options)
+        builder.build.tap { TokenKit.send(:check_options, builder, options) }
       else
         TokenKit.config_hash
       end
@@ -98,12 +102,46 @@ module TokenKit
     def tokenize(text)
       TokenKit._tokenize_with_config(text, @config.to_rust_config)
     end
+
+    # Tokenizes the given text, returning each token's offsets and attributes.
+    #
+    # @param text [String] The text to tokenize
+    # @return [Array<Token>] The tokens, in order
+    # @see TokenKit.analyze
+    def analyze(text)
+      TokenKit._analyze_with_config(text, @config.to_rust_config).map { |fields| Token.new(*fields) }
+    end
+
+    # Yields tokens one at a time using this tokenizer's configuration.
+    #
+    # @param input [String, IO] The text to tokenize, or an IO to read it from in chunks
+    # @yieldparam token [String] Each token in order
+    # @return [Enumerator, nil] An Enumerator when no block is given
+    #
+    # @example
+    #   tokenizer = TokenKit::Tokenizer.new(strategy: :whitespace)
+    #   File.open("huge.log") do |file|
+    #     tokenizer.each_token(file) { |token| index << token }
+    #   end
+    #
+    def each_token(input, &block)
+      return enum_for(:each_token, input) unless block
+
+      TokenKit._stream_tokens(input, @config.to_rust_config, &block)
+    end
   end
 
+  # Number of bytes read from an IO per chunk when streaming tokens
+  STREAM_CHUNK_SIZE = 64 * 1024
+
+  # Alternative names for options, mapped to the names the Rust side uses
+  OPTION_ALIASES = {preserve: "preserve_patterns", grapheme_extended: "extended"}.freeze
+
   extend self
 
   # Thread-safe storage for current configuration
   @current_config = nil
+  @analyzers = {}
   @config_mutex = Mutex.new
 
   # Tokenizes text using the global configuration or with temporary overrides.
@@ -112,16 +150,50 @@ module TokenKit
   # @param opts [Hash] Optional configuration overrides for this tokenization only
   # @option opts [Symbol] :strategy The tokenization strategy to use
   # @option opts [Boolean] :lowercase Whether to lowercase tokens
+  # @option opts [String, Symbol] :locale Language tag whose case rules lowercasing follows, such as +"tr"+
   # @option opts [Boolean] :remove_punctuation Whether to remove punctuation
   # @option opts [Array<Regexp>] :preserve_patterns Patterns to preserve
+  # @option opts [Array<String>] :word_break_dictionaries Thai, Lao, Khmer and Myanmar word lists added
+  #   on top of the bundled ones for :unicode strategy
   # @option opts [String, Regexp] :regex Pattern for :pattern strategy
   # @option opts [Integer] :min_gram Minimum n-gram size (for n-gram strategies)
   # @option opts [Integer] :max_gram Maximum n-gram size (for n-gram strategies)
   # @option opts [String] :delimiter Delimiter for :path_hierarchy strategy
   # @option opts [String] :split_on_chars Characters to split on for :char_group strategy
   # @option opts [Boolean] :extended Extended grapheme clusters for :grapheme strategy
+  # @option opts [String] :path Hugging Face tokenizer.json file for :huggingface strategy
+  # @option opts [Boolean] :add_special_tokens Whether :huggingface adds tokens like [CLS] (default true)
+  # @option opts [String] :dictionary Frequency dictionary replacing the bundled one for :cjk strategy,
+  #   or dictionary directory for :japanese strategy
+  # @option opts [Array<String>] :user_dictionaries Dictionaries added on top for :cjk and :japanese strategies
+  # @option opts [Boolean] :search_mode Whether :cjk also emits the words inside long words (default false)
+  # @option opts [Boolean] :hmm Whether :cjk guesses words missing from the dictionary (default true)
+  # @option opts [Integer] :max_input_bytes Largest input accepted, in bytes
+  # @option opts [Integer] :max_tokens Most tokens returned
+  # @option opts [Integer] :max_token_length Longest token, in characters
+  # @option opts [Symbol] :overflow +:error+ (default) or +:truncate+ when input or token count is over its limit
+  # @option opts [Symbol] :long_tokens +:truncate+ (default) or +:drop+ tokens over max_token_length
+  # @option opts [Integer] :regex_size_limit Largest compiled regex, in bytes
+  # @option opts [Integer] :regex_dfa_size_limit Lazy DFA cache size per regex, in bytes; a hint that never
+  #   rejects a pattern
+  # @option opts [Integer] :max_preserve_patterns Most preserve patterns accepted
+  # @option opts [Symbol] :invalid_utf8 +:raise+ (default), +:replace+ (with U+FFFD) or +:skip+ invalid UTF-8 bytes
+  # @option opts [Array<Symbol, Hash>] :char_filters Character filters applied in order to the text
+  #   before the strategy, such as +[:cjk_width]+
+  # @option opts [Array<Symbol, Hash>] :filters Token filters applied in order after the strategy, such as
+  #   +[:cjk_bigram]+, +[:arabic_normalization, :arabic_stem]+ or +[{type: :cjk_bigram, output_unigrams: true}]+
+  # @option opts [Array<Hash>] :routes For the :router strategy, the scripts (and optionally detected
+  #   languages) each tokenizer handles, such as
+  #   +[{scripts: [:han], strategy: :cjk}, {scripts: [:latin], languages: [:eng], strategy: :whitespace}]+
+  # @option opts [Hash] :fallback Options of the :router tokenizer for scripts no route names
+  # @param analyzer [Symbol, String, nil] Name of a {#register}ed analyzer to use
+  #   instead of the global configuration; other options override its settings
   #
   # @return [Array<String>] An array of tokens
+  # @raise [ConfigurationError] If an option is unknown or not used by the strategy
+  # @raise [LimitExceededError] If the input or token count is over a limit and +overflow+ is +:error+
+  # @raise [DictionaryError] If a :cjk or :japanese dictionary can't be read or is malformed
+  # @raise [InvalidUtf8Error] If the text isn't valid UTF-8 and +invalid_utf8+ is +:raise+
   #
   # @example Basic tokenization
   #   TokenKit.tokenize("Hello, world!")
@@ -135,8 +207,26 @@ module TokenKit
   #   TokenKit.tokenize("test-case", strategy: :char_group, split_on_chars: "-")
   #   # => ["test", "case"]
   #
-  def tokenize(text, **opts)
-    if opts.any?
+  # @example With a registered analyzer
+  #   TokenKit.register(:sku, strategy: :char_group, split_on_chars: "-")
+  #   TokenKit.tokenize("AB-123", analyzer: :sku)
+  #   # => ["ab", "123"]
+  #
+  # @example Scraped text with a broken encoding
+  #   TokenKit.tokenize("caf\xE9 menu", strategy: :whitespace, invalid_utf8: :replace)
+  #   # => ["caf\uFFFD", "menu"]
+  #
+  # @example Binary input, kept as bytes
+  #   TokenKit.tokenize("GIF89a \xFF\xD8".b, strategy: :bytes)
+  #   # => ["gif89a", "\xFF\xD8"] (ASCII-8BIT strings)
+  #
+  def tokenize(text, analyzer: nil, **opts)
+    if analyzer
+      # Registered analyzers are compiled once; overrides need a fresh tokenizer
+      return _tokenize_with_analyzer(text, analyzer.to_s) if opts.empty?
+
+      _tokenize_with_config(text, build_merged_config(opts, analyzer(analyzer)))
+    elsif opts.any?
       # Create a fresh tokenizer with merged config
       merged_config = build_merged_config(opts)
       _tokenize_with_config(text, merged_config)
@@ -146,6 +236,238 @@ module TokenKit
     end
   end
 
+  # Tokenizes text like {#tokenize}, returning {Token}s with the byte range
+  # of the text each came from. Tokens from the +:japanese+ strategy also
+  # carry their part of speech, base form and reading.
+  #
+  # @param text [String] The text to tokenize
+  # @param analyzer [Symbol, String, nil] Name of a {#register}ed analyzer to use
+  # @param opts [Hash] Optional configuration overrides for this call only
+  # @return [Array<Token>] The tokens, in order
+  #
+  # @example Offsets for highlighting
+  #   TokenKit.analyze("Hello world").map { |t| [t.text, t.start, t.end] }
+  #   # => [["hello", 0, 5], ["world", 6, 11]]
+  #
+  # @example Base forms of Japanese words
+  #   TokenKit.register(:ja, strategy: :japanese, dictionary: "mecab-ipadic-utf8")
+  #   TokenKit.analyze("東京で食べた", analyzer: :ja).map(&:base_form)
+  #   # => ["東京", "で", "食べる", "た"]
+  #
+  def analyze(text, analyzer: nil, **opts)
+    fields = if analyzer
+      if opts.empty?
+        _analyze_with_analyzer(text, analyzer.to_s)
+      else
+        _analyze_with_config(text, build_merged_config(opts, analyzer(analyzer)))
+      end
+    elsif opts.any?
+      _analyze_with_config(text, build_merged_config(opts))
+    else
+      _analyze(text)
+    end
+    fields.map { |token_fields| Token.new(*token_fields) }
+  end
+
+  # Yields tokens one at a time instead of building the whole array.
+  #
+  # Strings are tokenized lazily; IO objects (anything responding to +read+) are
+  # read in chunks of {STREAM_CHUNK_SIZE} bytes, and tokens spanning two chunks
+  # are still yielded whole. Accepts the same per-call options as {#tokenize}.
+  #
+  # @param input [String, IO] The text to tokenize, or an IO to read it from
+  # @param analyzer [Symbol, String, nil] Name of a {#register}ed analyzer to use
+  # @param opts [Hash] Optional configuration overrides for this call only
+  # @yieldparam token [String] Each token in order
+  # @return [Enumerator, nil] An Enumerator when no block is given
+  #
+  # @example Stream tokens from a large file
+  #   File.open("huge.log") do |file|
+  #     TokenKit.each_token(file, strategy: :whitespace) { |token| counts[token] += 1 }
+  #   end
+  #
+  # @example Lazy enumeration
+  #   TokenKit.each_token("one two three").first(2)
+  #   # => ["one", "two"]
+  #
+  def each_token(input, analyzer: nil, **opts, &block)
+    return enum_for(:each_token, input, analyzer: analyzer, **opts) unless block
+
+    base = analyzer ? analyzer(analyzer) : config_hash
+    rust_config = opts.any? ? build_merged_config(opts, base) : base.to_rust_config
+    _stream_tokens(input, rust_config, &block)
+  end
+
+  # Registers a named analyzer for use with the +analyzer:+ option.
+  #
+  # The analyzer starts from the default configuration (not the global one),
+  # takes the same options as {#tokenize}, and is compiled once up front.
+  # Registering an existing name replaces it.
+  #
+  # @param name [Symbol, String] The analyzer name
+  # @param config [Configuration, ConfigBuilder, Hash, nil] Optional base configuration
+  # @param opts [Hash] Configuration options, as for {#tokenize}
+  # @yield [ConfigBuilder] Optionally yields the builder for further changes
+  # @return [Configuration] The analyzer's configuration
+  #
+  # @raise [ConfigurationError] If the configuration is invalid
+  #
+  # @example
+  #   TokenKit.register(:title, strategy: :unicode)
+  #   TokenKit.register(:sku, strategy: :char_group, split_on_chars: "-", lowercase: false)
+  #   TokenKit.register(:email) do |config|
+  #     config.strategy = :url_email
+  #   end
+  #
+  def register(name, config = nil, **opts)
+    builder = case config
+    when Configuration then config.to_builder
+    when ConfigBuilder then config
+    when Hash
+      opts = config.transform_keys(&:to_sym).merge(opts)
+      ConfigBuilder.new
+    when nil then ConfigBuilder.new
+    else
+      raise ArgumentError, "Expected a Configuration or ConfigBuilder, got #{config.class}"
+    end
+
+    apply_options(builder, opts)
+    yield builder if block_given?
+    new_config = builder.build
+    check_options(builder, opts)
+
+    # Keep the Ruby and Rust registries in the same order under concurrent registration
+    @config_mutex.synchronize do
+      _register_analyzer(name.to_s, new_config.to_rust_config)
+      @analyzers[name.to_sym] = new_config
+    end
+
+    new_config
+  end
+
+  # Removes a registered analyzer.
+  #
+  # @param name [Symbol, String] The analyzer name
+  # @return [Boolean] Whether the analyzer was registered
+  #
+  def unregister(name)
+    @config_mutex.synchronize do
+      _unregister_analyzer(name.to_s)
+      !@analyzers.delete(name.to_sym).nil?
+    end
+  end
+
+  # Returns the configuration of a registered analyzer.
+  #
+  # @param name [Symbol, String] The analyzer name
+  # @return [Configuration]
+  # @raise [UnknownAnalyzerError] If no analyzer is registered under +name+
+  #
+  def analyzer(name)
+    @config_mutex.synchronize { @analyzers[name.to_sym] } ||
+      raise(UnknownAnalyzerError.new("Unknown analyzer: #{name}", name: name.to_s))
+  end
+
+  # Returns every registered analyzer by name.
+  #
+  # @return [Hash{Symbol => Configuration}]
+  #
+  def analyzers
+    @config_mutex.synchronize { @analyzers.dup }
+  end
+
+  # Registers an analyzer for every analyzer in Elasticsearch/OpenSearch
+  # analysis settings, so text can be tokenized the way the index does.
+  #
+  # Accepts the +GET /<index>/_settings+ response, the index settings or just
+  # the +analysis+ block. Only tokenizers and filters TokenKit reproduces
+  # exactly are supported; nothing is registered unless all of them are.
+  #
+  # @param settings [String, Hash] The settings as JSON or a parsed Hash
+  # @return [Hash{Symbol => Configuration}] The registered analyzers
+  #
+  # @raise [AnalysisSettingsError] Listing every unsupported setting with its path
+  #
+  # @example
+  #   TokenKit.import_elasticsearch(File.read("products_settings.json"))
+  #   TokenKit.tokenize("Laptop", analyzer: :autocomplete)
+  #
+  def import_elasticsearch(settings)
+    json = settings.is_a?(String) ? settings : JSON.generate(settings)
+
+    _import_elasticsearch(json).to_h do |name, hash|
+      [name.to_sym, register(name, Configuration.new(hash))]
+    end
+  end
+
+  # Guesses the language of +text+ from its script and character trigrams,
+  # among the 69 languages whose profiles ship with the gem.
+  #
+  # Routes of the +:router+ strategy can be limited to some of these
+  # languages with +languages:+.
+  #
+  # @param text [String] The text to identify
+  # @return [Language, nil] The most likely language, or nil when the text has no letters
+  #
+  # @example
+  #   language = TokenKit.detect_language("Die Katze schläft auf dem Sofa")
+  #   language.code        # => "deu"
+  #   language.confidence  # => 0.98
+  #
+  def detect_language(text)
+    fields = _detect_language(text)
+    fields && Language.new(*fields)
+  end
+
+  # Streams tokens from a String or IO with an already-built Rust config.
+  #
+  # @api private
+  #
+  def _stream_tokens(input, rust_config, &block)
+    if input.respond_to?(:read)
+      stream = TokenStream.new(rust_config)
+      while (chunk = input.read(STREAM_CHUNK_SIZE))
+        stream.push(chunk).each(&block)
+      end
+      stream.finish.each(&block)
+    else
+      _each_token(input, rust_config, &block)
+    end
+
+    nil
+  end
+
+  # Checks a configuration and returns every problem with it, rather than
+  # stopping at the first as building one does.
+  #
+  # Besides invalid values, it reports options that don't exist, with a
+  # suggestion for likely typos, and options the strategy doesn't use.
+  # Options are read as by {#register}, starting from the defaults.
+  #
+  # @param config [Hash, Configuration, ConfigBuilder, nil] The configuration to check
+  # @param opts [Hash] Options, as for {#tokenize}, checked on top of +config+
+  # @return [Array<Diagnostic>] The problems found; empty for a valid configuration
+  #
+  # @example
+  #   TokenKit.validate(strategy: :path_hierarchy, delimeter: "|", max_tokens: 0).map(&:to_s)
+  #   # => ["delimeter: Unknown option 'delimeter' (did you mean 'delimiter'?)",
+  #   #     "max_tokens: Invalid configuration: max_tokens must be at least 1"]
+  #
+  def validate(config = nil, **opts)
+    base = case config
+    when Configuration then config.to_rust_config
+    when ConfigBuilder then config.send(:build_config_hash)
+    when Hash
+      opts = config.transform_keys(&:to_sym).merge(opts)
+      {}
+    when nil then {}
+    else
+      raise ArgumentError, "Expected a Hash, Configuration or ConfigBuilder, got #{config.class}"
+    end
+
+    _validate_config(base.merge(rust_options(opts)))
+  end
+
   # Returns the global configuration object for backward compatibility.
   #
   # @deprecated Use {#config_hash} for read-only access or {#configure} to modify
@@ -184,8 +506,8 @@ module TokenKit
   # @yield [Config] Yields the configuration object for modification
   # @return [Configuration] The new configuration
   #
-  # @raise [ArgumentError] If invalid configuration is provided
-  # @raise [RegexpError] If invalid regex pattern is provided
+  # @raise [ConfigurationError] If invalid configuration is provided
+  # @raise [InvalidRegexError] If invalid regex pattern is provided
   #
   # @example Basic configuration
   #   TokenKit.configure do |config|
@@ -219,15 +541,7 @@ module TokenKit
 
     begin
       # Build and validate the new configuration
-      new_config = builder.build
-
-      # Apply to Rust tokenizer
-      _configure(new_config.to_rust_config)
-
-      # Store the new configuration
-      @config_mutex.synchronize do
-        @current_config = new_config
-      end
+      new_config = apply_configuration(builder.build)
 
       # Reset the compatibility wrapper
       Config.instance.reset_temp
@@ -240,7 +554,51 @@ module TokenKit
     end
   end
 
-  # Resets the tokenizer to default configuration.
+  # Loads the global configuration from a versioned JSON or YAML config file.
+  #
+  # The format is picked from the file extension (+.json+, +.yml+ or +.yaml+)
+  # unless +format+ is given. See docs/CONFIG_FILES.md for the schema.
+  #
+  # @param path [String] Path to the config file
+  # @param format [Symbol, nil] +:json+ or +:yaml+, overriding the extension
+  # @return [Configuration] The new configuration
+  #
+  # @raise [ConfigFileError] If the file is malformed, has unknown fields or a newer version
+  # @raise [InvalidRegexError] If the file contains an invalid regex pattern
+  #
+  # @example
+  #   TokenKit.load_config_file("config/tokenizer.yml")
+  #
+  def load_config_file(path, format: nil)
+    format ||= config_file_format(path)
+    hash = _parse_config_file(File.read(path, encoding: "UTF-8"), format.to_s)
+
+    apply_configuration(Configuration.new(hash).to_builder.build)
+  end
+
+  # Serializes the global configuration as a versioned config file.
+  #
+  # @param path [String, nil] When given, the config is also written to this file
+  # @param format [Symbol, nil] +:json+ or +:yaml+; defaults to the extension of
+  #   +path+, or +:yaml+ without a path
+  # @return [String] The serialized configuration
+  #
+  # @example Share the current config with another service
+  #   TokenKit.dump_config("config/tokenizer.json")
+  #
+  # @example
+  #   puts TokenKit.dump_config(format: :json)
+  #
+  def dump_config(path = nil, format: nil)
+    format ||= path ? config_file_format(path) : :yaml
+    dumped = _dump_config(config_hash.to_rust_config, format.to_s)
+
+    File.write(path, dumped) if path
+    dumped
+  end
+
+  # Resets the tokenizer to default configuration and removes all
+  # registered analyzers.
   #
   # @return [void]
   #
@@ -263,6 +621,7 @@ module TokenKit
     # Store the new configuration
     @config_mutex.synchronize do
       @current_config = new_config
+      @analyzers = {}
     end
 
     # Reset the compatibility wrapper
@@ -282,22 +641,52 @@ module TokenKit
 
   private
 
-  def build_merged_config(opts)
+  def apply_configuration(new_config)
+    # Apply to Rust tokenizer
+    _configure(new_config.to_rust_config)
+
+    # Store the new configuration
+    @config_mutex.synchronize do
+      @current_config = new_config
+    end
+
+    new_config
+  end
+
+  def config_file_format(path)
+    case File.extname(path).downcase
+    when ".json" then :json
+    when ".yml", ".yaml" then :yaml
+    else
+      raise ConfigFileError, "Cannot tell the config file format of #{path}; use .json, .yml or .yaml or pass format:"
+    end
+  end
+
+  def build_merged_config(opts, base = config_hash)
     # Build config with options merged in
-    builder = config_hash.to_builder
+    builder = base.to_builder
+    apply_options(builder, opts)
+    new_config = builder.build
+    check_options(builder, opts)
+    new_config.to_rust_config
+  end
 
-    # Apply options to builder
+  def apply_options(builder, opts)
     opts.each do |key, value|
       case key
       when :strategy
         builder.strategy = value
       when :lowercase
         builder.lowercase = value
+      when :locale
+        builder.locale = value
       when :remove_punctuation
         builder.remove_punctuation = value
       when :preserve, :preserve_patterns
         patterns = Array(value)
         builder.preserve_patterns = patterns
+      when :word_break_dictionaries
+        builder.word_break_dictionaries = Array(value)
       when :regex
         builder.regex = value
       when :extended, :grapheme_extended
@@ -310,10 +699,70 @@ module TokenKit
         builder.delimiter = value
       when :split_on_chars
         builder.split_on_chars = value
+      when :path
+        builder.path = value
+      when :add_special_tokens
+        builder.add_special_tokens = value
+      when :dictionary
+        builder.dictionary = value
+      when :user_dictionaries
+        builder.user_dictionaries = Array(value)
+      when :search_mode
+        builder.search_mode = value
+      when :hmm
+        builder.hmm = value
+      when :max_input_bytes
+        builder.max_input_bytes = value
+      when :max_tokens
+        builder.max_tokens = value
+      when :max_token_length
+        builder.max_token_length = value
+      when :overflow
+        builder.overflow = value
+      when :long_tokens
+        builder.long_tokens = value
+      when :regex_size_limit
+        builder.regex_size_limit = value
+      when :regex_dfa_size_limit
+        builder.regex_dfa_size_limit = value
+      when :max_preserve_patterns
+        builder.max_preserve_patterns = value
+      when :invalid_utf8
+        builder.invalid_utf8 = value
+      when :char_filters
+        builder.char_filters = Array(value)
+      when :filters
+        builder.filters = Array(value)
+      when :routes
+        builder.routes = Array(value)
+      when :fallback
+        builder.fallback = value
       end
     end
+  end
+
+  # Raises for options that don't exist or that the builder's strategy doesn't use
+  def check_options(builder, opts)
+    _check_options(builder.strategy.to_s, opts.keys.map { |key| OPTION_ALIASES.fetch(key.to_sym, key.to_s) })
+  end
 
-    builder.build.to_rust_config
+  # Options in the flat, string-keyed form the Rust side reads
+  def rust_options(opts)
+    opts.to_h do |key, value|
+      value = case value
+      when Regexp then RegexConverter.to_rust(value)
+      when Array
+        case key.to_sym
+        when :char_filters, :filters then ConfigBuilder.filter_hashes(value)
+        when :routes then ConfigBuilder.route_hashes(value)
+        else RegexConverter.patterns_to_rust(value)
+        end
+      when Hash then ConfigBuilder.tokenizer_hash(value)
+      when Symbol then value.to_s
+      else value
+      end
+      [OPTION_ALIASES.fetch(key.to_sym, key.to_s), value]
+    end
   end
 
   def _tokenize(text)
@@ -324,6 +773,26 @@ module TokenKit
     raise NotImplementedError, "Native extension not loaded"
   end
 
+  def _each_token(text, config_hash)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _analyze(text)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _analyze_with_analyzer(text, name)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _analyze_with_config(text, config_hash)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _detect_language(text)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
   def _configure(hash)
     raise NotImplementedError, "Native extension not loaded"
   end
@@ -339,4 +808,36 @@ module TokenKit
   def _load_config(hash)
     raise NotImplementedError, "Native extension not loaded"
   end
+
+  def _register_analyzer(name, hash)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _unregister_analyzer(name)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _tokenize_with_analyzer(text, name)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _parse_config_file(contents, format)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _dump_config(hash, format)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _import_elasticsearch(json)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _validate_config(hash)
+    raise NotImplementedError, "Native extension not loaded"
+  end
+
+  def _check_options(strategy, options)
+    raise NotImplementedError, "Native extension not loaded"
+  end
 end
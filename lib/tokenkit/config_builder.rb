@@ -5,42 +5,139 @@ require_relative 'regex_converter'
 module TokenKit
   # Builder for creating immutable Configuration objects
   class ConfigBuilder
-    attr_accessor :strategy, :lowercase, :remove_punctuation, :preserve_patterns
+    attr_accessor :strategy, :lowercase, :locale, :remove_punctuation, :preserve_patterns, :word_break_dictionaries
     attr_accessor :regex, :grapheme_extended, :min_gram, :max_gram
-    attr_accessor :delimiter, :split_on_chars
+    attr_accessor :delimiter, :split_on_chars, :path, :add_special_tokens
+    attr_accessor :dictionary, :user_dictionaries, :search_mode, :hmm
+    attr_accessor :max_input_bytes, :max_tokens, :max_token_length, :overflow, :long_tokens
+    attr_accessor :regex_size_limit, :regex_dfa_size_limit, :max_preserve_patterns
+    attr_accessor :invalid_utf8, :char_filters, :filters, :routes, :fallback
 
     # Default values
     DEFAULTS = {
       strategy: :unicode,
       lowercase: true,
+      locale: nil,
       remove_punctuation: false,
       preserve_patterns: [],
+      word_break_dictionaries: [],
       grapheme_extended: true,
       min_gram: 2,
       max_gram: 10,
       delimiter: "/",
-      split_on_chars: " \t\n\r"
+      split_on_chars: " \t\n\r",
+      path: nil,
+      add_special_tokens: true,
+      dictionary: nil,
+      user_dictionaries: [],
+      search_mode: false,
+      hmm: true,
+      max_input_bytes: nil,
+      max_tokens: nil,
+      max_token_length: nil,
+      overflow: :error,
+      long_tokens: :truncate,
+      regex_size_limit: nil,
+      regex_dfa_size_limit: nil,
+      max_preserve_patterns: nil,
+      invalid_utf8: :raise,
+      char_filters: [],
+      filters: [],
+      routes: [],
+      fallback: nil
     }.freeze
 
     VALID_STRATEGIES = [
       :unicode, :whitespace, :pattern, :sentence, :grapheme, :keyword,
       :edge_ngram, :ngram, :path_hierarchy, :url_email, :char_group,
-      :letter, :lowercase
+      :letter, :lowercase, :bytes, :cjk, :japanese, :huggingface, :router
     ].freeze
 
+    # Settings that must be positive integers. regex_dfa_size_limit is among
+    # them but is only a cache size hint; it never rejects a pattern
+    LIMITS = [
+      :max_input_bytes, :max_tokens, :max_token_length,
+      :regex_size_limit, :regex_dfa_size_limit, :max_preserve_patterns
+    ].freeze
+    OVERFLOW_POLICIES = [:error, :truncate].freeze
+    LONG_TOKEN_POLICIES = [:truncate, :drop].freeze
+    INVALID_UTF8_POLICIES = [:raise, :replace, :skip].freeze
+    VALID_FILTERS = [:cjk_bigram, :arabic_normalization, :persian_normalization, :hebrew_normalization, :arabic_stem, :indic_normalization, :cjk_width].freeze
+    VALID_CHAR_FILTERS = [:cjk_width].freeze
+
+    # Filters as the Rust side reads them: hashes with string keys and a string
+    # "type". A bare name is a filter with default options.
+    # @param filters [Array<Symbol, String, Hash>] As given to {#filters=}
+    # @return [Array<Hash>]
+    def self.filter_hashes(filters)
+      Array(filters).map do |filter|
+        if filter.is_a?(Hash)
+          filter.to_h { |key, value| [key.to_s, key.to_s == "type" ? value.to_s : value] }
+        else
+          {"type" => filter.to_s}
+        end
+      end
+    end
+
+    # Routes as the Rust side reads them. The options of a route other than
+    # +scripts+ configure its tokenizer, as for TokenKit.tokenize; they can
+    # also be given as a +tokenizer+ hash.
+    # @param routes [Array<Hash>] As given to {#routes=}
+    # @return [Array<Hash>]
+    def self.route_hashes(routes)
+      Array(routes).map do |route|
+        options = route.transform_keys(&:to_sym)
+        scripts = Array(options.delete(:scripts)).map(&:to_s)
+        languages = Array(options.delete(:languages)).map(&:to_s)
+        {
+          "scripts" => scripts,
+          "languages" => languages,
+          "tokenizer" => tokenizer_hash(options.delete(:tokenizer) || options)
+        }
+      end
+    end
+
+    # A route's or the fallback's tokenizer options as a config hash; hashes
+    # with string keys already are one.
+    def self.tokenizer_hash(options)
+      return options if options.keys.all?(String)
+      TokenKit.send(:rust_options, options.transform_keys(&:to_sym))
+    end
+
     def initialize(base_config = nil)
       if base_config
         # Copy from existing config
         @strategy = base_config.strategy
         @lowercase = base_config.lowercase
+        @locale = base_config.instance_variable_get(:@locale)
         @remove_punctuation = base_config.remove_punctuation
         @preserve_patterns = base_config.preserve_patterns.dup
+        @word_break_dictionaries = (base_config.instance_variable_get(:@word_break_dictionaries) || DEFAULTS[:word_break_dictionaries]).dup
         @regex = base_config.instance_variable_get(:@regex) if base_config.instance_variable_defined?(:@regex)
-        @grapheme_extended = base_config.instance_variable_get(:@grapheme_extended) || DEFAULTS[:grapheme_extended]
+        @grapheme_extended = base_config.instance_variable_get(:@grapheme_extended)
+        @grapheme_extended = DEFAULTS[:grapheme_extended] if @grapheme_extended.nil?
         @min_gram = base_config.instance_variable_get(:@min_gram) || DEFAULTS[:min_gram]
         @max_gram = base_config.instance_variable_get(:@max_gram) || DEFAULTS[:max_gram]
         @delimiter = base_config.instance_variable_get(:@delimiter) || DEFAULTS[:delimiter]
         @split_on_chars = base_config.instance_variable_get(:@split_on_chars) || DEFAULTS[:split_on_chars]
+        @path = base_config.instance_variable_get(:@path)
+        @add_special_tokens = base_config.instance_variable_get(:@add_special_tokens)
+        @add_special_tokens = DEFAULTS[:add_special_tokens] if @add_special_tokens.nil?
+        @dictionary = base_config.instance_variable_get(:@dictionary)
+        @user_dictionaries = (base_config.instance_variable_get(:@user_dictionaries) || DEFAULTS[:user_dictionaries]).dup
+        @search_mode = base_config.instance_variable_get(:@search_mode) || DEFAULTS[:search_mode]
+        @hmm = base_config.instance_variable_get(:@hmm)
+        @hmm = DEFAULTS[:hmm] if @hmm.nil?
+        LIMITS.each do |limit|
+          instance_variable_set("@#{limit}", base_config.instance_variable_get("@#{limit}"))
+        end
+        @overflow = base_config.instance_variable_get(:@overflow) || DEFAULTS[:overflow]
+        @long_tokens = base_config.instance_variable_get(:@long_tokens) || DEFAULTS[:long_tokens]
+        @invalid_utf8 = base_config.instance_variable_get(:@invalid_utf8) || DEFAULTS[:invalid_utf8]
+        @char_filters = (base_config.instance_variable_get(:@char_filters) || DEFAULTS[:char_filters]).dup
+        @filters = (base_config.instance_variable_get(:@filters) || DEFAULTS[:filters]).dup
+        @routes = (base_config.instance_variable_get(:@routes) || DEFAULTS[:routes]).dup
+        @fallback = base_config.instance_variable_get(:@fallback)
       else
         # Start with defaults
         DEFAULTS.each do |key, value|
@@ -51,7 +148,7 @@ module TokenKit
 
     # Build an immutable Configuration object
     # @return [Configuration] The built configuration
-    # @raise [Error] if configuration is invalid
+    # @raise [ConfigurationError] if configuration is invalid
     def build
       validate!
 
@@ -64,21 +161,103 @@ module TokenKit
     def validate!
       # Validate strategy
       unless VALID_STRATEGIES.include?(@strategy)
-        raise Error, "Invalid strategy: #{@strategy}. Valid strategies are: #{VALID_STRATEGIES.join(', ')}"
+        raise UnknownStrategyError.new(
+          "Invalid strategy: #{@strategy}. Valid strategies are: #{VALID_STRATEGIES.join(', ')}",
+          strategy: @strategy.to_s
+        )
+      end
+
+      LIMITS.each do |limit|
+        value = instance_variable_get("@#{limit}")
+        next if value.nil?
+        unless value.is_a?(Integer) && value >= 1
+          raise ConfigurationError.new("#{limit} must be a positive integer, got #{value.inspect}", key: limit.to_s)
+        end
+      end
+      unless OVERFLOW_POLICIES.include?(@overflow&.to_sym)
+        raise ConfigurationError.new(
+          "Invalid overflow policy: #{@overflow.inspect}. Valid policies are: #{OVERFLOW_POLICIES.join(', ')}",
+          key: "overflow"
+        )
+      end
+      unless LONG_TOKEN_POLICIES.include?(@long_tokens&.to_sym)
+        raise ConfigurationError.new(
+          "Invalid long_tokens policy: #{@long_tokens.inspect}. Valid policies are: #{LONG_TOKEN_POLICIES.join(', ')}",
+          key: "long_tokens"
+        )
+      end
+      unless INVALID_UTF8_POLICIES.include?(@invalid_utf8&.to_sym)
+        raise ConfigurationError.new(
+          "Invalid invalid_utf8 policy: #{@invalid_utf8.inspect}. Valid policies are: #{INVALID_UTF8_POLICIES.join(', ')}",
+          key: "invalid_utf8"
+        )
+      end
+      char_filter_hashes.each do |filter|
+        next if VALID_CHAR_FILTERS.include?(filter["type"].to_sym)
+        raise ConfigurationError.new(
+          "Invalid char filter: #{filter["type"]}. Valid char filters are: #{VALID_CHAR_FILTERS.join(', ')}",
+          key: "char_filters"
+        )
+      end
+      filter_hashes.each do |filter|
+        next if VALID_FILTERS.include?(filter["type"].to_sym)
+        raise ConfigurationError.new(
+          "Invalid filter: #{filter["type"]}. Valid filters are: #{VALID_FILTERS.join(', ')}",
+          key: "filters"
+        )
       end
 
       # Strategy-specific validations
       case @strategy
       when :pattern
-        raise Error, "Pattern strategy requires a regex" unless @regex
+        raise ConfigurationError.new("Pattern strategy requires a regex", key: "regex") unless @regex
         if @regex.is_a?(String)
           RegexConverter.validate!(@regex)
         end
       when :edge_ngram, :ngram
-        raise Error, "min_gram must be positive, got #{@min_gram}" if @min_gram < 1
-        raise Error, "max_gram (#{@max_gram}) must be >= min_gram (#{@min_gram})" if @max_gram < @min_gram
+        if @min_gram < 1
+          raise ConfigurationError.new("min_gram must be positive, got #{@min_gram}", key: "min_gram", min: @min_gram)
+        end
+        if @max_gram < @min_gram
+          raise ConfigurationError.new(
+            "max_gram (#{@max_gram}) must be >= min_gram (#{@min_gram})",
+            key: "max_gram", min: @min_gram, max: @max_gram
+          )
+        end
       when :path_hierarchy
-        raise Error, "Path hierarchy requires a delimiter" if @delimiter.nil? || @delimiter.empty?
+        if @delimiter.nil? || @delimiter.empty?
+          raise ConfigurationError.new("Path hierarchy requires a delimiter", key: "delimiter")
+        end
+      when :huggingface
+        if @path.nil? || @path.to_s.empty?
+          raise ConfigurationError.new("Hugging Face strategy requires a tokenizer.json path", key: "path")
+        end
+        if @preserve_patterns.any?
+          raise ConfigurationError.new("Hugging Face strategy does not support preserve_patterns", key: "preserve_patterns")
+        end
+      when :cjk
+        if !@dictionary.nil? && @dictionary.to_s.empty?
+          raise ConfigurationError.new(
+            "CJK dictionary path must not be empty; leave it out to use the bundled dictionary",
+            key: "dictionary"
+          )
+        end
+      when :japanese
+        if @dictionary.nil? || @dictionary.to_s.empty?
+          raise ConfigurationError.new("Japanese strategy requires a dictionary directory", key: "dictionary")
+        end
+      when :bytes
+        if @preserve_patterns.any?
+          raise ConfigurationError.new("Bytes strategy does not support preserve_patterns", key: "preserve_patterns")
+        end
+      when :router
+        if Array(@routes).empty?
+          raise ConfigurationError.new("Router strategy requires at least one route", key: "routes")
+        end
+        if @preserve_patterns.any?
+          raise ConfigurationError.new("Router strategy does not support preserve_patterns; set them per route",
+            key: "preserve_patterns")
+        end
       when :lowercase
         # Warn if lowercase: false with :lowercase strategy
         if !@lowercase
@@ -97,6 +276,9 @@ module TokenKit
 
       # Add strategy-specific parameters
       case @strategy
+      when :unicode
+        word_break_dictionaries = Array(@word_break_dictionaries).map(&:to_s)
+        config["word_break_dictionaries"] = word_break_dictionaries if word_break_dictionaries.any?
       when :pattern
         config["regex"] = @regex.is_a?(Regexp) ? RegexConverter.to_rust(@regex) : @regex.to_s
       when :grapheme
@@ -108,20 +290,60 @@ module TokenKit
         config["delimiter"] = @delimiter
       when :char_group
         config["split_on_chars"] = @split_on_chars
+      when :cjk
+        config["dictionary"] = @dictionary.to_s if @dictionary
+        config["user_dictionaries"] = Array(@user_dictionaries).map(&:to_s)
+        config["search_mode"] = @search_mode
+        config["hmm"] = @hmm
+      when :japanese
+        config["dictionary"] = @dictionary.to_s
+        config["user_dictionaries"] = Array(@user_dictionaries).map(&:to_s)
+      when :huggingface
+        config["path"] = @path.to_s
+        config["add_special_tokens"] = @add_special_tokens
+      when :router
+        config["routes"] = self.class.route_hashes(@routes)
+        config["fallback"] = self.class.tokenizer_hash(@fallback) if @fallback
       end
 
+      # Limits are only sent when set, so unlimited configs stay unchanged
+      LIMITS.each do |limit|
+        value = instance_variable_get("@#{limit}")
+        config[limit.to_s] = value if value
+      end
+      config["overflow"] = @overflow.to_s if @overflow.to_sym != DEFAULTS[:overflow]
+      config["long_tokens"] = @long_tokens.to_s if @long_tokens.to_sym != DEFAULTS[:long_tokens]
+      config["invalid_utf8"] = @invalid_utf8.to_s if @invalid_utf8.to_sym != DEFAULTS[:invalid_utf8]
+      config["locale"] = @locale.to_s if @locale
+      config["char_filters"] = char_filter_hashes if char_filter_hashes.any?
+      config["filters"] = filter_hashes if filter_hashes.any?
+
       config
     end
+
+    def char_filter_hashes
+      self.class.filter_hashes(@char_filters)
+    end
+
+    def filter_hashes
+      self.class.filter_hashes(@filters)
+    end
   end
 
   # Immutable configuration object
   class Configuration
-    attr_reader :strategy, :lowercase, :remove_punctuation, :preserve_patterns
+    attr_reader :strategy, :lowercase, :locale, :remove_punctuation, :preserve_patterns, :word_break_dictionaries
     attr_reader :regex, :grapheme_extended, :min_gram, :max_gram, :delimiter, :split_on_chars
+    attr_reader :path, :add_special_tokens
+    attr_reader :dictionary, :user_dictionaries, :search_mode, :hmm
+    attr_reader :max_input_bytes, :max_tokens, :max_token_length, :overflow, :long_tokens
+    attr_reader :regex_size_limit, :regex_dfa_size_limit, :max_preserve_patterns
+    attr_reader :invalid_utf8, :char_filters, :filters, :routes, :fallback
 
     def initialize(config_hash, builder = nil)
       @strategy = config_hash["strategy"]&.to_sym || :unicode
       @lowercase = config_hash.fetch("lowercase", true)
+      @locale = config_hash["locale"]
       @remove_punctuation = config_hash.fetch("remove_punctuation", false)
       @raw_hash = config_hash.freeze
 
@@ -129,21 +351,61 @@ module TokenKit
       if builder
         # Store original Ruby patterns, not the converted strings
         @preserve_patterns = builder.preserve_patterns.freeze
+        @word_break_dictionaries = Array(builder.word_break_dictionaries).freeze
         @regex = builder.regex
         @grapheme_extended = builder.grapheme_extended
         @min_gram = builder.min_gram
         @max_gram = builder.max_gram
         @delimiter = builder.delimiter
         @split_on_chars = builder.split_on_chars
+        @path = builder.path
+        @add_special_tokens = builder.add_special_tokens
+        @dictionary = builder.dictionary
+        @user_dictionaries = Array(builder.user_dictionaries).freeze
+        @search_mode = builder.search_mode
+        @hmm = builder.hmm
+        @max_input_bytes = builder.max_input_bytes
+        @max_tokens = builder.max_tokens
+        @max_token_length = builder.max_token_length
+        @regex_size_limit = builder.regex_size_limit
+        @regex_dfa_size_limit = builder.regex_dfa_size_limit
+        @max_preserve_patterns = builder.max_preserve_patterns
+        @overflow = builder.overflow.to_sym
+        @long_tokens = builder.long_tokens.to_sym
+        @invalid_utf8 = builder.invalid_utf8.to_sym
+        @char_filters = Array(builder.char_filters).freeze
+        @filters = Array(builder.filters).freeze
+        @routes = Array(builder.routes).freeze
+        @fallback = builder.fallback
       else
         # Extract from raw_hash for backward compatibility
         @preserve_patterns = config_hash.fetch("preserve_patterns", []).freeze
+        @word_break_dictionaries = config_hash.fetch("word_break_dictionaries", []).freeze
         @regex = config_hash["regex"]
         @grapheme_extended = config_hash.fetch("extended", ConfigBuilder::DEFAULTS[:grapheme_extended])
         @min_gram = config_hash.fetch("min_gram", ConfigBuilder::DEFAULTS[:min_gram])
         @max_gram = config_hash.fetch("max_gram", ConfigBuilder::DEFAULTS[:max_gram])
         @delimiter = config_hash.fetch("delimiter", ConfigBuilder::DEFAULTS[:delimiter])
         @split_on_chars = config_hash.fetch("split_on_chars", ConfigBuilder::DEFAULTS[:split_on_chars])
+        @path = config_hash["path"]
+        @add_special_tokens = config_hash.fetch("add_special_tokens", ConfigBuilder::DEFAULTS[:add_special_tokens])
+        @dictionary = config_hash["dictionary"]
+        @user_dictionaries = config_hash.fetch("user_dictionaries", []).freeze
+        @search_mode = config_hash.fetch("search_mode", ConfigBuilder::DEFAULTS[:search_mode])
+        @hmm = config_hash.fetch("hmm", ConfigBuilder::DEFAULTS[:hmm])
+        @max_input_bytes = config_hash["max_input_bytes"]
+        @max_tokens = config_hash["max_tokens"]
+        @max_token_length = config_hash["max_token_length"]
+        @regex_size_limit = config_hash["regex_size_limit"]
+        @regex_dfa_size_limit = config_hash["regex_dfa_size_limit"]
+        @max_preserve_patterns = config_hash["max_preserve_patterns"]
+        @overflow = config_hash.fetch("overflow", ConfigBuilder::DEFAULTS[:overflow]).to_sym
+        @long_tokens = config_hash.fetch("long_tokens", ConfigBuilder::DEFAULTS[:long_tokens]).to_sym
+        @invalid_utf8 = config_hash.fetch("invalid_utf8", ConfigBuilder::DEFAULTS[:invalid_utf8]).to_sym
+        @char_filters = config_hash.fetch("char_filters", []).freeze
+        @filters = config_hash.fetch("filters", []).freeze
+        @routes = config_hash.fetch("routes", []).freeze
+        @fallback = config_hash["fallback"]
       end
     end
 
@@ -189,6 +451,22 @@ module TokenKit
       strategy == :lowercase
     end
 
+    def huggingface?
+      strategy == :huggingface
+    end
+
+    def bytes?
+      strategy == :bytes
+    end
+
+    def cjk?
+      strategy == :cjk
+    end
+
+    def japanese?
+      strategy == :japanese
+    end
+
     def to_h
       @raw_hash.dup
     end
@@ -28,12 +28,20 @@ module TokenKit
 
     def respond_to_missing?(method, include_private = false)
       # Avoid infinite recursion by checking config_hash instead of config
-      return true if [:strategy=, :lowercase=, :remove_punctuation=, :preserve_patterns=,
+      return true if [:strategy=, :lowercase=, :locale=, :remove_punctuation=, :preserve_patterns=,
                       :regex=, :grapheme_extended=, :min_gram=, :max_gram=,
-                      :delimiter=, :split_on_chars=,
-                      :strategy, :lowercase, :remove_punctuation, :preserve_patterns,
+                      :delimiter=, :split_on_chars=, :path=, :add_special_tokens=,
+                      :dictionary=, :user_dictionaries=, :search_mode=, :hmm=,
+                      :max_input_bytes=, :max_tokens=, :max_token_length=, :overflow=, :long_tokens=,
+                      :regex_size_limit=, :regex_dfa_size_limit=, :max_preserve_patterns=, :invalid_utf8=, :char_filters=, :filters=,
+                      :routes=, :fallback=,
+                      :strategy, :lowercase, :locale, :remove_punctuation, :preserve_patterns,
                       :regex, :grapheme_extended, :min_gram, :max_gram,
-                      :delimiter, :split_on_chars].include?(method)
+                      :delimiter, :split_on_chars, :path, :add_special_tokens,
+                      :dictionary, :user_dictionaries, :search_mode, :hmm,
+                      :max_input_bytes, :max_tokens, :max_token_length, :overflow, :long_tokens,
+                      :regex_size_limit, :regex_dfa_size_limit, :max_preserve_patterns, :invalid_utf8, :char_filters, :filters,
+                      :routes, :fallback].include?(method)
       super
     end
 
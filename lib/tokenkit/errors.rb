@@ -0,0 +1,161 @@
+# frozen_string_literal: true
+
+module TokenKit
+  # Base error class for TokenKit exceptions
+  class Error < StandardError; end
+
+  # One problem with a configuration, as returned by {TokenKit.validate}.
+  #
+  # @!attribute path
+  #   @return [String] The option at fault, such as +"min_gram"+ or +"preserve_patterns[1]"+
+  # @!attribute message
+  #   @return [String] What is wrong with it
+  # @!attribute suggestion
+  #   @return [String, nil] The likely intended name, for misspelled options and strategies
+  Diagnostic = Struct.new(:path, :message, :suggestion) do
+    def to_s
+      "#{path}: #{message}"
+    end
+  end
+
+  # A configuration that can't be turned into a tokenizer.
+  #
+  # @example Point at the offending field in a form
+  #   begin
+  #     TokenKit.configure { |c| c.strategy = :ngram; c.min_gram = 5; c.max_gram = 2 }
+  #   rescue TokenKit::ConfigurationError => e
+  #     e.key  # => "max_gram"
+  #     e.min  # => 5
+  #     e.max  # => 2
+  #   end
+  class ConfigurationError < Error
+    # @return [String, nil] The config key at fault, such as +"min_gram"+
+    attr_reader :key
+    # @return [Integer, nil] The lower bound involved, for range errors
+    attr_reader :min
+    # @return [Integer, nil] The upper bound or limit involved
+    attr_reader :max
+    # @return [Array<Diagnostic>] Every problem, when there was more than one
+    attr_reader :diagnostics
+
+    def initialize(message = nil, key: nil, min: nil, max: nil, diagnostics: [])
+      super(message)
+      @key = key
+      @min = min
+      @max = max
+      @diagnostics = diagnostics
+    end
+  end
+
+  # A strategy name TokenKit doesn't know.
+  class UnknownStrategyError < ConfigurationError
+    # @return [String, nil] The strategy that was asked for
+    attr_reader :strategy
+
+    def initialize(message = nil, strategy: nil, **attributes)
+      super(message, key: "strategy", **attributes)
+      @strategy = strategy
+    end
+  end
+
+  # A +:pattern+ regex or preserve pattern that doesn't compile.
+  class InvalidRegexError < ConfigurationError
+    # @return [String, nil] The pattern, in Rust regex syntax
+    attr_reader :pattern
+    # @return [String, nil] Why it was rejected
+    attr_reader :reason
+
+    def initialize(message = nil, pattern: nil, reason: nil, **attributes)
+      super(message, **attributes)
+      @pattern = pattern
+      @reason = reason
+    end
+  end
+
+  # A pattern over +regex_size_limit+, or more preserve patterns than
+  # +max_preserve_patterns+. {#max} is the limit's value. +regex_dfa_size_limit+
+  # is only a cache size hint and never raises this.
+  class RegexLimitExceededError < InvalidRegexError
+    # @return [String, nil] The limit that was exceeded: +"size_limit"+ or +"max_preserve_patterns"+
+    attr_reader :limit
+
+    def initialize(message = nil, limit: nil, **attributes)
+      super(message, **attributes)
+      @limit = limit
+    end
+  end
+
+  # A config file that is malformed, has unknown fields or a newer version.
+  class ConfigFileError < ConfigurationError; end
+
+  # Elasticsearch/OpenSearch analysis settings that can't be imported.
+  class AnalysisSettingsError < ConfigurationError; end
+
+  # A Hugging Face +tokenizer.json+ that can't be loaded.
+  class HuggingFaceTokenizerError < ConfigurationError; end
+
+  # A +dictionary+ or user dictionary file that can't be read or has a
+  # malformed line.
+  class DictionaryError < ConfigurationError
+    # @return [String, nil] The file's path
+    attr_reader :path
+
+    def initialize(message = nil, path: nil, **attributes)
+      super(message, **attributes)
+      @path = path
+    end
+  end
+
+  # No analyzer is registered under the requested name.
+  class UnknownAnalyzerError < Error
+    # @return [String, nil] The analyzer name
+    attr_reader :name
+
+    def initialize(message = nil, name: nil)
+      super(message)
+      @name = name
+    end
+  end
+
+  # Input over +max_input_bytes+ or +max_tokens+ with +overflow: :error+.
+  class LimitExceededError < Error
+    # @return [String, nil] The limit that was exceeded, such as +"max_tokens"+
+    attr_reader :limit
+    # @return [Integer, nil] The limit's value
+    attr_reader :max
+
+    def initialize(message = nil, limit: nil, max: nil)
+      super(message)
+      @limit = limit
+      @max = max
+    end
+  end
+
+  # Input that isn't valid UTF-8 with +invalid_utf8: :raise+, the default.
+  #
+  # @example Find where a scraped page's encoding breaks
+  #   TokenKit.tokenize(body)
+  # rescue TokenKit::InvalidUtf8Error => e
+  #   body.byteslice(e.position, 16)
+  class InvalidUtf8Error < Error
+    # @return [Integer, nil] Byte offset of the first invalid byte
+    attr_reader :position
+
+    def initialize(message = nil, position: nil)
+      super(message)
+      @position = position
+    end
+  end
+
+  # A bug in TokenKit itself: the native extension panicked. The process
+  # carries on, but please report it with the input that caused it.
+  class InternalError < Error
+    # @return [String, nil] The panic message from Rust
+    attr_reader :panic_message
+
+    def initialize(message = nil, panic_message: nil)
+      super(message)
+      @panic_message = panic_message
+    end
+  end
+end
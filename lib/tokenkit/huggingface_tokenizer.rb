@@ -0,0 +1,51 @@
+# frozen_string_literal: true
+
+module TokenKit
+  # A Hugging Face +tokenizer.json+, loaded for the ids, offsets and decoding
+  # that the +:huggingface+ strategy leaves out.
+  #
+  # @example
+  #   tokenizer = TokenKit::HuggingFaceTokenizer.new("bert-base-uncased/tokenizer.json")
+  #   encoding = tokenizer.encode("Hello, world!")
+  #   encoding.tokens   # => ["[CLS]", "hello", ",", "world", "!", "[SEP]"]
+  #   encoding.ids      # => [101, 7592, 1010, 2088, 999, 102]
+  #   encoding.offsets  # => [[0, 0], [0, 5], [5, 6], [7, 12], [12, 13], [0, 0]]
+  #   tokenizer.decode(encoding.ids)
+  #   # => "hello, world!"
+  #
+  # @!method self.new(path)
+  #   Reads and checks the file once; encode and decode don't touch it again.
+  #   @param path [String] The tokenizer.json file
+  #   @raise [HuggingFaceTokenizerError] If the file can't be read or uses unsupported components
+  class HuggingFaceTokenizer
+    # The result of {HuggingFaceTokenizer#encode}.
+    #
+    # @!attribute ids
+    #   @return [Array<Integer>] The model's id for each token
+    # @!attribute tokens
+    #   @return [Array<String>] The tokens, as in the model's vocabulary
+    # @!attribute offsets
+    #   @return [Array<Array(Integer, Integer)>] Byte offsets of each token in the text;
+    #     +[0, 0]+ for special tokens
+    Encoding = Struct.new(:ids, :tokens, :offsets)
+
+    # Encodes +text+ the way the +tokenizers+ library does.
+    #
+    # @param text [String] The text to encode
+    # @param add_special_tokens [Boolean] Whether to add tokens like +[CLS]+ and +[SEP]+
+    # @return [Encoding]
+    # @raise [InvalidUtf8Error] If the text isn't valid UTF-8
+    def encode(text, add_special_tokens: true)
+      Encoding.new(*_encode(text, add_special_tokens))
+    end
+
+    # The text +ids+ stand for. Unknown ids are skipped.
+    #
+    # @param ids [Array<Integer>] Ids, as returned by {#encode}
+    # @param skip_special_tokens [Boolean] Whether to leave out tokens like +[CLS]+
+    # @return [String]
+    def decode(ids, skip_special_tokens: true)
+      _decode(ids, skip_special_tokens)
+    end
+  end
+end
@@ -0,0 +1,22 @@
+# frozen_string_literal: true
+
+module TokenKit
+  # The language a text is most likely written in. Returned by
+  # {TokenKit.detect_language}.
+  #
+  # @!attribute code
+  #   @return [String] ISO 639-3 code, such as +"eng"+
+  # @!attribute name
+  #   @return [String] English name, such as +"English"+
+  # @!attribute confidence
+  #   @return [Float] How far ahead of the runner-up this language is, from 0.0 to 1.0
+  # @!attribute reliable
+  #   @return [Boolean] Whether the confidence is high enough to rely on; short texts often fall short
+  Language = Struct.new(:code, :name, :confidence, :reliable) do
+    alias_method :reliable?, :reliable
+
+    def to_s
+      code
+    end
+  end
+end
@@ -33,13 +33,16 @@ module TokenKit
     # Validate a regex pattern
     # @param pattern [String] The regex pattern to validate
     # @return [Boolean] true if valid
-    # @raise [Error] if invalid
+    # @raise [InvalidRegexError] if invalid
     def validate!(pattern)
       # Try to compile it in Ruby first
       Regexp.new(pattern)
       true
     rescue RegexpError => e
-      raise Error, "Invalid regex pattern '#{pattern}': #{e.message}"
+      raise InvalidRegexError.new(
+        "Invalid regex pattern '#{pattern}': #{e.message}",
+        key: "regex", pattern: pattern, reason: e.message
+      )
     end
 
     private
@@ -0,0 +1,24 @@
+# frozen_string_literal: true
+
+module TokenKit
+  # A token with where it came from and, for the +:japanese+ strategy, what
+  # the dictionary says about it. Returned by {TokenKit.analyze}.
+  #
+  # @!attribute text
+  #   @return [String] The token, after lowercasing and other filters
+  # @!attribute start
+  #   @return [Integer] Byte offset of the token's first byte in the text
+  # @!attribute end
+  #   @return [Integer] Byte offset just past the token's last byte
+  # @!attribute part_of_speech
+  #   @return [String, nil] Part-of-speech levels joined with +-+, such as +"名詞-固有名詞-地域-一般"+
+  # @!attribute base_form
+  #   @return [String, nil] Dictionary form of an inflected word, such as +"食べる"+ for +"食べ"+
+  # @!attribute reading
+  #   @return [String, nil] Reading in katakana
+  Token = Struct.new(:text, :start, :end, :part_of_speech, :base_form, :reading) do
+    def to_s
+      text
+    end
+  end
+end
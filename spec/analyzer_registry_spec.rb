@@ -0,0 +1,132 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Named analyzers" do
+  after { TokenKit.reset }
+
+  describe "TokenKit.register" do
+    it "registers an analyzer from options" do
+      TokenKit.register(:sku, strategy: :char_group, split_on_chars: "-", lowercase: false)
+      expect(TokenKit.tokenize("AB-123-X", analyzer: :sku)).to eq(["AB", "123", "X"])
+    end
+
+    it "registers an analyzer from a block" do
+      TokenKit.register(:autocomplete) do |config|
+        config.strategy = :edge_ngram
+        config.min_gram = 2
+        config.max_gram = 4
+      end
+
+      expect(TokenKit.tokenize("Laptop", analyzer: :autocomplete)).to eq(["la", "lap", "lapt"])
+    end
+
+    it "registers an analyzer from a Configuration" do
+      config = TokenKit::Tokenizer.new(strategy: :whitespace, lowercase: false).config
+      TokenKit.register("plain", config)
+
+      expect(TokenKit.tokenize("Hello World", analyzer: :plain)).to eq(["Hello", "World"])
+    end
+
+    it "starts from the defaults rather than the global configuration" do
+      TokenKit.configure { |c| c.strategy = :whitespace }
+      TokenKit.register(:default)
+
+      expect(TokenKit.analyzer(:default).strategy).to eq(:unicode)
+      expect(TokenKit.tokenize("hello, world", analyzer: :default)).to eq(["hello", "world"])
+    end
+
+    it "returns the analyzer's configuration" do
+      config = TokenKit.register(:paths, strategy: :path_hierarchy)
+      expect(config).to be_a(TokenKit::Configuration)
+      expect(config.strategy).to eq(:path_hierarchy)
+    end
+
+    it "replaces an analyzer registered under the same name" do
+      TokenKit.register(:field, strategy: :whitespace)
+      TokenKit.register(:field, strategy: :keyword)
+
+      expect(TokenKit.tokenize("a b", analyzer: :field)).to eq(["a b"])
+    end
+
+    it "rejects invalid configurations without registering them" do
+      expect {
+        TokenKit.register(:broken, preserve_patterns: ["[unclosed"])
+      }.to raise_error(TokenKit::InvalidRegexError)
+
+      expect(TokenKit.analyzers).not_to have_key(:broken)
+      expect { TokenKit.tokenize("text", analyzer: :broken) }.to raise_error(TokenKit::UnknownAnalyzerError, /Unknown analyzer/)
+    end
+
+    it "leaves the global configuration alone" do
+      TokenKit.register(:sku, strategy: :char_group, split_on_chars: "-")
+      expect(TokenKit.config_hash.strategy).to eq(:unicode)
+      expect(TokenKit.tokenize("AB-123")).to eq(["ab", "123"])
+    end
+  end
+
+  describe "TokenKit.tokenize with analyzer:" do
+    before { TokenKit.register(:sku, strategy: :char_group, split_on_chars: "-", lowercase: false) }
+
+    it "applies per-call overrides on top of the analyzer" do
+      expect(TokenKit.tokenize("AB-123", analyzer: :sku, lowercase: true)).to eq(["ab", "123"])
+      expect(TokenKit.tokenize("AB-123", analyzer: :sku)).to eq(["AB", "123"])
+    end
+
+    it "raises UnknownAnalyzerError for an unknown analyzer" do
+      expect { TokenKit.tokenize("text", analyzer: :missing) }.to raise_error(TokenKit::UnknownAnalyzerError, /Unknown analyzer: missing/)
+      expect { TokenKit.tokenize("text", analyzer: :missing, lowercase: false) }.to raise_error(TokenKit::UnknownAnalyzerError)
+    end
+
+    it "streams with each_token" do
+      expect(TokenKit.each_token(StringIO.new("AB-123-X"), analyzer: :sku).to_a).to eq(["AB", "123", "X"])
+    end
+  end
+
+  describe "TokenKit.unregister" do
+    it "removes the analyzer" do
+      TokenKit.register(:sku, strategy: :whitespace)
+
+      expect(TokenKit.unregister(:sku)).to be true
+      expect(TokenKit.unregister(:sku)).to be false
+      expect { TokenKit.tokenize("text", analyzer: :sku) }.to raise_error(TokenKit::UnknownAnalyzerError)
+    end
+  end
+
+  describe "TokenKit.analyzers" do
+    it "lists registered analyzers by name" do
+      TokenKit.register(:a, strategy: :whitespace)
+      TokenKit.register("b", strategy: :keyword)
+
+      expect(TokenKit.analyzers.keys).to contain_exactly(:a, :b)
+      expect(TokenKit.analyzers[:b].strategy).to eq(:keyword)
+    end
+  end
+
+  describe "TokenKit.reset" do
+    it "removes every analyzer" do
+      TokenKit.register(:sku, strategy: :whitespace)
+      TokenKit.reset
+
+      expect(TokenKit.analyzers).to be_empty
+      expect { TokenKit.tokenize("text", analyzer: :sku) }.to raise_error(TokenKit::UnknownAnalyzerError)
+    end
+  end
+
+  it "tokenizes with different analyzers from many threads" do
+    TokenKit.register(:words, strategy: :whitespace)
+    TokenKit.register(:grams, strategy: :edge_ngram, min_gram: 1, max_gram: 2)
+
+    results = Array.new(8) do |i|
+      Thread.new do
+        Array.new(50) do
+          i.even? ? TokenKit.tokenize("a b", analyzer: :words) : TokenKit.tokenize("ab", analyzer: :grams)
+        end.uniq
+      end
+    end.map(&:value)
+
+    results.each_with_index do |result, i|
+      expect(result).to eq(i.even? ? [["a", "b"]] : [["a", "ab"]])
+    end
+  end
+end
@@ -0,0 +1,41 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Bytes Tokenizer" do
+  after { TokenKit.reset }
+
+  let(:tokenizer) { TokenKit::Tokenizer.new(strategy: :bytes) }
+
+  it "splits binary strings on ASCII whitespace and returns binary tokens" do
+    tokens = tokenizer.tokenize("GIF89a \xC0\xFF\x85DATA\r\nEnd.".b)
+
+    expect(tokens).to eq(["gif89a".b, "\xC0\xFF\x85data".b, "end.".b])
+    expect(tokens.map(&:encoding).uniq).to eq([Encoding::ASCII_8BIT])
+  end
+
+  it "only changes ASCII letters" do
+    # "À" is \xC3\x80 in UTF-8; \xA0 would be a no-break space in Latin-1
+    expect(tokenizer.tokenize("ÀB\xA0C".b)).to eq(["\xC3\x80b\xA0c".b])
+  end
+
+  it "tokenizes UTF-8 strings as text" do
+    expect(tokenizer.tokenize("Crème BRÛLÉE")).to eq(["crème", "brÛlÉe"])
+  end
+
+  it "removes ASCII punctuation when asked" do
+    tokens = TokenKit.tokenize("(\xFE\xFF) ok.".b, strategy: :bytes, remove_punctuation: true)
+    expect(tokens).to eq(["\xFE\xFF".b, "ok".b])
+  end
+
+  it "streams binary IO input as bytes" do
+    io = StringIO.new("PNG \x89\x50 IEND".b)
+    expect(tokenizer.each_token(io).to_a).to eq(["png".b, "\x89p".b, "iend".b])
+  end
+
+  it "does not support preserve patterns" do
+    expect {
+      TokenKit::Tokenizer.new(strategy: :bytes, preserve_patterns: [/\d+/])
+    }.to raise_error(TokenKit::ConfigurationError, /does not support preserve_patterns/)
+  end
+end
@@ -0,0 +1,69 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "CJK Tokenizer" do
+  after { TokenKit.reset }
+
+  let(:fixtures) { File.expand_path("../crates/tokenkit-core/tests/fixtures/cjk", __dir__) }
+
+  it "segments Chinese with the bundled dictionary" do
+    expect(TokenKit.tokenize("我来到北京清华大学", strategy: :cjk)).to eq(["我", "来到", "北京", "清华大学"])
+  end
+
+  it "drops punctuation and whitespace and lowercases other words" do
+    expect(TokenKit.tokenize("我爱 Rust，也爱北京。", strategy: :cjk)).to eq(["我", "爱", "rust", "也", "爱", "北京"])
+  end
+
+  it "adds the words inside long words in search mode" do
+    tokens = TokenKit.tokenize("毕业于中国科学院", strategy: :cjk, search_mode: true)
+
+    expect(tokens).to include("中国", "科学", "学院", "科学院", "中国科学院")
+  end
+
+  it "keeps user dictionary words whole" do
+    text = "李小福是创新办主任"
+    expect(TokenKit.tokenize(text, strategy: :cjk)).not_to include("创新办")
+
+    tokens = TokenKit.tokenize(text, strategy: :cjk, user_dictionaries: [File.join(fixtures, "user.txt")])
+    expect(tokens).to include("创新办")
+  end
+
+  it "replaces the bundled dictionary" do
+    tokens = TokenKit.tokenize("小明硕士毕业于中国科学院", strategy: :cjk, dictionary: File.join(fixtures, "small.txt"))
+    expect(tokens).to eq(["小明", "硕士", "毕业", "于", "中国科学院"])
+  end
+
+  it "reports the file of a malformed dictionary" do
+    path = File.join(fixtures, "broken.txt")
+
+    expect {
+      TokenKit::Tokenizer.new(strategy: :cjk, user_dictionaries: [path])
+    }.to raise_error(TokenKit::DictionaryError, /line 2/) { |error| expect(error.path).to eq(path) }
+  end
+
+  it "rejects an empty dictionary path" do
+    expect {
+      TokenKit.configure { |c| c.strategy = :cjk; c.dictionary = "" }
+    }.to raise_error(TokenKit::ConfigurationError) { |error| expect(error.key).to eq("dictionary") }
+  end
+
+  it "streams IO input" do
+    tokenizer = TokenKit::Tokenizer.new(strategy: :cjk)
+    io = StringIO.new("我来到北京清华大学。他来到了网易杭研大厦")
+
+    expect(tokenizer.each_token(io).to_a).to eq(tokenizer.tokenize(io.string))
+  end
+
+  it "exposes its options on the configuration" do
+    TokenKit.configure do |c|
+      c.strategy = :cjk
+      c.search_mode = true
+      c.hmm = false
+    end
+
+    expect(TokenKit.config_hash).to be_cjk
+    expect(TokenKit.config_hash.search_mode).to be true
+    expect(TokenKit.config_hash.hmm).to be false
+  end
+end
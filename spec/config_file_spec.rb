@@ -0,0 +1,136 @@
+# frozen_string_literal: true
+
+require "json"
+require "tmpdir"
+
+RSpec.describe "Config files" do
+  after { TokenKit.reset }
+
+  around do |example|
+    Dir.mktmpdir do |dir|
+      @dir = dir
+      example.run
+    end
+  end
+
+  def write(name, contents)
+    File.join(@dir, name).tap { |path| File.write(path, contents) }
+  end
+
+  describe "TokenKit.load_config_file" do
+    it "loads a YAML file" do
+      path = write("tokenizer.yml", <<~YAML)
+        version: 1
+        tokenizer:
+          strategy:
+            type: edge_ngram
+            min_gram: 2
+            max_gram: 4
+          preserve_patterns:
+            - '\\d+mg'
+      YAML
+
+      config = TokenKit.load_config_file(path)
+
+      expect(config.strategy).to eq(:edge_ngram)
+      expect(config.max_gram).to eq(4)
+      expect(TokenKit.config_hash).to eq(config)
+      expect(TokenKit.tokenize("Laptop")).to eq(["la", "lap", "lapt"])
+    end
+
+    it "loads a JSON file" do
+      path = write("tokenizer.json", <<~JSON)
+        {"version": 1, "tokenizer": {"strategy": {"type": "whitespace"}, "lowercase": false}}
+      JSON
+
+      TokenKit.load_config_file(path)
+      expect(TokenKit.tokenize("Hello, World")).to eq(["Hello,", "World"])
+    end
+
+    it "fills in defaults for omitted fields" do
+      path = write("tokenizer.yml", "version: 1\ntokenizer:\n  strategy:\n    type: grapheme\n")
+
+      config = TokenKit.load_config_file(path)
+      expect(config.strategy).to eq(:grapheme)
+      expect(config.extended).to be true
+      expect(config.lowercase).to be true
+    end
+
+    it "keeps false strategy options" do
+      path = write("tokenizer.yml", "version: 1\ntokenizer:\n  strategy:\n    type: grapheme\n    extended: false\n")
+      expect(TokenKit.load_config_file(path).extended).to be false
+    end
+
+    it "accepts an explicit format" do
+      path = write("tokenizer.conf", '{"version": 1, "tokenizer": {"strategy": {"type": "letter"}}}')
+      expect(TokenKit.load_config_file(path, format: :json).strategy).to eq(:letter)
+    end
+
+    it "rejects unknown fields" do
+      path = write("tokenizer.json", '{"version": 1, "tokenizer": {"lowercse": false}}')
+      expect { TokenKit.load_config_file(path) }.to raise_error(TokenKit::ConfigFileError, /unknown field `lowercse`/)
+    end
+
+    it "rejects files from a newer schema version" do
+      path = write("tokenizer.json", '{"version": 99, "tokenizer": {}}')
+      expect { TokenKit.load_config_file(path) }.to raise_error(TokenKit::ConfigFileError, /version 99 is newer/)
+    end
+
+    it "rejects invalid regexes" do
+      path = write("tokenizer.json", '{"version": 1, "tokenizer": {"preserve_patterns": ["[a-"]}}')
+      expect { TokenKit.load_config_file(path) }.to raise_error(TokenKit::InvalidRegexError)
+    end
+
+    it "leaves the configuration unchanged on error" do
+      path = write("tokenizer.json", '{"version": 1, "tokenizer": {"strategy": {"type": "nope"}}}')
+      expect { TokenKit.load_config_file(path) }.to raise_error(TokenKit::ConfigFileError)
+      expect(TokenKit.config_hash.strategy).to eq(:unicode)
+    end
+
+    it "requires a known extension without format:" do
+      path = write("tokenizer.conf", "{}")
+      expect { TokenKit.load_config_file(path) }.to raise_error(TokenKit::ConfigFileError, /format/)
+    end
+  end
+
+  describe "TokenKit.dump_config" do
+    before do
+      TokenKit.configure do |config|
+        config.strategy = :ngram
+        config.min_gram = 2
+        config.max_gram = 3
+        config.preserve_patterns = [/\d+mg/i]
+      end
+    end
+
+    it "dumps YAML by default" do
+      yaml = TokenKit.dump_config
+      expect(yaml).to start_with("version: 1\n")
+      expect(yaml).to include("type: ngram")
+    end
+
+    it "dumps JSON" do
+      json = JSON.parse(TokenKit.dump_config(format: :json))
+      expect(json).to eq(
+        "version" => 1,
+        "tokenizer" => {
+          "strategy" => {"type" => "ngram", "min_gram" => 2, "max_gram" => 3},
+          "lowercase" => true,
+          "remove_punctuation" => false,
+          "preserve_patterns" => ["(?i)\\d+mg"]
+        }
+      )
+    end
+
+    it "round-trips through a file" do
+      path = File.join(@dir, "tokenizer.yaml")
+      TokenKit.dump_config(path)
+      expected = TokenKit.tokenize("a 5MG")
+
+      TokenKit.reset
+      TokenKit.load_config_file(path)
+      expect(TokenKit.tokenize("a 5MG")).to eq(expected)
+      expect(TokenKit.config_hash.max_gram).to eq(3)
+    end
+  end
+end
@@ -0,0 +1,60 @@
+# frozen_string_literal: true
+
+RSpec.describe "Elasticsearch analysis import" do
+  after { TokenKit.reset }
+
+  let(:settings) do
+    {
+      "settings" => {
+        "analysis" => {
+          "analyzer" => {
+            "autocomplete" => {"tokenizer" => "autocomplete", "filter" => ["lowercase"]},
+            "sku" => {"tokenizer" => "sku"}
+          },
+          "tokenizer" => {
+            "autocomplete" => {
+              "type" => "edge_ngram", "min_gram" => 2, "max_gram" => 4,
+              "token_chars" => ["letter", "digit", "punctuation", "symbol"]
+            },
+            "sku" => {"type" => "char_group", "tokenize_on_chars" => ["whitespace", "-"]}
+          }
+        }
+      }
+    }
+  end
+
+  it "registers an analyzer for each analyzer in the settings" do
+    analyzers = TokenKit.import_elasticsearch(settings)
+
+    expect(analyzers.keys).to contain_exactly(:autocomplete, :sku)
+    expect(analyzers[:autocomplete].strategy).to eq(:edge_ngram)
+    expect(TokenKit.analyzers.keys).to contain_exactly(:autocomplete, :sku)
+
+    expect(TokenKit.tokenize("Laptop", analyzer: :autocomplete)).to eq(["la", "lap", "lapt"])
+    expect(TokenKit.tokenize("AB-123 X", analyzer: :sku)).to eq(["AB", "123", "X"])
+  end
+
+  it "accepts JSON" do
+    TokenKit.import_elasticsearch(JSON.generate(settings))
+    expect(TokenKit.tokenize("Laptop", analyzer: :autocomplete)).to eq(["la", "lap", "lapt"])
+  end
+
+  it "reports every unsupported setting and registers nothing" do
+    settings["settings"]["analysis"]["analyzer"]["folded"] = {
+      "tokenizer" => "standard", "filter" => ["lowercase", "asciifolding"]
+    }
+    settings["settings"]["analysis"]["analyzer"]["english"] = {"type" => "english"}
+
+    expect { TokenKit.import_elasticsearch(settings) }.to raise_error(TokenKit::AnalysisSettingsError) { |error|
+      expect(error.message).to include("analyzer.folded.filter[1]: unsupported token filter 'asciifolding'")
+      expect(error.message).to include("analyzer.english.type: unsupported analyzer type 'english'")
+    }
+    expect(TokenKit.analyzers).to be_empty
+  end
+
+  it "rejects n-gram token_chars that split on more than whitespace" do
+    settings["settings"]["analysis"]["tokenizer"]["autocomplete"]["token_chars"] = ["letter"]
+
+    expect { TokenKit.import_elasticsearch(settings) }.to raise_error(TokenKit::AnalysisSettingsError, /token_chars/)
+  end
+end
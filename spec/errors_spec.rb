@@ -0,0 +1,96 @@
+# frozen_string_literal: true
+
+RSpec.describe "Error classes" do
+  after { TokenKit.reset }
+
+  it "derives every error from TokenKit::Error" do
+    [
+      TokenKit::ConfigurationError, TokenKit::UnknownStrategyError, TokenKit::InvalidRegexError,
+      TokenKit::RegexLimitExceededError, TokenKit::ConfigFileError, TokenKit::AnalysisSettingsError,
+      TokenKit::HuggingFaceTokenizerError, TokenKit::DictionaryError, TokenKit::UnknownAnalyzerError,
+      TokenKit::LimitExceededError, TokenKit::InvalidUtf8Error, TokenKit::InternalError
+    ].each do |error_class|
+      expect(error_class).to be < TokenKit::Error
+    end
+    expect(TokenKit::RegexLimitExceededError).to be < TokenKit::InvalidRegexError
+  end
+
+  describe TokenKit::ConfigurationError do
+    it "names the key and bounds of an impossible n-gram range" do
+      expect {
+        TokenKit.tokenize("text", strategy: :ngram, min_gram: 5, max_gram: 2)
+      }.to raise_error(TokenKit::ConfigurationError) { |error|
+        expect(error.key).to eq("max_gram")
+        expect(error.min).to eq(5)
+        expect(error.max).to eq(2)
+      }
+    end
+
+    it "names the key of an invalid limit" do
+      expect {
+        TokenKit.tokenize("text", max_tokens: -1)
+      }.to raise_error(TokenKit::ConfigurationError) { |error| expect(error.key).to eq("max_tokens") }
+    end
+  end
+
+  describe TokenKit::UnknownStrategyError do
+    it "carries the strategy" do
+      expect {
+        TokenKit.tokenize("text", strategy: :stemmer)
+      }.to raise_error(TokenKit::UnknownStrategyError) { |error|
+        expect(error.strategy).to eq("stemmer")
+        expect(error.key).to eq("strategy")
+      }
+    end
+  end
+
+  describe TokenKit::InvalidRegexError do
+    it "carries the pattern and the reason from Rust" do
+      expect {
+        TokenKit.register(:broken, preserve_patterns: ["[unclosed"])
+      }.to raise_error(TokenKit::InvalidRegexError) { |error|
+        expect(error.pattern).to eq("[unclosed")
+        expect(error.reason).to include("unclosed character class")
+      }
+    end
+  end
+
+  describe TokenKit::RegexLimitExceededError do
+    it "carries the pattern, the limit and its value" do
+      expect {
+        TokenKit.tokenize("text", strategy: :pattern, regex: '\w{50}', regex_size_limit: 10_000)
+      }.to raise_error(TokenKit::RegexLimitExceededError) { |error|
+        expect(error.pattern).to eq('\w{50}')
+        expect(error.limit).to eq("size_limit")
+        expect(error.max).to eq(10_000)
+      }
+    end
+  end
+
+  describe TokenKit::LimitExceededError do
+    it "carries the limit and its value" do
+      expect {
+        TokenKit.tokenize("one two three", max_tokens: 2)
+      }.to raise_error(TokenKit::LimitExceededError) { |error|
+        expect(error.limit).to eq("max_tokens")
+        expect(error.max).to eq(2)
+        expect(error.message).to eq("Input exceeds max_tokens (2)")
+      }
+    end
+  end
+
+  describe TokenKit::InternalError do
+    it "is not a configuration problem" do
+      expect(TokenKit::InternalError).not_to be < TokenKit::ConfigurationError
+      expect(TokenKit::InternalError.new("boom", panic_message: "boom").panic_message).to eq("boom")
+    end
+  end
+
+  describe TokenKit::UnknownAnalyzerError do
+    it "carries the name" do
+      expect {
+        TokenKit.tokenize("text", analyzer: :missing)
+      }.to raise_error(TokenKit::UnknownAnalyzerError) { |error| expect(error.name).to eq("missing") }
+    end
+  end
+end
@@ -0,0 +1,128 @@
+# frozen_string_literal: true
+
+require "tmpdir"
+
+RSpec.describe "Token filters" do
+  after { TokenKit.reset }
+
+  describe "cjk_bigram" do
+    it "turns CJK runs into overlapping bigrams" do
+      expect(TokenKit.tokenize("東京都庁", filters: [:cjk_bigram])).to eq(["東京", "京都", "都庁"])
+      expect(TokenKit.tokenize("한국어 사전", filters: [:cjk_bigram])).to eq(["한국", "국어", "사전"])
+    end
+
+    it "passes other tokens through" do
+      expect(TokenKit.tokenize("Rust 2024 東京 日", filters: [:cjk_bigram])).to eq(["rust", "2024", "東京", "日"])
+    end
+
+    it "emits unigrams when asked" do
+      tokens = TokenKit.tokenize("一二三", filters: [{type: :cjk_bigram, output_unigrams: true}])
+      expect(tokens).to eq(["一", "一二", "二", "二三", "三"])
+    end
+
+    it "gives bigrams the offsets of their characters" do
+      tokens = TokenKit.analyze("in 東京都", filters: [:cjk_bigram])
+      expect(tokens.map { |t| [t.text, t.start, t.end] }).to eq([["in", 0, 2], ["東京", 3, 9], ["京都", 6, 12]])
+    end
+
+    it "works after any strategy" do
+      tokens = TokenKit.tokenize("東京タワー tower", strategy: :whitespace, filters: ["cjk_bigram"])
+      expect(tokens).to eq(["東京", "京タ", "タワ", "ワー", "tower"])
+    end
+  end
+
+  describe "arabic, persian and hebrew filters" do
+    it "normalizes Arabic letter forms and diacritics" do
+      expect(TokenKit.tokenize("أحمد إلى مدرسةٌ", filters: [:arabic_normalization])).to eq(["احمد", "الي", "مدرسه"])
+    end
+
+    it "folds Persian letters into their Arabic forms" do
+      expect(TokenKit.tokenize("کتابی", filters: [:persian_normalization])).to eq(["كتابي"])
+    end
+
+    it "strips Hebrew points" do
+      expect(TokenKit.tokenize("שָׁלוֹם", filters: [:hebrew_normalization])).to eq(["שלום"])
+    end
+
+    it "stems Arabic after normalization" do
+      tokens = TokenKit.analyze("قرأ والمكتبة", filters: [:arabic_normalization, :arabic_stem])
+      expect(tokens.map { |t| [t.text, t.start, t.end] }).to eq([["قرا", 0, 6], ["مكتب", 7, 23]])
+    end
+  end
+
+  describe "indic_normalization" do
+    it "removes zero-width joiners and joins split vowels" do
+      expect(TokenKit.tokenize("क्\u200Dष अ\u093Eम", filters: [:indic_normalization])).to eq(["क्ष", "आम"])
+    end
+
+    it "writes nukta letters as the letter and the nukta" do
+      expect(TokenKit.tokenize("\u0958ुरान", filters: [:indic_normalization])).to eq(["\u0915\u093Cुरान"])
+    end
+  end
+
+  describe "cjk_width" do
+    it "folds full-width ASCII and half-width katakana" do
+      expect(TokenKit.tokenize("ｉＰｈｏｎｅ１５ ｶﾞｲﾄﾞ", filters: [:cjk_width])).to eq(["iphone15", "ガイド"])
+    end
+
+    it "leaves other compatibility characters alone" do
+      expect(TokenKit.tokenize("½ ①", filters: [:cjk_width])).to eq(["½", "①"])
+    end
+
+    it "runs before the strategy as a char filter" do
+      tokens = TokenKit.analyze("ｶﾞｲﾄﾞ ５０ｍｇ", char_filters: [:cjk_width], preserve_patterns: [/\d+mg/])
+
+      expect(tokens.map(&:text)).to eq(["ガイド", "50mg"])
+      expect(tokens.map { |t| [t.start, t.end] }).to eq([[0, 15], [16, 28]])
+    end
+
+    it "is kept on the configuration as a char filter" do
+      TokenKit.configure { |c| c.char_filters = [:cjk_width] }
+
+      expect(TokenKit.config_hash.char_filters).to eq([:cjk_width])
+      expect(TokenKit.config_hash.to_h["char_filters"]).to eq([{"type" => "cjk_width"}])
+      expect(TokenKit.tokenize("ＡＢＣ")).to eq(["abc"])
+    end
+
+    it "rejects unknown char filters" do
+      expect {
+        TokenKit.tokenize("text", char_filters: [:cjk_bigram])
+      }.to raise_error(TokenKit::ConfigurationError, /Invalid char filter: cjk_bigram/) { |error| expect(error.key).to eq("char_filters") }
+    end
+  end
+
+  it "is kept on the configuration" do
+    TokenKit.configure { |c| c.filters = [:cjk_bigram] }
+
+    expect(TokenKit.config_hash.filters).to eq([:cjk_bigram])
+    expect(TokenKit.config_hash.to_h["filters"]).to eq([{"type" => "cjk_bigram"}])
+    expect(TokenKit.tokenize("東京都")).to eq(["東京", "京都"])
+  end
+
+  it "rejects unknown filters" do
+    expect {
+      TokenKit.tokenize("text", filters: [:cjk_bigrams])
+    }.to raise_error(TokenKit::ConfigurationError, /Invalid filter: cjk_bigrams/) { |error| expect(error.key).to eq("filters") }
+  end
+
+  it "reports misspelled filter options" do
+    diagnostics = TokenKit.validate(filters: [{type: :cjk_bigram, output_unigram: true}])
+
+    expect(diagnostics.map(&:path)).to eq(["filters[0].output_unigram"])
+    expect(diagnostics.first.suggestion).to eq("output_unigrams")
+  end
+
+  it "round-trips through config files" do
+    TokenKit.configure { |c| c.filters = [{type: :cjk_bigram, output_unigrams: true}] }
+
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "tokenizer.yml")
+      TokenKit.dump_config(path)
+      TokenKit.reset
+
+      config = TokenKit.load_config_file(path)
+      expect(config.filters).to eq([{"type" => "cjk_bigram", "output_unigrams" => true}])
+      expect(TokenKit.tokenize("東京")).to eq(["東", "東京", "京"])
+    end
+  end
+end
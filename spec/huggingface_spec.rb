@@ -0,0 +1,106 @@
+# frozen_string_literal: true
+
+require "json"
+require "tmpdir"
+
+RSpec.describe "Hugging Face tokenizer.json" do
+  after { TokenKit.reset }
+
+  let(:fixtures) { File.expand_path("../crates/tokenkit-core/tests/fixtures/huggingface", __dir__) }
+  let(:bert) { File.join(fixtures, "bert", "tokenizer.json") }
+
+  it "tokenizes into the model's subword tokens" do
+    TokenKit.configure do |config|
+      config.strategy = :huggingface
+      config.path = bert
+    end
+
+    expect(TokenKit.tokenize("Hello, world!")).to eq(["[CLS]", "hello", ",", "world", "!", "[SEP]"])
+    expect(TokenKit.config_hash.huggingface?).to be true
+    expect(TokenKit.config_hash.path).to eq(bert)
+  end
+
+  it "can leave out special tokens" do
+    tokens = TokenKit.tokenize("Hello, world!", strategy: :huggingface, path: bert, add_special_tokens: false)
+    expect(tokens).to eq(["hello", ",", "world", "!"])
+  end
+
+  it "matches the reference implementation's tokens" do
+    %w[bert roberta unigram llama llama3 suffix_bpe].each do |name|
+      TokenKit.register(name, strategy: :huggingface, path: File.join(fixtures, name, "tokenizer.json"))
+      expected = JSON.parse(File.read(File.join(fixtures, name, "expected.json")))
+
+      expected.each do |example|
+        text = example["text"]
+        expect(TokenKit.tokenize(text, analyzer: name)).to eq(example["tokens"]), "#{name}: #{text.inspect}"
+      end
+    end
+  end
+
+  it "rejects remove_punctuation" do
+    expect {
+      TokenKit.tokenize("text", strategy: :huggingface, path: bert, remove_punctuation: true)
+    }.to raise_error(TokenKit::ConfigurationError, /remove_punctuation is not supported/)
+  end
+
+  describe TokenKit::HuggingFaceTokenizer do
+    it "encodes and decodes like the reference implementation" do
+      %w[bert roberta unigram llama llama3 suffix_bpe].each do |name|
+        tokenizer = described_class.new(File.join(fixtures, name, "tokenizer.json"))
+        expected = JSON.parse(File.read(File.join(fixtures, name, "expected.json")))
+
+        expected.each do |example|
+          encoding = tokenizer.encode(example["text"])
+          expect(encoding.ids).to eq(example["ids"]), "#{name}: #{example["text"].inspect}"
+          expect(encoding.tokens).to eq(example["tokens"]), "#{name}: #{example["text"].inspect}"
+          expect(encoding.offsets).to eq(example["offsets"]), "#{name}: #{example["text"].inspect}"
+          expect(tokenizer.decode(encoding.ids)).to eq(example["decoded"]), "#{name}: #{example["text"].inspect}"
+        end
+      end
+    end
+
+    it "can leave out special tokens" do
+      tokenizer = described_class.new(bert)
+      encoding = tokenizer.encode("Hello, world!", add_special_tokens: false)
+
+      expect(encoding.tokens).to eq(["hello", ",", "world", "!"])
+      expect(encoding.offsets.first).to eq([0, 5])
+      expect(tokenizer.decode(tokenizer.encode("Hello").ids, skip_special_tokens: false)).to eq("[CLS] hello [SEP]")
+    end
+
+    it "raises for a file it can't load" do
+      expect {
+        described_class.new(File.join(fixtures, "missing", "tokenizer.json"))
+      }.to raise_error(TokenKit::HuggingFaceTokenizerError, /cannot read/)
+    end
+  end
+
+  it "requires a path" do
+    expect {
+      TokenKit.tokenize("text", strategy: :huggingface)
+    }.to raise_error(TokenKit::Error, /requires a tokenizer.json path/)
+  end
+
+  it "rejects preserve patterns" do
+    expect {
+      TokenKit.tokenize("text", strategy: :huggingface, path: bert, preserve_patterns: [/\d+mg/])
+    }.to raise_error(TokenKit::Error, /does not support preserve_patterns/)
+  end
+
+  it "reports unsupported components by name" do
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "tokenizer.json")
+      File.write(path, JSON.generate(
+        "normalizer" => {"type" => "Precompiled", "precompiled_charsmap" => ""},
+        "model" => {"type" => "WordLevel", "vocab" => {}, "unk_token" => "[UNK]"}
+      ))
+
+      expect {
+        TokenKit.tokenize("text", strategy: :huggingface, path: path)
+      }.to raise_error(TokenKit::HuggingFaceTokenizerError) { |error|
+        expect(error.message).to include("normalizer: unsupported normalizer 'Precompiled'")
+        expect(error.message).to include("model: unsupported model 'WordLevel'")
+      }
+    end
+  end
+end
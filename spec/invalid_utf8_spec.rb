@@ -0,0 +1,65 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Invalid UTF-8 input" do
+  after { TokenKit.reset }
+
+  let(:broken) { "caf\xE9 na\xC3\xAFve \xFF\xFEok".dup.force_encoding(Encoding::UTF_8) }
+
+  it "raises by default, with the offset of the first bad byte" do
+    expect { TokenKit.tokenize(broken) }.to raise_error(TokenKit::InvalidUtf8Error) { |error|
+      expect(error.position).to eq(3)
+      expect(error.message).to eq("Input is not valid UTF-8: invalid byte at offset 3")
+    }
+  end
+
+  it "replaces invalid bytes with U+FFFD" do
+    tokens = TokenKit.tokenize(broken, strategy: :whitespace, invalid_utf8: :replace)
+    expect(tokens).to eq(["caf\u{FFFD}", "naïve", "\u{FFFD}\u{FFFD}ok"])
+  end
+
+  it "skips invalid bytes" do
+    expect(TokenKit.tokenize(broken, invalid_utf8: :skip)).to eq(["caf", "naïve", "ok"])
+  end
+
+  it "decodes binary strings as UTF-8" do
+    body = "naïve caf\xE9".b
+
+    expect(TokenKit.tokenize("naïve café".b)).to eq(["naïve", "café"])
+    expect(TokenKit.tokenize(body, invalid_utf8: :skip)).to eq(["naïve", "caf"])
+    expect { TokenKit.tokenize(body) }.to raise_error(TokenKit::InvalidUtf8Error)
+  end
+
+  it "transcodes other encodings, applying the policy to what can't be" do
+    latin1 = "café".encode(Encoding::ISO_8859_1)
+    expect(TokenKit.tokenize(latin1)).to eq(["café"])
+
+    sjis = "\x82\xA0\xFF".dup.force_encoding(Encoding::Shift_JIS)
+    expect(TokenKit.tokenize(sjis, strategy: :whitespace, invalid_utf8: :replace)).to eq(["あ\u{FFFD}"])
+    expect(TokenKit.tokenize(sjis, strategy: :whitespace, invalid_utf8: :skip)).to eq(["あ"])
+  end
+
+  it "applies to the global configuration and registered analyzers" do
+    TokenKit.configure { |config| config.invalid_utf8 = :skip }
+    TokenKit.register(:lenient, invalid_utf8: :replace, strategy: :whitespace)
+
+    expect(TokenKit.tokenize(broken)).to eq(["caf", "naïve", "ok"])
+    expect(TokenKit.tokenize(broken, analyzer: :lenient)).to eq(["caf\u{FFFD}", "naïve", "\u{FFFD}\u{FFFD}ok"])
+    expect(TokenKit.config_hash.invalid_utf8).to eq(:skip)
+  end
+
+  it "applies while streaming" do
+    expect(TokenKit.each_token(broken, invalid_utf8: :skip).to_a).to eq(["caf", "naïve", "ok"])
+    expect(TokenKit.each_token(StringIO.new(broken), invalid_utf8: :skip).to_a).to eq(["caf", "naïve", "ok"])
+    expect { TokenKit.each_token(StringIO.new(broken)).to_a }.to raise_error(TokenKit::InvalidUtf8Error)
+  end
+
+  it "rejects unknown policies" do
+    expect {
+      TokenKit.tokenize("text", invalid_utf8: :ignore)
+    }.to raise_error(TokenKit::ConfigurationError, /Invalid invalid_utf8 policy/) { |error|
+      expect(error.key).to eq("invalid_utf8")
+    }
+  end
+end
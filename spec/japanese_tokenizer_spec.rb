@@ -0,0 +1,85 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Japanese Tokenizer" do
+  after { TokenKit.reset }
+
+  let(:fixtures) { File.expand_path("../crates/tokenkit-core/tests/fixtures/japanese", __dir__) }
+  let(:dictionary) { File.join(fixtures, "ipadic") }
+
+  it "takes the cheapest path through the dictionary" do
+    tokens = TokenKit.tokenize("すもももももももものうち", strategy: :japanese, dictionary: dictionary)
+    expect(tokens).to eq(["すもも", "も", "もも", "も", "もも", "の", "うち"])
+  end
+
+  it "drops punctuation and whitespace" do
+    expect(TokenKit.tokenize("東京、 食べた。", strategy: :japanese, dictionary: dictionary)).to eq(["東京", "食べ", "た"])
+  end
+
+  it "analyzes tokens with their offsets and attributes" do
+    tokens = TokenKit.analyze("東京で食べた", strategy: :japanese, dictionary: dictionary)
+
+    expect(tokens.map(&:text)).to eq(["東京", "で", "食べ", "た"])
+    expect(tokens[2]).to eq(TokenKit::Token.new("食べ", 9, 15, "動詞-自立", "食べる", "タベ"))
+    expect(tokens[0].part_of_speech).to eq("名詞-固有名詞-地域-一般")
+    expect(tokens[0].reading).to eq("トウキョウ")
+  end
+
+  it "leaves attributes out for other strategies" do
+    expect(TokenKit.analyze("Hello world")).to eq([
+      TokenKit::Token.new("hello", 0, 5),
+      TokenKit::Token.new("world", 6, 11)
+    ])
+  end
+
+  it "analyzes with registered analyzers and tokenizer instances" do
+    TokenKit.register(:ja, strategy: :japanese, dictionary: dictionary)
+    tokenizer = TokenKit::Tokenizer.new(strategy: :japanese, dictionary: dictionary)
+
+    expect(TokenKit.analyze("食べた", analyzer: :ja).map(&:base_form)).to eq(["食べる", "た"])
+    expect(tokenizer.analyze("食べた")).to eq(TokenKit.analyze("食べた", analyzer: :ja))
+  end
+
+  it "segments user dictionary words as given" do
+    tokens = TokenKit.analyze(
+      "関西国際空港に行った",
+      strategy: :japanese, dictionary: dictionary, user_dictionaries: [File.join(fixtures, "user.csv")]
+    )
+
+    expect(tokens.map(&:text)).to eq(["関西", "国際", "空港", "に", "行っ", "た"])
+    expect(tokens[1].part_of_speech).to eq("カスタム名詞")
+    expect(tokens[1].reading).to eq("コクサイ")
+  end
+
+  it "requires a dictionary" do
+    expect {
+      TokenKit.configure { |c| c.strategy = :japanese }
+    }.to raise_error(TokenKit::ConfigurationError) { |error| expect(error.key).to eq("dictionary") }
+  end
+
+  it "reports the file of a malformed user dictionary" do
+    path = File.join(fixtures, "broken_user.csv")
+
+    expect {
+      TokenKit::Tokenizer.new(strategy: :japanese, dictionary: dictionary, user_dictionaries: [path])
+    }.to raise_error(TokenKit::DictionaryError, /line 1/) { |error| expect(error.path).to eq(path) }
+  end
+
+  it "streams IO input" do
+    tokenizer = TokenKit::Tokenizer.new(strategy: :japanese, dictionary: dictionary)
+    io = StringIO.new("東京で食べた。すもももももももものうち")
+
+    expect(tokenizer.each_token(io).to_a).to eq(tokenizer.tokenize(io.string))
+  end
+
+  it "exposes its options on the configuration" do
+    TokenKit.configure do |c|
+      c.strategy = :japanese
+      c.dictionary = dictionary
+    end
+
+    expect(TokenKit.config_hash).to be_japanese
+    expect(TokenKit.config_hash.dictionary).to eq(dictionary)
+  end
+end
@@ -0,0 +1,25 @@
+# frozen_string_literal: true
+
+RSpec.describe "Language detection" do
+  it "detects the language with a confidence score" do
+    language = TokenKit.detect_language("Die Katze schläft auf dem Sofa, während die Kinder im Garten spielen.")
+
+    expect(language.code).to eq("deu")
+    expect(language.name).to eq("German")
+    expect(language.confidence).to be_between(0.0, 1.0)
+    expect(language).to be_reliable
+  end
+
+  it "tells apart languages in other scripts" do
+    expect(TokenKit.detect_language("Быстрая коричневая лиса прыгает через ленивую собаку").code).to eq("rus")
+    expect(TokenKit.detect_language("素早い茶色の狐がのろまな犬を飛び越える").code).to eq("jpn")
+  end
+
+  it "flags guesses from short text as unreliable" do
+    expect(TokenKit.detect_language("ok")).not_to be_reliable
+  end
+
+  it "returns nil for text without letters" do
+    expect(TokenKit.detect_language("123 !?")).to be_nil
+  end
+end
@@ -0,0 +1,126 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Resource limits" do
+  after { TokenKit.reset }
+
+  describe "max_input_bytes" do
+    it "raises for input over the limit" do
+      expect {
+        TokenKit.tokenize("a little longer", max_input_bytes: 10)
+      }.to raise_error(TokenKit::LimitExceededError, /max_input_bytes \(10\)/)
+    end
+
+    it "tokenizes the start of the input when truncating" do
+      tokens = TokenKit.tokenize("a little longer", strategy: :whitespace, max_input_bytes: 10, overflow: :truncate)
+      expect(tokens).to eq(["a", "little"])
+    end
+  end
+
+  describe "max_tokens" do
+    it "raises when there are more tokens" do
+      expect {
+        TokenKit.tokenize("one two three", max_tokens: 2)
+      }.to raise_error(TokenKit::LimitExceededError, /max_tokens \(2\)/)
+    end
+
+    it "returns the first tokens when truncating" do
+      tokens = TokenKit.tokenize("one two three", max_tokens: 2, overflow: :truncate)
+      expect(tokens).to eq(["one", "two"])
+    end
+
+    it "stops n-gram generation early" do
+      text = "abcdefghij" * 100_000
+      tokens = TokenKit.tokenize(text, strategy: :ngram, min_gram: 1, max_gram: 10, max_tokens: 5, overflow: :truncate)
+      expect(tokens).to eq(["a", "b", "c", "d", "e"])
+    end
+
+    it "applies to each_token" do
+      expect {
+        TokenKit.each_token("one two three", max_tokens: 2) { |_| }
+      }.to raise_error(TokenKit::LimitExceededError, /max_tokens/)
+    end
+
+    it "counts tokens across every chunk of an IO" do
+      text = "word " * 20_000
+
+      expect {
+        TokenKit.each_token(StringIO.new(text), max_tokens: 15_000) { |_| }
+      }.to raise_error(TokenKit::LimitExceededError, /max_tokens/)
+      expect(TokenKit.each_token(StringIO.new(text), max_tokens: 15_000, overflow: :truncate).count).to eq(15_000)
+    end
+  end
+
+  describe "max_token_length" do
+    it "truncates long tokens by default" do
+      expect(TokenKit.tokenize("tiny enormous", max_token_length: 4)).to eq(["tiny", "enor"])
+    end
+
+    it "can drop them instead" do
+      expect(TokenKit.tokenize("tiny enormous", max_token_length: 4, long_tokens: :drop)).to eq(["tiny"])
+    end
+  end
+
+  it "applies limits from the global configuration" do
+    TokenKit.configure do |config|
+      config.max_tokens = 1
+      config.overflow = :truncate
+    end
+
+    expect(TokenKit.tokenize("one two")).to eq(["one"])
+    expect(TokenKit.config_hash.max_tokens).to eq(1)
+    expect(TokenKit.config_hash.overflow).to eq(:truncate)
+  end
+
+  it "is unlimited by default" do
+    config = TokenKit.config_hash
+    expect(config.max_input_bytes).to be_nil
+    expect(config.max_tokens).to be_nil
+    expect(config.max_token_length).to be_nil
+    expect(config.overflow).to eq(:error)
+    expect(config.long_tokens).to eq(:truncate)
+  end
+
+  it "rejects limits that are not positive integers" do
+    expect { TokenKit.tokenize("text", max_tokens: 0) }.to raise_error(TokenKit::Error, /max_tokens must be a positive integer/)
+    expect { TokenKit.tokenize("text", max_input_bytes: "10") }.to raise_error(TokenKit::Error, /max_input_bytes/)
+  end
+
+  it "rejects unknown policies" do
+    expect { TokenKit.tokenize("text", overflow: :ignore) }.to raise_error(TokenKit::Error, /Invalid overflow policy/)
+    expect { TokenKit.tokenize("text", long_tokens: :split) }.to raise_error(TokenKit::Error, /Invalid long_tokens policy/)
+  end
+
+  describe "regex limits" do
+    it "rejects patterns over regex_size_limit" do
+      expect {
+        TokenKit.tokenize("text", strategy: :pattern, regex: '\w{50}', regex_size_limit: 10_000)
+      }.to raise_error(TokenKit::RegexLimitExceededError, /'\\w\{50\}' exceeds size_limit \(10000\)/)
+    end
+
+    it "applies to preserve patterns" do
+      expect {
+        TokenKit.tokenize("text", preserve_patterns: [/\w{50}/], regex_size_limit: 10_000)
+      }.to raise_error(TokenKit::RegexLimitExceededError, /exceeds size_limit/)
+    end
+
+    it "limits the number of preserve patterns" do
+      expect {
+        TokenKit.tokenize("text", preserve_patterns: [/a/, /b/, /c/], max_preserve_patterns: 2)
+      }.to raise_error(TokenKit::RegexLimitExceededError, /exceeds max_preserve_patterns \(2\)/)
+    end
+
+    it "keeps the limits in the configuration" do
+      TokenKit.configure do |config|
+        config.regex_size_limit = 100_000
+        config.regex_dfa_size_limit = 1_000_000
+        config.max_preserve_patterns = 10
+      end
+
+      expect(TokenKit.config_hash.regex_size_limit).to eq(100_000)
+      expect(TokenKit.config_hash.max_preserve_patterns).to eq(10)
+      expect(TokenKit.tokenize("Hello")).to eq(["hello"])
+    end
+  end
+end
@@ -0,0 +1,33 @@
+# frozen_string_literal: true
+
+RSpec.describe "Locale-aware lowercasing" do
+  after { TokenKit.reset }
+
+  it "uses Turkish dotted and dotless i" do
+    expect(TokenKit.tokenize("İSTANBUL IRMAK", locale: "tr")).to eq(["istanbul", "ırmak"])
+    expect(TokenKit.tokenize("İSTANBUL IRMAK")).to eq(["i̇stanbul", "irmak"])
+  end
+
+  it "applies in the sentence and path hierarchy strategies" do
+    expect(TokenKit.tokenize("IRMAK TAŞTI.", strategy: :sentence, locale: :tr)).to eq(["ırmak taştı."])
+    expect(TokenKit.tokenize("/İL/IRMAK", strategy: :path_hierarchy, locale: "az")).to eq(["/il", "/il/ırmak"])
+  end
+
+  it "ends Greek words with a final sigma" do
+    expect(TokenKit.tokenize("ΟΔΟΣ ΣΟΦΙΑΣ", strategy: :lowercase)).to eq(["οδος", "σοφιας"])
+  end
+
+  it "keeps the locale in the global configuration" do
+    TokenKit.configure do |config|
+      config.locale = "tr"
+    end
+
+    expect(TokenKit.config.locale).to eq("tr")
+    expect(TokenKit.tokenize("KIŞ")).to eq(["kış"])
+  end
+
+  it "rejects malformed locales" do
+    expect(TokenKit.validate(locale: "turkish").map(&:path)).to eq(["locale"])
+    expect(TokenKit.validate(strategy: :bytes, locale: "tr").map(&:message)).to include(/not supported by the bytes strategy/)
+  end
+end
@@ -38,6 +38,11 @@ RSpec.describe "Pattern Preservation Edge Cases" do
       expect(tokens).to eq(["take", "100mg"])
     end
 
+    it "still lowercases text without any match" do
+      tokens = TokenKit.tokenize("Take Daily")
+      expect(tokens).to eq(["take", "daily"])
+    end
+
     it "preserves pattern as only token" do
       tokens = TokenKit.tokenize("100mg")
       expect(tokens).to eq(["100mg"])
@@ -66,7 +66,7 @@ RSpec.describe "Pattern Preservation" do
         TokenKit.configure do |config|
           config.preserve_patterns = ["[invalid(regex"]
         end
-      }.to raise_error(RegexpError, /Invalid regex pattern/)
+      }.to raise_error(TokenKit::InvalidRegexError, /Invalid regex pattern/)
     end
   end
 end
@@ -0,0 +1,81 @@
+# frozen_string_literal: true
+
+require "tmpdir"
+
+RSpec.describe "Router strategy" do
+  after { TokenKit.reset }
+
+  let(:routes) do
+    [
+      {scripts: [:han], strategy: :cjk},
+      {scripts: [:latin], strategy: :whitespace, lowercase: false}
+    ]
+  end
+
+  it "sends each script to its route's tokenizer" do
+    tokens = TokenKit.tokenize("Hello World 我来到北京清华大学", strategy: :router, routes: routes)
+    expect(tokens).to eq(["Hello", "World", "我", "来到", "北京", "清华大学"])
+  end
+
+  it "uses the fallback for scripts without a route" do
+    tokens = TokenKit.tokenize("Hello ภาษาไทย", strategy: :router, routes: routes, fallback: {strategy: :keyword})
+    expect(tokens).to eq(["Hello", "ภาษาไทย"])
+
+    # The default Unicode strategy without a fallback
+    expect(TokenKit.tokenize("Hello ภาษาไทย", strategy: :router, routes: routes)).to eq(["Hello", "ภาษา", "ไทย"])
+  end
+
+  it "keeps offsets into the whole input" do
+    text = "Hello 北京 World"
+    tokens = TokenKit.analyze(text, strategy: :router, routes: routes)
+
+    expect(tokens.map { |t| text.byteslice(t.start...t.end) }).to eq(["Hello", "北京", "World"])
+  end
+
+  it "works as a registered analyzer and keeps its routes" do
+    config = TokenKit.register(:mixed, strategy: :router, routes: routes)
+
+    expect(config.routes.size).to eq(2)
+    expect(TokenKit.tokenize("Tokyo 北京", analyzer: :mixed)).to eq(["Tokyo", "北京"])
+  end
+
+  it "round trips through config files" do
+    Dir.mktmpdir do |dir|
+      path = File.join(dir, "router.yml")
+      TokenKit.configure do |config|
+        config.strategy = :router
+        config.routes = routes
+      end
+      TokenKit.dump_config(path)
+      TokenKit.reset
+      TokenKit.load_config_file(path)
+
+      expect(TokenKit.tokenize("Hello 北京")).to eq(["Hello", "北京"])
+    end
+  end
+
+  it "limits routes to the detected language" do
+    language_routes = [{languages: [:english], strategy: :whitespace}]
+    options = {strategy: :router, routes: language_routes, fallback: {strategy: :keyword}}
+
+    expect(TokenKit.tokenize("The cat sleeps on the sofa", **options)).to eq(%w[the cat sleeps on the sofa])
+    expect(TokenKit.tokenize("Die Katze schläft auf dem Sofa", **options)).to eq(["die katze schläft auf dem sofa"])
+  end
+
+  it "requires a route" do
+    expect {
+      TokenKit.configure { |c| c.strategy = :router }
+    }.to raise_error(TokenKit::ConfigurationError) { |error| expect(error.key).to eq("routes") }
+  end
+
+  it "reports problems inside routes with their path" do
+    diagnostics = TokenKit.validate(
+      strategy: :router,
+      routes: [{scripts: [:klingon], strategy: :ngram, min_gram: 3, max_gram: 1, min_grams: 2}]
+    )
+
+    expect(diagnostics.map(&:path)).to include(
+      "routes[0].tokenizer.min_grams", "routes[0].scripts", "routes[0].tokenizer.max_gram"
+    )
+  end
+end
@@ -0,0 +1,98 @@
+# frozen_string_literal: true
+
+require "stringio"
+
+RSpec.describe "Streaming tokenization" do
+  after { TokenKit.reset }
+
+  describe "TokenKit.each_token" do
+    it "yields the same tokens as tokenize" do
+      text = "Hello, World! This is a test of café and 世界."
+      yielded = []
+      TokenKit.each_token(text) { |token| yielded << token }
+      expect(yielded).to eq(TokenKit.tokenize(text))
+    end
+
+    it "returns an Enumerator without a block" do
+      enum = TokenKit.each_token("one two three")
+      expect(enum).to be_a(Enumerator)
+      expect(enum.first(2)).to eq(["one", "two"])
+    end
+
+    it "accepts per-call options" do
+      tokens = TokenKit.each_token("Hello World", lowercase: false).to_a
+      expect(tokens).to eq(["Hello", "World"])
+    end
+
+    it "uses the global configuration" do
+      TokenKit.configure do |config|
+        config.strategy = :edge_ngram
+        config.min_gram = 2
+        config.max_gram = 3
+      end
+
+      expect(TokenKit.each_token("hello").to_a).to eq(["he", "hel"])
+    end
+
+    it "applies preserve patterns" do
+      tokens = TokenKit.each_token("Took 100MG today", preserve_patterns: [/\d+mg/i]).to_a
+      expect(tokens).to eq(["took", "100MG", "today"])
+    end
+  end
+
+  describe "IO input" do
+    it "tokenizes an IO read in chunks" do
+      text = "alpha beta gamma " * 10_000
+      tokens = TokenKit.each_token(StringIO.new(text)).to_a
+      expect(tokens).to eq(TokenKit.tokenize(text))
+    end
+
+    it "keeps tokens whole across chunk boundaries" do
+      stub_const("TokenKit::STREAM_CHUNK_SIZE", 3)
+      text = "supercalifragilistic expialidocious words"
+      expect(TokenKit.each_token(StringIO.new(text)).to_a).to eq(TokenKit.tokenize(text))
+    end
+
+    it "keeps multibyte characters whole across chunk boundaries" do
+      stub_const("TokenKit::STREAM_CHUNK_SIZE", 1)
+      text = "café naïve 世界 👨‍👩‍👧‍👦"
+      expect(TokenKit.each_token(StringIO.new(text), strategy: :whitespace).to_a)
+        .to eq(TokenKit.tokenize(text, strategy: :whitespace))
+    end
+
+    it "buffers strategies that need the whole input" do
+      stub_const("TokenKit::STREAM_CHUNK_SIZE", 4)
+      tokens = TokenKit.each_token(StringIO.new("/usr/local/bin"), strategy: :path_hierarchy).to_a
+      expect(tokens).to eq(["/usr", "/usr/local", "/usr/local/bin"])
+    end
+
+    it "splits char_group input on its own split characters" do
+      stub_const("TokenKit::STREAM_CHUNK_SIZE", 2)
+      tokens = TokenKit.each_token(StringIO.new("a b,c d,e"), strategy: :char_group, split_on_chars: ",").to_a
+      expect(tokens).to eq(["a b", "c d", "e"])
+    end
+
+    it "handles sentences spanning chunks" do
+      stub_const("TokenKit::STREAM_CHUNK_SIZE", 5)
+      text = "Hello world! How are you? I am fine."
+      expect(TokenKit.each_token(StringIO.new(text), strategy: :sentence).to_a)
+        .to eq(TokenKit.tokenize(text, strategy: :sentence))
+    end
+
+    it "handles empty IO" do
+      expect(TokenKit.each_token(StringIO.new("")).to_a).to eq([])
+    end
+  end
+
+  describe "TokenKit::Tokenizer#each_token" do
+    it "streams with the instance configuration" do
+      tokenizer = TokenKit::Tokenizer.new(strategy: :ngram, min_gram: 2, max_gram: 2)
+      expect(tokenizer.each_token("abc").to_a).to eq(["ab", "bc"])
+    end
+
+    it "streams IO input" do
+      tokenizer = TokenKit::Tokenizer.new(strategy: :whitespace, lowercase: false)
+      expect(tokenizer.each_token(StringIO.new("One Two")).to_a).to eq(["One", "Two"])
+    end
+  end
+end
@@ -67,6 +67,45 @@ RSpec.describe "Thread Safety" do
       expect(results.size).to eq(10)
     end
 
+    it "always tokenizes with a complete configuration while it is being swapped" do
+      configs = [
+        {strategy: :whitespace, lowercase: false},
+        {strategy: :char_group, split_on_chars: "-", lowercase: true}
+      ]
+      # Every result must come from one config; a mix would show a torn swap
+      expected = [["Foo-Bar", "Baz"], ["foo", "bar baz"]]
+      stop = false
+
+      swapper = Thread.new do
+        until stop
+          configs.each do |opts|
+            TokenKit.configure do |config|
+              opts.each { |key, value| config.public_send(:"#{key}=", value) }
+            end
+          end
+        end
+      end
+
+      results = Array.new(4) do
+        Thread.new { Array.new(200) { TokenKit.tokenize("Foo-Bar Baz") } }
+      end.flat_map(&:value)
+
+      stop = true
+      swapper.join
+
+      expect(results - expected).to be_empty
+    end
+
+    it "keeps the previous default when a configuration fails to build" do
+      TokenKit.configure { |c| c.strategy = :whitespace }
+
+      expect {
+        TokenKit.configure { |c| c.preserve_patterns = ["[unclosed"] }
+      }.to raise_error(TokenKit::InvalidRegexError)
+
+      expect(TokenKit.tokenize("a-b c")).to eq(["a-b", "c"])
+    end
+
     it "maintains configuration as global defaults (Phase 3 complete)" do
       # After Phase 3: configure sets global defaults,
       # but each tokenize call creates its own instance
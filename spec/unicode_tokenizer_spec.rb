@@ -51,4 +51,33 @@ RSpec.describe "Unicode Tokenizer" do
       expect(tokens).to include("Anti-CD3", "antibody")
     end
   end
+
+  context "with scripts written without spaces" do
+    let(:fixtures) { File.expand_path("../crates/tokenkit-core/tests/fixtures/word_break", __dir__) }
+
+    it "breaks Thai, Lao, Khmer and Myanmar runs into words" do
+      expect(TokenKit.tokenize("ภาษาไทยง่ายนิดเดียว")).to eq(["ภาษา", "ไทย", "ง่าย", "นิด", "เดียว"])
+      expect(TokenKit.tokenize("ພາສາລາວ")).to eq(["ພາສາ", "ລາວ"])
+      expect(TokenKit.tokenize("ខ្ញុំស្រលាញ់ភាសាខ្មែរ")).to eq(["ខ្ញុំ", "ស្រលាញ់", "ភាសាខ្មែរ"])
+      expect(TokenKit.tokenize("မြန်မာစကား")).to eq(["မြန်မာ", "စကား"])
+    end
+
+    it "keeps offsets into the input" do
+      text = "Say ฉันกินข้าว"
+      tokens = TokenKit.analyze(text)
+
+      expect(tokens.map { |t| text.byteslice(t.start...t.end) }).to eq(["Say", "ฉัน", "กิน", "ข้าว"])
+    end
+
+    it "adds user dictionary words" do
+      tokens = TokenKit.tokenize("ฉันกินข้าว", word_break_dictionaries: [File.join(fixtures, "thai_user.txt")])
+      expect(tokens).to eq(["ฉัน", "กินข้าว"])
+    end
+
+    it "raises DictionaryError for a missing user dictionary" do
+      expect {
+        TokenKit::Tokenizer.new(strategy: :unicode, word_break_dictionaries: ["/missing/words.txt"])
+      }.to raise_error(TokenKit::DictionaryError)
+    end
+  end
 end
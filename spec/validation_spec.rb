@@ -0,0 +1,98 @@
+# frozen_string_literal: true
+
+RSpec.describe "Configuration validation" do
+  after { TokenKit.reset }
+
+  describe "TokenKit.validate" do
+    it "returns nothing for a valid configuration" do
+      expect(TokenKit.validate(strategy: :edge_ngram, min_gram: 2, max_gram: 5)).to eq([])
+      expect(TokenKit.validate(TokenKit.config_hash)).to eq([])
+    end
+
+    it "reports every problem with its option" do
+      diagnostics = TokenKit.validate(
+        strategy: :ngram, max_grams: 3, min_gram: 5, max_gram: 2,
+        max_tokens: 0, preserve_patterns: [/\d+mg/, "[unclosed"]
+      )
+
+      expect(diagnostics.map(&:path)).to eq(["max_grams", "max_gram", "max_tokens", "preserve_patterns[1]"])
+      expect(diagnostics).to all(be_a(TokenKit::Diagnostic))
+    end
+
+    it "suggests the option a typo was meant to be" do
+      diagnostic = TokenKit.validate(strategy: :path_hierarchy, delimeter: "|").first
+
+      expect(diagnostic.path).to eq("delimeter")
+      expect(diagnostic.suggestion).to eq("delimiter")
+      expect(diagnostic.to_s).to eq("delimeter: Unknown option 'delimeter' (did you mean 'delimiter'?)")
+    end
+
+    it "suggests strategies too" do
+      diagnostic = TokenKit.validate(strategy: :edge_ngrams).first
+
+      expect(diagnostic.path).to eq("strategy")
+      expect(diagnostic.suggestion).to eq("edge_ngram")
+    end
+
+    it "reports options the strategy doesn't use" do
+      diagnostics = TokenKit.validate(strategy: :unicode, min_gram: 3)
+
+      expect(diagnostics.map(&:to_s)).to eq(["min_gram: Option 'min_gram' is not used by the unicode strategy"])
+    end
+
+    it "reports values of the wrong type and keeps going" do
+      diagnostics = TokenKit.validate(strategy: :edge_ngram, min_gram: "two", max_tokens: 0)
+
+      expect(diagnostics.map(&:path)).to eq(["min_gram", "max_tokens"])
+    end
+
+    it "accepts string keys and option aliases" do
+      expect(TokenKit.validate("strategy" => "grapheme", "grapheme_extended" => false)).to eq([])
+    end
+
+    it "checks a builder without building it" do
+      builder = TokenKit::ConfigBuilder.new
+      builder.strategy = :path_hierarchy
+      builder.delimiter = ""
+
+      expect(TokenKit.validate(builder).map(&:path)).to eq(["delimiter"])
+    end
+  end
+
+  describe "per-call options" do
+    it "rejects unknown options with a suggestion" do
+      expect {
+        TokenKit.tokenize("a/b", strategy: :path_hierarchy, delimeter: "|")
+      }.to raise_error(TokenKit::ConfigurationError, /did you mean 'delimiter'/) { |error|
+        expect(error.key).to eq("delimeter")
+      }
+    end
+
+    it "rejects options the strategy doesn't use" do
+      expect {
+        TokenKit.tokenize("text", strategy: :whitespace, max_gram: 3)
+      }.to raise_error(TokenKit::ConfigurationError, /not used by the whitespace strategy/)
+    end
+
+    it "allows strategy options for the global strategy" do
+      TokenKit.configure { |config| config.strategy = :edge_ngram }
+
+      expect(TokenKit.tokenize("test", min_gram: 3, max_gram: 4)).to eq(["tes", "test"])
+    end
+
+    it "lists every problem when there are several" do
+      expect {
+        TokenKit.register(:broken, strategy: :unicode, delimiter: "|", max_grams: 3)
+      }.to raise_error(TokenKit::ConfigurationError) { |error|
+        expect(error.diagnostics.map(&:path)).to eq(["delimiter", "max_grams"])
+        expect(error.message).to include("delimiter: Option 'delimiter' is not used by the unicode strategy")
+      }
+    end
+
+    it "applies to tokenizer instances" do
+      expect {
+        TokenKit::Tokenizer.new(strategy: :ngram, min_grams: 2)
+      }.to raise_error(TokenKit::ConfigurationError, /did you mean 'min_gram'/)
+    end
+  end
+end